@@ -1,12 +1,54 @@
 use std::{cell::RefCell, rc::Rc};
 
 use agui_core::{
-    element::mock::{render::MockRenderWidget, DummyRenderObject, DummyWidget},
+    element::{
+        mock::{render::MockRenderWidget, DummyRenderObject, DummyWidget},
+        ElementUpdate,
+    },
     engine::Engine,
-    widget::IntoWidget,
+    widget::{IntoWidget, Widget},
 };
 use criterion::{criterion_group, criterion_main, Criterion};
 
+/// Creates a chain of `depth` render widgets, each of which creates a new child widget every time
+/// it's built so that rebuilding any one of them also rebuilds everything beneath it.
+fn nested_widget(depth: usize) -> Widget {
+    let widget = MockRenderWidget::new("NestedWidget");
+    {
+        widget
+            .mock
+            .borrow_mut()
+            .expect_children()
+            .returning_st(move || {
+                if depth > 1 {
+                    vec![nested_widget(depth - 1)]
+                } else {
+                    vec![DummyWidget.into_widget()]
+                }
+            });
+
+        widget
+            .mock
+            .borrow_mut()
+            .expect_create_render_object()
+            .returning(|| DummyRenderObject.into());
+
+        widget
+            .mock
+            .borrow_mut()
+            .expect_update()
+            .returning(|_| ElementUpdate::RebuildNecessary);
+
+        widget
+            .mock
+            .borrow_mut()
+            .expect_update_render_object()
+            .returning(|_| {});
+    }
+
+    widget.into_widget()
+}
+
 fn engine_ops(c: &mut Criterion) {
     let mut group = c.benchmark_group("engine (single)");
 
@@ -45,7 +87,7 @@ fn engine_ops(c: &mut Criterion) {
 
                 let mut engine = Engine::builder().with_root(root_widget).build();
 
-                engine.update();
+                engine.update().unwrap();
 
                 children.borrow_mut().clear();
 
@@ -131,7 +173,7 @@ fn engine_ops(c: &mut Criterion) {
 
                 let mut engine = Engine::builder().with_root(root_widget).build();
 
-                engine.update();
+                engine.update().unwrap();
 
                 children.borrow_mut().clear();
 
@@ -144,6 +186,40 @@ fn engine_ops(c: &mut Criterion) {
     });
 
     group.finish();
+
+    let mut group = c.benchmark_group("engine (nested)");
+
+    group.throughput(criterion::Throughput::Elements(100));
+
+    // Every element is marked dirty, so each one could be rebuilt once on its own and again by
+    // each of its ancestors if they weren't rebuilt in depth order.
+    group.sample_size(100).bench_function("rebuilds", |b| {
+        b.iter_with_setup(
+            || {
+                let mut engine = Engine::builder().with_root(nested_widget(100)).build();
+
+                engine.update().unwrap();
+
+                let element_ids = engine
+                    .elements()
+                    .iter_down_from(engine.root())
+                    .collect::<Vec<_>>();
+
+                for element_id in element_ids {
+                    engine.mark_dirty(element_id);
+                }
+
+                engine
+            },
+            |mut engine| {
+                engine.update().unwrap();
+
+                engine.rebuilds_saved()
+            },
+        )
+    });
+
+    group.finish();
 }
 
 criterion_group!(benches, engine_ops);
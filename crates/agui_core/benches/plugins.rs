@@ -104,7 +104,7 @@ fn plugin_ops(c: &mut Criterion) {
                     .with_root(root_widget)
                     .build();
 
-                engine.update();
+                engine.update().unwrap();
 
                 children.borrow_mut().clear();
 
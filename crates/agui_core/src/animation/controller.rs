@@ -0,0 +1,640 @@
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use crate::{
+    listenable::{Listenable, Notifier, NotifierHandle},
+    scheduler::{FrameSchedulerPlugin, Ticker},
+};
+
+use super::{Lerp, Simulation, SpringDescription, SpringSimulation, Tween};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnimationStatus {
+    /// Stopped at the beginning.
+    Dismissed,
+
+    /// Running from the beginning towards the end.
+    Forward,
+
+    /// Running from the end towards the beginning.
+    Reverse,
+
+    /// Stopped at the end.
+    Completed,
+}
+
+/// Drives a value from `0.0` to `1.0` and back again over a duration, advancing once per frame
+/// using a [`Ticker`] from the [`FrameSchedulerPlugin`]. It may also be driven by a
+/// [`Simulation`], in which case the value moves freely between its bounds.
+///
+/// Listeners are notified each frame that the value changes. Clones share the same animation.
+#[derive(Clone)]
+pub struct AnimationController {
+    inner: Rc<RefCell<ControllerInner>>,
+
+    notifier: Notifier,
+}
+
+struct ControllerInner {
+    ticker: Option<Ticker>,
+
+    duration: Duration,
+    reverse_duration: Option<Duration>,
+
+    lower_bound: f32,
+    upper_bound: f32,
+
+    value: f32,
+    status: AnimationStatus,
+
+    run: Option<Run>,
+}
+
+#[derive(Clone)]
+enum Run {
+    To {
+        from: f32,
+        to: f32,
+        duration: Duration,
+    },
+
+    Repeat {
+        reverse: bool,
+        period: Duration,
+    },
+
+    Simulation(Rc<dyn Simulation>),
+}
+
+impl AnimationController {
+    pub fn new(scheduler: &FrameSchedulerPlugin, duration: Duration) -> Self {
+        let inner = Rc::new(RefCell::new(ControllerInner {
+            ticker: None,
+
+            duration,
+            reverse_duration: None,
+
+            lower_bound: 0.0,
+            upper_bound: 1.0,
+
+            value: 0.0,
+            status: AnimationStatus::Dismissed,
+
+            run: None,
+        }));
+
+        let notifier = Notifier::new();
+
+        // The ticker only holds a weak reference, otherwise the controller would be kept alive by
+        // the scheduler and never be dropped.
+        let ticker = scheduler.create_ticker_with({
+            let inner = Rc::downgrade(&inner);
+            let notifier = notifier.clone();
+
+            move |elapsed| {
+                let Some(inner) = inner.upgrade() else {
+                    return;
+                };
+
+                let is_changed = inner.borrow_mut().tick(elapsed);
+
+                if is_changed {
+                    notifier.notify_listeners();
+                }
+            }
+        });
+
+        inner.borrow_mut().ticker = Some(ticker);
+
+        Self { inner, notifier }
+    }
+
+    /// Sets the duration used when running in reverse, which otherwise matches the forward
+    /// duration.
+    pub fn with_reverse_duration(self, reverse_duration: Duration) -> Self {
+        self.inner.borrow_mut().reverse_duration = Some(reverse_duration);
+        self
+    }
+
+    /// Sets the range that the value may take, which is otherwise `0.0` to `1.0`. Infinite bounds
+    /// may be used for values that move freely, such as a scroll offset.
+    pub fn with_bounds(self, lower_bound: f32, upper_bound: f32) -> Self {
+        {
+            let mut inner = self.inner.borrow_mut();
+
+            inner.lower_bound = lower_bound;
+            inner.upper_bound = upper_bound;
+
+            inner.value = inner.value.clamp(lower_bound, upper_bound);
+        }
+
+        self
+    }
+
+    pub fn value(&self) -> f32 {
+        self.inner.borrow().value
+    }
+
+    pub fn status(&self) -> AnimationStatus {
+        self.inner.borrow().status
+    }
+
+    pub fn duration(&self) -> Duration {
+        self.inner.borrow().duration
+    }
+
+    /// Changes the duration of the animation, which takes effect the next time it is run.
+    pub fn set_duration(&self, duration: Duration) {
+        self.inner.borrow_mut().duration = duration;
+    }
+
+    pub fn is_animating(&self) -> bool {
+        self.inner.borrow().run.is_some()
+    }
+
+    /// Stops the animation and jumps to the given value, notifying listeners.
+    pub fn set_value(&self, value: f32) {
+        {
+            let mut inner = self.inner.borrow_mut();
+
+            inner.stop();
+
+            inner.value = value.clamp(inner.lower_bound, inner.upper_bound);
+            inner.status = inner.status_at(inner.value, inner.status);
+        }
+
+        self.notify_listeners();
+    }
+
+    /// Stops the animation and jumps back to the beginning.
+    pub fn reset(&self) {
+        let lower_bound = self.inner.borrow().lower_bound;
+
+        self.set_value(lower_bound);
+    }
+
+    /// Runs the animation from its current value towards the end.
+    pub fn forward(&self) {
+        let upper_bound = self.inner.borrow().upper_bound;
+
+        self.animate_to(upper_bound);
+    }
+
+    /// Runs the animation from its current value towards the beginning.
+    pub fn reverse(&self) {
+        let lower_bound = self.inner.borrow().lower_bound;
+
+        self.animate_to(lower_bound);
+    }
+
+    /// Runs the animation from its current value to the target. The time taken is proportional
+    /// to the distance travelled, so running half way takes half of the duration.
+    pub fn animate_to(&self, target: f32) {
+        let is_finished = {
+            let mut inner = self.inner.borrow_mut();
+
+            let target = target.clamp(inner.lower_bound, inner.upper_bound);
+
+            let from = inner.value;
+
+            let (status, duration) = if target < from {
+                (
+                    AnimationStatus::Reverse,
+                    inner.reverse_duration.unwrap_or(inner.duration),
+                )
+            } else {
+                (AnimationStatus::Forward, inner.duration)
+            };
+
+            let range = inner.upper_bound - inner.lower_bound;
+            let distance = (target - from).abs();
+
+            // Unbounded controllers have no range to be proportional to, so take the full duration.
+            // If the bounds are equal there's nowhere to go, so the run completes immediately.
+            let duration = if !range.is_finite() {
+                duration
+            } else if range > 0.0 && distance > 0.0 {
+                duration.mul_f32(distance / range)
+            } else {
+                Duration::ZERO
+            };
+
+            if duration.is_zero() || target == from {
+                inner.stop();
+
+                inner.value = target;
+                inner.status = inner.status_at(target, status);
+
+                true
+            } else {
+                inner.status = status;
+
+                inner.start(Run::To {
+                    from,
+                    to: target,
+                    duration,
+                });
+
+                false
+            }
+        };
+
+        if is_finished {
+            self.notify_listeners();
+        }
+    }
+
+    /// Runs the animation from the beginning to the end indefinitely. If `reverse` is `true`,
+    /// every other cycle runs from the end back to the beginning.
+    ///
+    /// A controller with a zero duration has no cycle to repeat, so it jumps straight to the end.
+    pub fn repeat(&self, reverse: bool) {
+        let is_finished = {
+            let mut inner = self.inner.borrow_mut();
+
+            let period = inner.duration;
+
+            if period.is_zero() {
+                inner.stop();
+
+                inner.value = inner.upper_bound;
+                inner.status = AnimationStatus::Completed;
+
+                true
+            } else {
+                inner.status = AnimationStatus::Forward;
+
+                inner.start(Run::Repeat { reverse, period });
+
+                false
+            }
+        };
+
+        if is_finished {
+            self.notify_listeners();
+        }
+    }
+
+    /// Drives the value using the simulation until it comes to rest. The value is kept within the
+    /// controller's bounds.
+    pub fn animate_with(&self, simulation: impl Simulation + 'static) {
+        let mut inner = self.inner.borrow_mut();
+
+        inner.status = if simulation.velocity(0.0) < 0.0 {
+            AnimationStatus::Reverse
+        } else {
+            AnimationStatus::Forward
+        };
+
+        inner.start(Run::Simulation(Rc::new(simulation)));
+    }
+
+    /// Flings the value towards the upper bound, or towards the lower bound if the velocity is
+    /// negative, using a critically damped spring.
+    pub fn fling(&self, velocity: f32) {
+        let (value, target) = {
+            let inner = self.inner.borrow();
+
+            let target = if velocity < 0.0 {
+                inner.lower_bound
+            } else {
+                inner.upper_bound
+            };
+
+            (inner.value, target)
+        };
+
+        self.animate_with(SpringSimulation::new(
+            SpringDescription::with_damping_ratio(1.0, 500.0, 1.0),
+            value,
+            target,
+            velocity,
+        ));
+    }
+
+    /// Stops the animation at its current value.
+    pub fn stop(&self) {
+        self.inner.borrow_mut().stop();
+    }
+
+    /// Returns the value of the tween at the current point in the animation.
+    pub fn evaluate<T>(&self, tween: &Tween<T>) -> T
+    where
+        T: Lerp,
+    {
+        tween.transform(self.value())
+    }
+}
+
+impl ControllerInner {
+    fn status_at(&self, value: f32, status: AnimationStatus) -> AnimationStatus {
+        if value == self.lower_bound {
+            AnimationStatus::Dismissed
+        } else if value == self.upper_bound {
+            AnimationStatus::Completed
+        } else {
+            status
+        }
+    }
+
+    fn start(&mut self, run: Run) {
+        self.run = Some(run);
+
+        if let Some(ticker) = &self.ticker {
+            // Restart the ticker so that the elapsed time is relative to the new run
+            ticker.stop();
+            ticker.start();
+        }
+    }
+
+    fn stop(&mut self) {
+        self.run = None;
+
+        if let Some(ticker) = &self.ticker {
+            ticker.stop();
+        }
+    }
+
+    /// Advances the animation, returning `true` if the value or status changed.
+    fn tick(&mut self, elapsed: Duration) -> bool {
+        let Some(run) = self.run.clone() else {
+            return false;
+        };
+
+        let previous = (self.value, self.status);
+
+        match run {
+            Run::To { from, to, duration } => {
+                let t = (elapsed.as_secs_f32() / duration.as_secs_f32()).min(1.0);
+
+                self.value = f32::lerp(&from, &to, t);
+
+                if t >= 1.0 {
+                    self.value = to;
+                    self.status = self.status_at(to, self.status);
+
+                    self.stop();
+                }
+            }
+
+            Run::Repeat { reverse, period } => {
+                let cycles = elapsed.as_secs_f32() / period.as_secs_f32();
+
+                let t = if reverse && (cycles as u64) % 2 == 1 {
+                    self.status = AnimationStatus::Reverse;
+
+                    1.0 - cycles.fract()
+                } else {
+                    self.status = AnimationStatus::Forward;
+
+                    cycles.fract()
+                };
+
+                self.value = f32::lerp(&self.lower_bound, &self.upper_bound, t);
+            }
+
+            Run::Simulation(simulation) => {
+                let time = elapsed.as_secs_f32();
+
+                self.value = simulation
+                    .position(time)
+                    .clamp(self.lower_bound, self.upper_bound);
+
+                if simulation.is_done(time) {
+                    self.status = if self.value == self.lower_bound {
+                        AnimationStatus::Dismissed
+                    } else {
+                        AnimationStatus::Completed
+                    };
+
+                    self.stop();
+                }
+            }
+        }
+
+        previous != (self.value, self.status)
+    }
+}
+
+impl Listenable for AnimationController {
+    type Handle = NotifierHandle;
+
+    fn notify_listeners(&self) {
+        self.notifier.notify_listeners();
+    }
+
+    fn add_listener(&self, func: impl Fn() + 'static) -> Self::Handle {
+        self.notifier.add_listener(func)
+    }
+}
+
+impl std::fmt::Debug for AnimationController {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let inner = self.inner.borrow();
+
+        f.debug_struct("AnimationController")
+            .field("duration", &inner.duration)
+            .field("value", &inner.value)
+            .field("status", &inner.status)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::Cell, rc::Rc, time::Duration};
+
+    use crate::{
+        element::mock::DummyWidget,
+        engine::Engine,
+        listenable::Listenable,
+        plugin::context::ContextPlugins,
+        scheduler::{FrameSchedulerPlugin, ManualClock},
+    };
+
+    use crate::animation::{FrictionSimulation, Simulation};
+
+    use super::{AnimationController, AnimationStatus};
+
+    fn engine_with_clock() -> (Engine, ManualClock) {
+        let clock = ManualClock::default();
+
+        let mut engine = Engine::builder()
+            .add_plugin(FrameSchedulerPlugin::with_clock(clock.clone()))
+            .with_root(DummyWidget)
+            .build();
+
+        engine.update().unwrap();
+
+        (engine, clock)
+    }
+
+    fn frame(engine: &mut Engine, clock: &ManualClock, millis: u64) {
+        clock.advance(Duration::from_millis(millis));
+
+        engine.update().unwrap();
+    }
+
+    #[test]
+    pub fn runs_forward_and_reverse() {
+        let (mut engine, clock) = engine_with_clock();
+
+        let controller = AnimationController::new(
+            engine.plugins().get::<FrameSchedulerPlugin>().unwrap(),
+            Duration::from_millis(100),
+        );
+
+        let notified = Rc::new(Cell::new(0));
+
+        let _handle = controller.add_listener({
+            let notified = Rc::clone(&notified);
+
+            move || notified.set(notified.get() + 1)
+        });
+
+        controller.forward();
+
+        assert_eq!(controller.status(), AnimationStatus::Forward);
+
+        frame(&mut engine, &clock, 16);
+
+        assert_eq!(controller.value(), 0.0, "first frame should start the run");
+
+        frame(&mut engine, &clock, 50);
+
+        assert_eq!(controller.value(), 0.5);
+
+        frame(&mut engine, &clock, 60);
+
+        assert_eq!(controller.value(), 1.0);
+        assert_eq!(controller.status(), AnimationStatus::Completed);
+        assert!(!controller.is_animating(), "should stop once complete");
+
+        assert_eq!(notified.get(), 2, "should notify when the value changes");
+
+        controller.reverse();
+
+        frame(&mut engine, &clock, 16);
+        frame(&mut engine, &clock, 25);
+
+        assert_eq!(controller.value(), 0.75);
+        assert_eq!(controller.status(), AnimationStatus::Reverse);
+
+        controller.stop();
+
+        frame(&mut engine, &clock, 100);
+
+        assert_eq!(controller.value(), 0.75, "should not advance once stopped");
+    }
+
+    #[test]
+    pub fn repeats_in_reverse() {
+        let (mut engine, clock) = engine_with_clock();
+
+        let controller = AnimationController::new(
+            engine.plugins().get::<FrameSchedulerPlugin>().unwrap(),
+            Duration::from_millis(100),
+        );
+
+        controller.repeat(true);
+
+        frame(&mut engine, &clock, 16);
+        frame(&mut engine, &clock, 125);
+
+        assert_eq!(controller.status(), AnimationStatus::Reverse);
+        assert!((controller.value() - 0.75).abs() < 0.001);
+
+        frame(&mut engine, &clock, 100);
+
+        assert_eq!(controller.status(), AnimationStatus::Forward);
+        assert!((controller.value() - 0.25).abs() < 0.001);
+    }
+
+    #[test]
+    pub fn completes_immediately_when_the_bounds_are_equal() {
+        let (mut engine, clock) = engine_with_clock();
+
+        let controller = AnimationController::new(
+            engine.plugins().get::<FrameSchedulerPlugin>().unwrap(),
+            Duration::from_millis(100),
+        )
+        .with_bounds(0.5, 0.5);
+
+        controller.forward();
+
+        assert!(!controller.is_animating(), "should have nowhere to go");
+
+        frame(&mut engine, &clock, 16);
+
+        assert_eq!(controller.value(), 0.5);
+    }
+
+    #[test]
+    pub fn completes_immediately_when_repeating_without_a_duration() {
+        let (mut engine, clock) = engine_with_clock();
+
+        let controller = AnimationController::new(
+            engine.plugins().get::<FrameSchedulerPlugin>().unwrap(),
+            Duration::ZERO,
+        );
+
+        let notified = Rc::new(Cell::new(0));
+
+        let _handle = controller.add_listener({
+            let notified = Rc::clone(&notified);
+
+            move || notified.set(notified.get() + 1)
+        });
+
+        controller.repeat(true);
+
+        assert!(!controller.is_animating(), "should have no cycle to repeat");
+        assert_eq!(controller.status(), AnimationStatus::Completed);
+        assert_eq!(notified.get(), 1, "should notify that it completed");
+
+        frame(&mut engine, &clock, 16);
+
+        assert_eq!(controller.value(), 1.0);
+    }
+
+    #[test]
+    pub fn runs_simulations_within_bounds() {
+        let (mut engine, clock) = engine_with_clock();
+
+        let controller = AnimationController::new(
+            engine.plugins().get::<FrameSchedulerPlugin>().unwrap(),
+            Duration::from_millis(100),
+        )
+        .with_bounds(0.0, 100.0);
+
+        controller.animate_with(FrictionSimulation::new(0.1, 0.0, 50.0));
+
+        assert_eq!(controller.status(), AnimationStatus::Forward);
+
+        frame(&mut engine, &clock, 16);
+        frame(&mut engine, &clock, 1000);
+
+        let simulation = FrictionSimulation::new(0.1, 0.0, 50.0);
+
+        assert_eq!(controller.value(), simulation.position(1.0));
+
+        frame(&mut engine, &clock, 10_000);
+
+        assert!(!controller.is_animating(), "should stop once at rest");
+        assert_eq!(controller.status(), AnimationStatus::Completed);
+        assert!((controller.value() - simulation.final_position()).abs() < 0.01);
+
+        controller.fling(-1.0);
+
+        assert_eq!(controller.status(), AnimationStatus::Reverse);
+
+        frame(&mut engine, &clock, 16);
+        frame(&mut engine, &clock, 5_000);
+
+        assert_eq!(
+            controller.value(),
+            0.0,
+            "should be clamped to the lower bound"
+        );
+        assert_eq!(controller.status(), AnimationStatus::Dismissed);
+    }
+}
@@ -0,0 +1,319 @@
+use std::f32::consts::PI;
+
+/// An easing function, mapping the progress of an animation to the progress of the value being
+/// animated. Both are expected to be `0.0` at the start and `1.0` at the end, though the value
+/// may overshoot in between.
+pub trait Curve {
+    fn transform(&self, t: f32) -> f32;
+
+    /// Returns a curve that plays this one backwards.
+    fn flipped(self) -> Flipped<Self>
+    where
+        Self: Sized,
+    {
+        Flipped(self)
+    }
+}
+
+impl<F> Curve for F
+where
+    F: Fn(f32) -> f32,
+{
+    fn transform(&self, t: f32) -> f32 {
+        self(t)
+    }
+}
+
+/// The identity curve.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Linear;
+
+impl Curve for Linear {
+    fn transform(&self, t: f32) -> f32 {
+        t
+    }
+}
+
+/// A cubic bezier from `(0, 0)` to `(1, 1)` with control points `(a, b)` and `(c, d)`, as used
+/// by CSS `cubic-bezier()` timing functions.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cubic {
+    pub a: f32,
+    pub b: f32,
+    pub c: f32,
+    pub d: f32,
+}
+
+impl Cubic {
+    const EPSILON: f32 = 0.001;
+
+    /// The most steps the search for a point on the curve may take before settling for its
+    /// latest estimate, in case it never comes within [`Self::EPSILON`] of the target.
+    const MAX_ITERATIONS: usize = 32;
+
+    pub const fn new(a: f32, b: f32, c: f32, d: f32) -> Self {
+        Self { a, b, c, d }
+    }
+
+    fn evaluate(p1: f32, p2: f32, m: f32) -> f32 {
+        3.0 * p1 * (1.0 - m) * (1.0 - m) * m + 3.0 * p2 * (1.0 - m) * m * m + m * m * m
+    }
+}
+
+impl Curve for Cubic {
+    fn transform(&self, t: f32) -> f32 {
+        if t <= 0.0 || t >= 1.0 {
+            return t.clamp(0.0, 1.0);
+        }
+
+        // Find the point on the curve at the given time using a binary search, since solving the
+        // cubic directly is both slower and less stable.
+        let mut start = 0.0;
+        let mut end = 1.0;
+
+        let mut midpoint = 0.5;
+
+        for _ in 0..Self::MAX_ITERATIONS {
+            midpoint = (start + end) / 2.0;
+
+            let estimate = Self::evaluate(self.a, self.c, midpoint);
+
+            if (t - estimate).abs() < Self::EPSILON {
+                break;
+            }
+
+            if estimate < t {
+                start = midpoint;
+            } else {
+                end = midpoint;
+            }
+        }
+
+        Self::evaluate(self.b, self.d, midpoint)
+    }
+}
+
+/// Runs the inner curve over the `[begin, end]` portion of the animation, holding at `0.0`
+/// before and `1.0` after.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Interval<C> {
+    pub begin: f32,
+    pub end: f32,
+
+    pub curve: C,
+}
+
+impl Interval<Linear> {
+    pub const fn new(begin: f32, end: f32) -> Self {
+        Self {
+            begin,
+            end,
+
+            curve: Linear,
+        }
+    }
+}
+
+impl<C> Interval<C> {
+    pub fn with_curve<T>(self, curve: T) -> Interval<T> {
+        Interval {
+            begin: self.begin,
+            end: self.end,
+
+            curve,
+        }
+    }
+}
+
+impl<C> Curve for Interval<C>
+where
+    C: Curve,
+{
+    fn transform(&self, t: f32) -> f32 {
+        let t = ((t - self.begin) / (self.end - self.begin)).clamp(0.0, 1.0);
+
+        if t == 0.0 || t == 1.0 {
+            t
+        } else {
+            self.curve.transform(t)
+        }
+    }
+}
+
+/// Jumps from `0.0` to `1.0` once the animation passes the threshold.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Threshold(pub f32);
+
+impl Curve for Threshold {
+    fn transform(&self, t: f32) -> f32 {
+        if t < self.0 {
+            0.0
+        } else {
+            1.0
+        }
+    }
+}
+
+/// Plays the inner curve backwards, so an ease-in becomes an ease-out.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Flipped<C>(pub C);
+
+impl<C> Curve for Flipped<C>
+where
+    C: Curve,
+{
+    fn transform(&self, t: f32) -> f32 {
+        1.0 - self.0.transform(1.0 - t)
+    }
+}
+
+/// Follows a quarter period of a sine wave, easing into the animation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SineIn;
+
+impl Curve for SineIn {
+    fn transform(&self, t: f32) -> f32 {
+        1.0 - (t * PI / 2.0).cos()
+    }
+}
+
+/// Bounces against the end of the animation before settling.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BounceOut;
+
+impl Curve for BounceOut {
+    fn transform(&self, t: f32) -> f32 {
+        if t < 1.0 / 2.75 {
+            7.5625 * t * t
+        } else if t < 2.0 / 2.75 {
+            let t = t - 1.5 / 2.75;
+
+            7.5625 * t * t + 0.75
+        } else if t < 2.5 / 2.75 {
+            let t = t - 2.25 / 2.75;
+
+            7.5625 * t * t + 0.9375
+        } else {
+            let t = t - 2.625 / 2.75;
+
+            7.5625 * t * t + 0.984375
+        }
+    }
+}
+
+/// Oscillates around the end of the animation with decreasing amplitude, overshooting it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ElasticOut {
+    pub period: f32,
+}
+
+impl Curve for ElasticOut {
+    fn transform(&self, t: f32) -> f32 {
+        if t <= 0.0 || t >= 1.0 {
+            return t.clamp(0.0, 1.0);
+        }
+
+        let s = self.period / 4.0;
+
+        2.0_f32.powf(-10.0 * t) * ((t - s) * (PI * 2.0) / self.period).sin() + 1.0
+    }
+}
+
+/// A collection of commonly used curves.
+pub struct Curves;
+
+impl Curves {
+    pub const LINEAR: Linear = Linear;
+
+    pub const EASE: Cubic = Cubic::new(0.25, 0.1, 0.25, 1.0);
+    pub const EASE_IN: Cubic = Cubic::new(0.42, 0.0, 1.0, 1.0);
+    pub const EASE_OUT: Cubic = Cubic::new(0.0, 0.0, 0.58, 1.0);
+    pub const EASE_IN_OUT: Cubic = Cubic::new(0.42, 0.0, 0.58, 1.0);
+
+    pub const EASE_IN_BACK: Cubic = Cubic::new(0.6, -0.28, 0.735, 0.045);
+    pub const EASE_OUT_BACK: Cubic = Cubic::new(0.175, 0.885, 0.32, 1.275);
+
+    pub const FAST_OUT_SLOW_IN: Cubic = Cubic::new(0.4, 0.0, 0.2, 1.0);
+    pub const DECELERATE: Cubic = Cubic::new(0.0, 0.0, 0.2, 1.0);
+
+    pub const SINE_IN: SineIn = SineIn;
+    pub const SINE_OUT: Flipped<SineIn> = Flipped(SineIn);
+
+    pub const BOUNCE_IN: Flipped<BounceOut> = Flipped(BounceOut);
+    pub const BOUNCE_OUT: BounceOut = BounceOut;
+
+    pub const ELASTIC_IN: Flipped<ElasticOut> = Flipped(ElasticOut { period: 0.4 });
+    pub const ELASTIC_OUT: ElasticOut = ElasticOut { period: 0.4 };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Cubic, Curve, Curves, Interval, Threshold};
+
+    #[test]
+    pub fn curves_start_and_end_at_their_bounds() {
+        let curves: [(&str, &dyn Curve); 14] = [
+            ("LINEAR", &Curves::LINEAR),
+            ("EASE", &Curves::EASE),
+            ("EASE_IN", &Curves::EASE_IN),
+            ("EASE_OUT", &Curves::EASE_OUT),
+            ("EASE_IN_OUT", &Curves::EASE_IN_OUT),
+            ("EASE_IN_BACK", &Curves::EASE_IN_BACK),
+            ("EASE_OUT_BACK", &Curves::EASE_OUT_BACK),
+            ("FAST_OUT_SLOW_IN", &Curves::FAST_OUT_SLOW_IN),
+            ("DECELERATE", &Curves::DECELERATE),
+            ("SINE_IN", &Curves::SINE_IN),
+            ("SINE_OUT", &Curves::SINE_OUT),
+            ("BOUNCE_IN", &Curves::BOUNCE_IN),
+            ("BOUNCE_OUT", &Curves::BOUNCE_OUT),
+            ("ELASTIC_OUT", &Curves::ELASTIC_OUT),
+        ];
+
+        for (name, curve) in curves {
+            assert!(
+                curve.transform(0.0).abs() < 0.001,
+                "{} should start at 0.0",
+                name
+            );
+
+            assert!(
+                (curve.transform(1.0) - 1.0).abs() < 0.001,
+                "{} should end at 1.0",
+                name
+            );
+        }
+    }
+
+    #[test]
+    pub fn cubic_follows_the_curve() {
+        assert!(Curves::EASE_IN.transform(0.25) < 0.25, "should ease in");
+        assert!(Curves::EASE_OUT.transform(0.25) > 0.25, "should ease out");
+
+        assert!(
+            (Curves::EASE_IN_OUT.transform(0.5) - 0.5).abs() < 0.01,
+            "should be symmetrical"
+        );
+    }
+
+    #[test]
+    pub fn cubic_gives_up_on_curves_that_never_converge() {
+        let cubic = Cubic::new(f32::NAN, 0.0, 1.0, 1.0);
+
+        assert!(
+            cubic.transform(0.5).is_finite(),
+            "should have settled for the last estimate"
+        );
+    }
+
+    #[test]
+    pub fn interval_holds_outside_of_its_bounds() {
+        let interval = Interval::new(0.25, 0.75);
+
+        assert_eq!(interval.transform(0.1), 0.0);
+        assert_eq!(interval.transform(0.5), 0.5);
+        assert_eq!(interval.transform(0.9), 1.0);
+
+        assert_eq!(Threshold(0.5).transform(0.49), 0.0);
+        assert_eq!(Threshold(0.5).transform(0.5), 1.0);
+    }
+}
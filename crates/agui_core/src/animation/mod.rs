@@ -0,0 +1,9 @@
+mod controller;
+mod curve;
+mod simulation;
+mod tween;
+
+pub use controller::*;
+pub use curve::*;
+pub use simulation::*;
+pub use tween::*;
@@ -0,0 +1,81 @@
+use super::{Simulation, Tolerance};
+
+/// Decelerates from an initial velocity until coming to rest, such as after a fling.
+///
+/// The `drag` is the fraction of the velocity that remains after each second, so it must be
+/// between `0.0` and `1.0`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrictionSimulation {
+    drag: f32,
+    drag_log: f32,
+
+    position: f32,
+    velocity: f32,
+
+    tolerance: Tolerance,
+}
+
+impl FrictionSimulation {
+    pub fn new(drag: f32, position: f32, velocity: f32) -> Self {
+        Self {
+            drag,
+            drag_log: drag.ln(),
+
+            position,
+            velocity,
+
+            tolerance: Tolerance::default(),
+        }
+    }
+
+    pub fn with_tolerance(mut self, tolerance: Tolerance) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// The position at which the simulation will come to rest.
+    pub fn final_position(&self) -> f32 {
+        self.position - self.velocity / self.drag_log
+    }
+}
+
+impl Simulation for FrictionSimulation {
+    fn position(&self, time: f32) -> f32 {
+        self.position + self.velocity * self.drag.powf(time) / self.drag_log
+            - self.velocity / self.drag_log
+    }
+
+    fn velocity(&self, time: f32) -> f32 {
+        self.velocity * self.drag.powf(time)
+    }
+
+    fn is_done(&self, time: f32) -> bool {
+        self.velocity(time).abs() < self.tolerance.velocity
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::animation::Simulation;
+
+    use super::FrictionSimulation;
+
+    #[test]
+    pub fn decelerates_to_rest() {
+        let simulation = FrictionSimulation::new(0.5, 0.0, 100.0);
+
+        assert_eq!(simulation.position(0.0), 0.0);
+        assert_eq!(simulation.velocity(0.0), 100.0);
+
+        assert_eq!(simulation.velocity(1.0), 50.0);
+        assert!(simulation.position(1.0) > 0.0);
+
+        assert!(!simulation.is_done(1.0));
+        assert!(simulation.is_done(30.0));
+
+        assert!(
+            (simulation.position(30.0) - simulation.final_position()).abs() < 0.01,
+            "should come to rest at the final position"
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use super::Simulation;
+
+/// Accelerates at a constant rate from a starting position and velocity until it has travelled
+/// as far as `end`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GravitySimulation {
+    acceleration: f32,
+
+    start: f32,
+    end: f32,
+
+    velocity: f32,
+}
+
+impl GravitySimulation {
+    pub fn new(acceleration: f32, start: f32, end: f32, velocity: f32) -> Self {
+        Self {
+            acceleration,
+
+            start,
+            end,
+
+            velocity,
+        }
+    }
+}
+
+impl Simulation for GravitySimulation {
+    fn position(&self, time: f32) -> f32 {
+        self.start + self.velocity * time + 0.5 * self.acceleration * time * time
+    }
+
+    fn velocity(&self, time: f32) -> f32 {
+        self.velocity + self.acceleration * time
+    }
+
+    fn is_done(&self, time: f32) -> bool {
+        (self.position(time) - self.start).abs() >= (self.end - self.start).abs()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::animation::Simulation;
+
+    use super::GravitySimulation;
+
+    #[test]
+    pub fn accelerates_until_reaching_the_end() {
+        let simulation = GravitySimulation::new(10.0, 0.0, 20.0, 0.0);
+
+        assert_eq!(simulation.position(1.0), 5.0);
+        assert_eq!(simulation.velocity(1.0), 10.0);
+        assert!(!simulation.is_done(1.0));
+
+        assert_eq!(simulation.position(2.0), 20.0);
+        assert!(simulation.is_done(2.0));
+    }
+}
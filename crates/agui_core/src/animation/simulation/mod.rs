@@ -0,0 +1,43 @@
+mod friction;
+mod gravity;
+mod spring;
+
+pub use friction::*;
+pub use gravity::*;
+pub use spring::*;
+
+/// A physical model of a value's position over time, such as a spring or an object coming to
+/// rest under friction.
+///
+/// Simulations are evaluated at the time since they started, in seconds, rather than integrated
+/// frame by frame, so they produce the same result regardless of the frame rate.
+pub trait Simulation {
+    /// The position at the given time.
+    fn position(&self, time: f32) -> f32;
+
+    /// The velocity at the given time, in units per second.
+    fn velocity(&self, time: f32) -> f32;
+
+    /// Whether the simulation has come to rest at the given time.
+    fn is_done(&self, time: f32) -> bool;
+}
+
+/// How close to its resting state a simulation must be before it is considered done.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tolerance {
+    pub distance: f32,
+    pub velocity: f32,
+}
+
+impl Tolerance {
+    pub const DEFAULT: Self = Self {
+        distance: 0.001,
+        velocity: 0.001,
+    };
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
@@ -0,0 +1,206 @@
+use super::{Simulation, Tolerance};
+
+/// The physical properties of a damped spring.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpringDescription {
+    pub mass: f32,
+    pub stiffness: f32,
+    pub damping: f32,
+}
+
+impl SpringDescription {
+    pub const fn new(mass: f32, stiffness: f32, damping: f32) -> Self {
+        Self {
+            mass,
+            stiffness,
+            damping,
+        }
+    }
+
+    /// Creates a spring whose damping is given as a ratio of critical damping, where `1.0` is
+    /// critically damped, values below it oscillate, and values above it settle more slowly.
+    pub fn with_damping_ratio(mass: f32, stiffness: f32, ratio: f32) -> Self {
+        Self {
+            mass,
+            stiffness,
+            damping: ratio * 2.0 * (mass * stiffness).sqrt(),
+        }
+    }
+}
+
+/// Moves from a starting position and velocity towards the `end` position, as though attached
+/// to it by a spring. Useful for settling a value back into place, such as when a scroll view is
+/// overscrolled.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SpringSimulation {
+    end: f32,
+
+    solution: SpringSolution,
+
+    tolerance: Tolerance,
+}
+
+impl SpringSimulation {
+    pub fn new(spring: SpringDescription, start: f32, end: f32, velocity: f32) -> Self {
+        Self {
+            end,
+
+            solution: SpringSolution::new(spring, start - end, velocity),
+
+            tolerance: Tolerance::default(),
+        }
+    }
+
+    pub fn with_tolerance(mut self, tolerance: Tolerance) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+}
+
+impl Simulation for SpringSimulation {
+    fn position(&self, time: f32) -> f32 {
+        self.end + self.solution.position(time)
+    }
+
+    fn velocity(&self, time: f32) -> f32 {
+        self.solution.velocity(time)
+    }
+
+    fn is_done(&self, time: f32) -> bool {
+        self.solution.position(time).abs() < self.tolerance.distance
+            && self.solution.velocity(time).abs() < self.tolerance.velocity
+    }
+}
+
+/// The closed-form solution of the spring's motion relative to its resting position, which
+/// depends on how heavily it is damped.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SpringSolution {
+    Critical { r: f32, c1: f32, c2: f32 },
+    Over { r1: f32, r2: f32, c1: f32, c2: f32 },
+    Under { w: f32, r: f32, c1: f32, c2: f32 },
+}
+
+impl SpringSolution {
+    fn new(spring: SpringDescription, distance: f32, velocity: f32) -> Self {
+        let SpringDescription {
+            mass,
+            stiffness,
+            damping,
+        } = spring;
+
+        let cmk = damping * damping - 4.0 * mass * stiffness;
+
+        // Springs created from a damping ratio of exactly `1.0` rarely cancel out perfectly, so
+        // treat anything within rounding error as critically damped.
+        if cmk.abs() <= damping * damping * 1e-5 {
+            let r = -damping / (2.0 * mass);
+
+            Self::Critical {
+                r,
+                c1: distance,
+                c2: velocity - r * distance,
+            }
+        } else if cmk > 0.0 {
+            let r1 = (-damping - cmk.sqrt()) / (2.0 * mass);
+            let r2 = (-damping + cmk.sqrt()) / (2.0 * mass);
+
+            let c2 = (velocity - r1 * distance) / (r2 - r1);
+
+            Self::Over {
+                r1,
+                r2,
+                c1: distance - c2,
+                c2,
+            }
+        } else {
+            let w = (-cmk).sqrt() / (2.0 * mass);
+            let r = -damping / (2.0 * mass);
+
+            Self::Under {
+                w,
+                r,
+                c1: distance,
+                c2: (velocity - r * distance) / w,
+            }
+        }
+    }
+
+    fn position(&self, time: f32) -> f32 {
+        match *self {
+            Self::Critical { r, c1, c2 } => (c1 + c2 * time) * (r * time).exp(),
+
+            Self::Over { r1, r2, c1, c2 } => c1 * (r1 * time).exp() + c2 * (r2 * time).exp(),
+
+            Self::Under { w, r, c1, c2 } => {
+                (r * time).exp() * (c1 * (w * time).cos() + c2 * (w * time).sin())
+            }
+        }
+    }
+
+    fn velocity(&self, time: f32) -> f32 {
+        match *self {
+            Self::Critical { r, c1, c2 } => (r * time).exp() * (r * c1 + c2 * (r * time + 1.0)),
+
+            Self::Over { r1, r2, c1, c2 } => {
+                c1 * r1 * (r1 * time).exp() + c2 * r2 * (r2 * time).exp()
+            }
+
+            Self::Under { w, r, c1, c2 } => {
+                (r * time).exp()
+                    * ((r * c1 + c2 * w) * (w * time).cos() + (r * c2 - c1 * w) * (w * time).sin())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::animation::Simulation;
+
+    use super::{SpringDescription, SpringSimulation};
+
+    #[test]
+    pub fn settles_at_the_end() {
+        for (name, spring) in [
+            (
+                "critically damped",
+                SpringDescription::new(1.0, 100.0, 20.0),
+            ),
+            ("over damped", SpringDescription::new(1.0, 100.0, 40.0)),
+            ("under damped", SpringDescription::new(1.0, 100.0, 5.0)),
+        ] {
+            let simulation = SpringSimulation::new(spring, 0.0, 10.0, 5.0);
+
+            assert!(
+                simulation.position(0.0).abs() < 0.001,
+                "{} should begin at the start",
+                name
+            );
+
+            assert!(
+                (simulation.velocity(0.0) - 5.0).abs() < 0.001,
+                "{} should begin with the initial velocity",
+                name
+            );
+
+            assert!(!simulation.is_done(0.1), "{} should be moving", name);
+
+            assert!(
+                (simulation.position(10.0) - 10.0).abs() < 0.001,
+                "{} should settle at the end",
+                name
+            );
+
+            assert!(simulation.is_done(10.0), "{} should be done", name);
+        }
+
+        let under_damped =
+            SpringSimulation::new(SpringDescription::new(1.0, 100.0, 5.0), 0.0, 10.0, 0.0);
+
+        assert!(
+            (0..100).any(|i| under_damped.position(i as f32 / 100.0) > 10.0),
+            "under damped springs should overshoot"
+        );
+    }
+}
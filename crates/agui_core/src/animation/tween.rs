@@ -0,0 +1,158 @@
+use crate::unit::{Alignment, Color, Constraints, EdgeInsets, Offset, Rect, Size};
+
+use super::Curve;
+
+/// A value that can be linearly interpolated.
+pub trait Lerp: Sized {
+    /// Interpolates between `a` and `b`, where a `t` of `0.0` returns `a` and `1.0` returns `b`.
+    /// Values of `t` outside of that range extrapolate, which curves may use to overshoot.
+    fn lerp(a: &Self, b: &Self, t: f32) -> Self;
+}
+
+impl Lerp for f32 {
+    fn lerp(a: &Self, b: &Self, t: f32) -> Self {
+        a + (b - a) * t
+    }
+}
+
+/// Interpolates when both values are present, otherwise jumps straight to `b`.
+impl<T> Lerp for Option<T>
+where
+    T: Lerp + Clone,
+{
+    fn lerp(a: &Self, b: &Self, t: f32) -> Self {
+        match (a, b) {
+            (Some(a), Some(b)) => Some(T::lerp(a, b, t)),
+            _ => b.clone(),
+        }
+    }
+}
+
+impl Lerp for Color {
+    fn lerp(a: &Self, b: &Self, t: f32) -> Self {
+        Self {
+            red: f32::lerp(&a.red, &b.red, t),
+            green: f32::lerp(&a.green, &b.green, t),
+            blue: f32::lerp(&a.blue, &b.blue, t),
+            alpha: f32::lerp(&a.alpha, &b.alpha, t),
+        }
+    }
+}
+
+impl Lerp for Offset {
+    fn lerp(a: &Self, b: &Self, t: f32) -> Self {
+        Self {
+            x: f32::lerp(&a.x, &b.x, t),
+            y: f32::lerp(&a.y, &b.y, t),
+        }
+    }
+}
+
+impl Lerp for Alignment {
+    fn lerp(a: &Self, b: &Self, t: f32) -> Self {
+        Self {
+            x: f32::lerp(&a.x, &b.x, t),
+            y: f32::lerp(&a.y, &b.y, t),
+        }
+    }
+}
+
+impl Lerp for Size {
+    fn lerp(a: &Self, b: &Self, t: f32) -> Self {
+        Self {
+            width: f32::lerp(&a.width, &b.width, t),
+            height: f32::lerp(&a.height, &b.height, t),
+        }
+    }
+}
+
+impl Lerp for Rect {
+    fn lerp(a: &Self, b: &Self, t: f32) -> Self {
+        Self {
+            left: f32::lerp(&a.left, &b.left, t),
+            top: f32::lerp(&a.top, &b.top, t),
+            width: f32::lerp(&a.width, &b.width, t),
+            height: f32::lerp(&a.height, &b.height, t),
+        }
+    }
+}
+
+impl Lerp for EdgeInsets {
+    fn lerp(a: &Self, b: &Self, t: f32) -> Self {
+        Self {
+            top: f32::lerp(&a.top, &b.top, t),
+            right: f32::lerp(&a.right, &b.right, t),
+            bottom: f32::lerp(&a.bottom, &b.bottom, t),
+            left: f32::lerp(&a.left, &b.left, t),
+        }
+    }
+}
+
+impl Lerp for Constraints {
+    fn lerp(a: &Self, b: &Self, t: f32) -> Self {
+        Constraints::lerp(a, b, t)
+    }
+}
+
+/// Interpolates between a beginning and ending value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tween<T> {
+    pub begin: T,
+    pub end: T,
+}
+
+impl<T> Tween<T>
+where
+    T: Lerp,
+{
+    pub fn new(begin: T, end: T) -> Self {
+        Self { begin, end }
+    }
+
+    /// Returns the value at `t`, where `0.0` is the beginning and `1.0` is the end.
+    pub fn transform(&self, t: f32) -> T {
+        T::lerp(&self.begin, &self.end, t)
+    }
+
+    /// Returns the value at `t` after easing it with the given curve.
+    pub fn transform_with(&self, curve: &impl Curve, t: f32) -> T {
+        self.transform(curve.transform(t))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        animation::Curves,
+        unit::{Color, EdgeInsets},
+    };
+
+    use super::Tween;
+
+    #[test]
+    pub fn interpolates_between_values() {
+        let tween = Tween::new(
+            Color::from_rgba((0.0, 0.0, 0.0, 0.0)),
+            Color::from_rgba((1.0, 0.5, 0.0, 1.0)),
+        );
+
+        assert_eq!(tween.transform(0.0), tween.begin);
+        assert_eq!(
+            tween.transform(0.5),
+            Color::from_rgba((0.5, 0.25, 0.0, 0.5))
+        );
+        assert_eq!(tween.transform(1.0), tween.end);
+
+        let tween = Tween::new(EdgeInsets::all(0.0), EdgeInsets::all(10.0));
+
+        assert_eq!(
+            tween.transform_with(&Curves::LINEAR, 0.25),
+            EdgeInsets::all(2.5)
+        );
+
+        let tween = Tween::new(Some(0.0_f32), Some(10.0));
+
+        assert_eq!(tween.transform(0.5), Some(5.0));
+        assert_eq!(Tween::new(None, Some(10.0_f32)).transform(0.5), Some(10.0));
+    }
+}
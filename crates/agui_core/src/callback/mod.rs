@@ -1,27 +1,42 @@
 use std::{
     any::{Any, TypeId},
+    hash::{Hash, Hasher},
     marker::PhantomData,
     sync::Arc,
 };
 
+use rustc_hash::FxHasher;
+
 use crate::{element::ElementId, unit::AsAny};
 
 mod context;
 mod queue;
+mod request;
+mod response;
 
 pub use context::*;
 pub use queue::*;
+pub use request::*;
+pub use response::*;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct CallbackId {
     element_id: ElementId,
     type_id: TypeId,
+
+    /// Distinguishes callbacks created from the same closure type by the same element, such as
+    /// one per row of a list.
+    key: Option<u64>,
 }
 
 impl CallbackId {
     pub fn element_id(&self) -> ElementId {
         self.element_id
     }
+
+    pub fn key(&self) -> Option<u64> {
+        self.key
+    }
 }
 
 #[derive(Default, Clone)]
@@ -95,10 +110,37 @@ where
         Self::new_unchecked(element_id, TypeId::of::<F>(), callback_queue)
     }
 
+    /// Creates a callback that is distinct from any other callback of the same closure type
+    /// created by the element with a different `key`.
+    pub fn new_keyed<F: 'static>(
+        element_id: ElementId,
+        key: impl Hash,
+        callback_queue: CallbackQueue,
+    ) -> Self {
+        let mut hasher = FxHasher::default();
+        key.hash(&mut hasher);
+
+        Self::new_keyed_unchecked(
+            element_id,
+            TypeId::of::<F>(),
+            Some(hasher.finish()),
+            callback_queue,
+        )
+    }
+
     pub fn new_unchecked(
         element_id: ElementId,
         type_id: TypeId,
         callback_queue: CallbackQueue,
+    ) -> Self {
+        Self::new_keyed_unchecked(element_id, type_id, None, callback_queue)
+    }
+
+    pub fn new_keyed_unchecked(
+        element_id: ElementId,
+        type_id: TypeId,
+        key: Option<u64>,
+        callback_queue: CallbackQueue,
     ) -> Self {
         Self {
             phantom: PhantomData,
@@ -106,6 +148,7 @@ where
             id: CallbackId {
                 element_id,
                 type_id,
+                key,
             },
 
             callback_queue,
@@ -116,6 +159,17 @@ where
         self.id
     }
 
+    /// Check if the element that owns this callback still exists. Once it has been removed from
+    /// the tree, any further calls are discarded, so long-running work may use this to stop early.
+    pub fn is_alive(&self) -> bool {
+        self.callback_queue.is_alive(self.id.element_id)
+    }
+
+    /// Discards any invocations of this callback that have not yet been delivered.
+    pub fn cancel(&self) {
+        self.callback_queue.cancel(self.id);
+    }
+
     pub fn call(&self, arg: A) {
         self.callback_queue.call_unchecked(self.id, Box::new(arg));
     }
@@ -129,8 +183,10 @@ where
     }
 }
 
-unsafe impl<A> Send for WidgetCallback<A> {}
-unsafe impl<A> Sync for WidgetCallback<A> {}
+// The callback only holds the ID of the element and the queue it is delivered through, so it may
+// be used from any thread so long as the argument it sends may be.
+unsafe impl<A: Send> Send for WidgetCallback<A> {}
+unsafe impl<A: Send> Sync for WidgetCallback<A> {}
 
 impl<A> PartialEq for WidgetCallback<A> {
     fn eq(&self, other: &Self) -> bool {
@@ -207,11 +263,14 @@ where
 
 #[cfg(test)]
 mod tests {
-    use std::{any::TypeId, rc::Rc};
+    use std::{any::TypeId, cell::RefCell, rc::Rc};
 
     use crate::{
-        callback::WidgetCallback,
-        element::mock::{build::MockBuildWidget, DummyWidget},
+        callback::{CallbackCancelled, CallbackRequest, RequestCallback, WidgetCallback},
+        element::{
+            mock::{build::MockBuildWidget, DummyWidget},
+            ElementId,
+        },
         engine::Engine,
         widget::IntoWidget,
     };
@@ -232,7 +291,7 @@ mod tests {
 
         let mut engine = Engine::builder().with_root(widget).build();
 
-        engine.update();
+        engine.update().unwrap();
 
         WidgetCallback::new_unchecked(
             engine.root(),
@@ -258,7 +317,7 @@ mod tests {
 
         let mut engine = Engine::builder().with_root(widget).build();
 
-        engine.update();
+        engine.update().unwrap();
 
         WidgetCallback::new_unchecked(
             engine.root(),
@@ -267,6 +326,196 @@ mod tests {
         )
         .call(7);
 
-        engine.update();
+        engine.update().unwrap();
+    }
+
+    #[test]
+    pub fn keyed_callbacks_are_distinct() {
+        let called = Rc::new(RefCell::new(Vec::new()));
+
+        let widget = MockBuildWidget::default();
+        let widget_mock = Rc::clone(&widget.mock);
+        {
+            let mut widget_mock = widget_mock.borrow_mut();
+
+            widget_mock
+                .expect_build()
+                .returning(|_| DummyWidget.into_widget());
+
+            widget_mock.expect_call().times(2).returning_st({
+                let called = Rc::clone(&called);
+
+                move |_, callback_id, _| {
+                    called.borrow_mut().push(callback_id);
+
+                    false
+                }
+            });
+        }
+
+        let mut engine = Engine::builder().with_root(widget).build();
+
+        engine.update().unwrap();
+
+        let first_callback = WidgetCallback::<()>::new_keyed::<()>(
+            engine.root(),
+            "first",
+            engine.callback_queue().clone(),
+        );
+
+        let second_callback = WidgetCallback::<()>::new_keyed::<()>(
+            engine.root(),
+            "second",
+            engine.callback_queue().clone(),
+        );
+
+        assert_ne!(
+            first_callback.id(),
+            second_callback.id(),
+            "callbacks with different keys should have different IDs"
+        );
+
+        assert_eq!(
+            first_callback.id(),
+            WidgetCallback::<()>::new_keyed::<()>(
+                engine.root(),
+                "first",
+                engine.callback_queue().clone()
+            )
+            .id(),
+            "callbacks with the same key should have the same ID"
+        );
+
+        first_callback.call(());
+        second_callback.call(());
+
+        engine.update().unwrap();
+
+        assert_eq!(
+            *called.borrow(),
+            vec![first_callback.id(), second_callback.id()],
+            "should have called each callback separately"
+        );
+    }
+
+    #[test]
+    pub fn can_cancel_pending_callbacks() {
+        let widget = MockBuildWidget::default();
+        let widget_mock = Rc::clone(&widget.mock);
+        {
+            let mut widget_mock = widget_mock.borrow_mut();
+
+            widget_mock
+                .expect_build()
+                .returning(|_| DummyWidget.into_widget());
+
+            widget_mock.expect_call().never();
+        }
+
+        let mut engine = Engine::builder().with_root(widget).build();
+
+        engine.update().unwrap();
+
+        let callback = WidgetCallback::new_unchecked(
+            engine.root(),
+            TypeId::of::<()>(),
+            engine.callback_queue().clone(),
+        );
+
+        callback.call(7);
+        callback.cancel();
+
+        engine.update().unwrap();
+    }
+
+    fn doubling_widget() -> MockBuildWidget {
+        let widget = MockBuildWidget::default();
+        {
+            let mut widget_mock = widget.mock.borrow_mut();
+
+            widget_mock
+                .expect_build()
+                .returning(|_| DummyWidget.into_widget());
+
+            widget_mock.expect_call().returning(|_, _, arg| {
+                let request = arg
+                    .downcast::<CallbackRequest<i32, i32>>()
+                    .expect("failed to downcast callback argument");
+
+                let (arg, responder) = request.into_parts();
+
+                responder.respond(arg * 2);
+
+                false
+            });
+        }
+
+        widget
+    }
+
+    #[test]
+    pub fn can_respond_to_requests() {
+        let mut engine = Engine::builder().with_root(doubling_widget()).build();
+
+        engine.update().unwrap();
+
+        let callback = RequestCallback::<i32, i32>::new_unchecked(
+            engine.root(),
+            TypeId::of::<()>(),
+            engine.callback_queue().clone(),
+        );
+
+        let mut response = callback.request(21);
+
+        assert_eq!(
+            response.try_recv(),
+            None,
+            "should not respond until the engine updates"
+        );
+
+        engine.update().unwrap();
+
+        assert_eq!(response.try_recv(), Some(Ok(42)));
+    }
+
+    #[test]
+    pub fn can_wait_for_responses_from_other_threads() {
+        let mut engine = Engine::builder().with_root(doubling_widget()).build();
+
+        engine.update().unwrap();
+
+        let callback = RequestCallback::<i32, i32>::new_unchecked(
+            engine.root(),
+            TypeId::of::<()>(),
+            engine.callback_queue().clone(),
+        );
+
+        let handle = std::thread::spawn(move || callback.request(4).wait());
+
+        while !handle.is_finished() {
+            engine.update().unwrap();
+
+            std::thread::yield_now();
+        }
+
+        assert_eq!(handle.join().unwrap(), Ok(8));
+    }
+
+    #[test]
+    pub fn requests_to_missing_elements_are_cancelled() {
+        let mut engine = Engine::builder().with_root(doubling_widget()).build();
+
+        engine.update().unwrap();
+
+        let mut response = RequestCallback::<i32, i32>::new_unchecked(
+            ElementId::default(),
+            TypeId::of::<()>(),
+            engine.callback_queue().clone(),
+        )
+        .request(1);
+
+        engine.update().unwrap();
+
+        assert_eq!(response.try_recv(), Some(Err(CallbackCancelled)));
     }
 }
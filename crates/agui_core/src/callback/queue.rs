@@ -4,8 +4,9 @@ use std::{
 };
 
 use parking_lot::Mutex;
+use rustc_hash::FxHashSet;
 
-use crate::unit::AsAny;
+use crate::{element::ElementId, unit::AsAny};
 
 use super::CallbackId;
 
@@ -15,18 +16,72 @@ pub struct CallbackQueue(Arc<InnerCallbackQueue>);
 struct InnerCallbackQueue {
     queue: Arc<Mutex<Vec<CallbackInvoke>>>,
     update_notifier_tx: mpsc::Sender<()>,
+
+    /// The elements that are currently active in the tree, and so may still receive callbacks.
+    elements: Mutex<FxHashSet<ElementId>>,
+
+    /// Whether callbacks invoked on elements that no longer exist are logged as warnings, rather
+    /// than at the debug level.
+    warn_on_dropped: bool,
 }
 
 impl CallbackQueue {
     #[allow(clippy::arc_with_non_send_sync)]
-    pub(crate) fn new(update_notifier_tx: mpsc::Sender<()>) -> Self {
+    pub(crate) fn new(update_notifier_tx: mpsc::Sender<()>, warn_on_dropped: bool) -> Self {
         Self(Arc::new(InnerCallbackQueue {
             queue: Arc::default(),
 
             update_notifier_tx,
+
+            elements: Mutex::default(),
+
+            warn_on_dropped,
         }))
     }
 
+    pub(crate) fn insert_element(&self, element_id: ElementId) {
+        self.0.elements.lock().insert(element_id);
+    }
+
+    /// Stops the element from receiving callbacks, discarding any that are still pending.
+    pub(crate) fn remove_element(&self, element_id: ElementId) {
+        if self.0.elements.lock().remove(&element_id) {
+            self.0
+                .queue
+                .lock()
+                .retain(|invoke| invoke.callback_id.element_id() != element_id);
+        }
+    }
+
+    /// Check if the element is still active in the tree. Callbacks invoked on an element that has
+    /// been removed are discarded, even if it's yet to be destroyed.
+    pub fn is_alive(&self, element_id: ElementId) -> bool {
+        self.0.elements.lock().contains(&element_id)
+    }
+
+    /// Logs that an invocation of the callback was dropped because its element no longer exists.
+    pub(crate) fn log_dropped(&self, callback_id: CallbackId) {
+        if self.0.warn_on_dropped {
+            tracing::warn!(
+                ?callback_id,
+                "dropping callback invoked on an element that no longer exists"
+            );
+        } else {
+            tracing::debug!(
+                ?callback_id,
+                "dropping callback invoked on an element that no longer exists"
+            );
+        }
+    }
+
+    /// Discards any pending invocations of the given callback.
+    pub fn cancel(&self, callback_id: CallbackId) {
+        self.0
+            .queue
+            .lock()
+            .retain(|invoke| invoke.callback_id != callback_id);
+    }
+
     pub(crate) fn take(&mut self) -> Vec<CallbackInvoke> {
         self.0.queue.lock().drain(..).collect()
     }
@@ -35,10 +90,26 @@ impl CallbackQueue {
         self.0.queue.lock().is_empty()
     }
 
+    /// Returns the IDs of the callbacks that are waiting to be invoked.
+    pub(crate) fn pending(&self) -> Vec<CallbackId> {
+        self.0
+            .queue
+            .lock()
+            .iter()
+            .map(|invoke| invoke.callback_id)
+            .collect()
+    }
+
     /// # Panics
     ///
     /// This function must be called with the expected `arg` for the `callback_id`, or it will panic.
     pub fn call_unchecked(&self, callback_id: CallbackId, arg: Box<dyn Any>) {
+        if !self.is_alive(callback_id.element_id()) {
+            self.log_dropped(callback_id);
+
+            return;
+        }
+
         self.0
             .queue
             .lock()
@@ -57,18 +128,18 @@ impl CallbackQueue {
     ) where
         A: AsAny + Clone,
     {
-        self.0
-            .queue
-            .lock()
-            .extend(
-                callback_ids
-                    .into_iter()
-                    .copied()
-                    .map(|callback_id| CallbackInvoke {
-                        callback_id,
-                        arg: Box::new(arg.clone()),
-                    }),
-            );
+        let elements = self.0.elements.lock();
+
+        self.0.queue.lock().extend(
+            callback_ids
+                .into_iter()
+                .copied()
+                .filter(|callback_id| elements.contains(&callback_id.element_id()))
+                .map(|callback_id| CallbackInvoke {
+                    callback_id,
+                    arg: Box::new(arg.clone()),
+                }),
+        );
 
         let _ = self.0.update_notifier_tx.send(());
     }
@@ -0,0 +1,151 @@
+use std::{any::TypeId, hash::Hash, marker::PhantomData};
+
+use crate::element::ElementId;
+
+use super::{
+    channel, CallbackId, CallbackQueue, CallbackResponder, CallbackResponse, WidgetCallback,
+};
+
+/// A callback that responds to each call with a value computed by the element that owns it.
+///
+/// Requests are intended to be made from background threads, so the callback may only be sent
+/// across threads if both the argument and the response can be.
+///
+/// ```compile_fail
+/// use std::rc::Rc;
+///
+/// use agui_core::callback::RequestCallback;
+///
+/// fn assert_send<T: Send>() {}
+///
+/// assert_send::<RequestCallback<Rc<i32>, i32>>();
+/// ```
+pub struct RequestCallback<A, R> {
+    callback: WidgetCallback<CallbackRequest<A, R>>,
+}
+
+/// The argument delivered to a [`RequestCallback`], pairing the caller's argument with the
+/// responder used to send the result back.
+pub struct CallbackRequest<A, R> {
+    arg: A,
+    responder: CallbackResponder<R>,
+}
+
+impl<A, R> CallbackRequest<A, R> {
+    pub fn new(arg: A) -> (Self, CallbackResponse<R>) {
+        let (responder, response) = channel();
+
+        (Self { arg, responder }, response)
+    }
+
+    pub fn arg(&self) -> &A {
+        &self.arg
+    }
+
+    pub fn into_parts(self) -> (A, CallbackResponder<R>) {
+        (self.arg, self.responder)
+    }
+
+    pub fn respond(self, value: R) {
+        self.responder.respond(value);
+    }
+}
+
+impl<A, R> std::fmt::Debug for CallbackRequest<A, R>
+where
+    A: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CallbackRequest")
+            .field("arg", &self.arg)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<A, R> RequestCallback<A, R>
+where
+    A: Send + 'static,
+    R: Send + 'static,
+{
+    pub fn new<F: 'static>(element_id: ElementId, callback_queue: CallbackQueue) -> Self {
+        Self {
+            callback: WidgetCallback::new::<F>(element_id, callback_queue),
+        }
+    }
+
+    /// Creates a callback that is distinct from any other callback of the same closure type
+    /// created by the element with a different `key`.
+    pub fn new_keyed<F: 'static>(
+        element_id: ElementId,
+        key: impl Hash,
+        callback_queue: CallbackQueue,
+    ) -> Self {
+        Self {
+            callback: WidgetCallback::new_keyed::<F>(element_id, key, callback_queue),
+        }
+    }
+
+    pub fn new_unchecked(
+        element_id: ElementId,
+        type_id: TypeId,
+        callback_queue: CallbackQueue,
+    ) -> Self {
+        Self {
+            callback: WidgetCallback::new_unchecked(element_id, type_id, callback_queue),
+        }
+    }
+
+    pub fn id(&self) -> CallbackId {
+        self.callback.id()
+    }
+
+    /// Check if the element that owns this callback still exists. Once it has been removed from
+    /// the tree, any further requests are cancelled.
+    pub fn is_alive(&self) -> bool {
+        self.callback.is_alive()
+    }
+
+    /// Cancels any requests to this callback that have not yet been delivered.
+    pub fn cancel(&self) {
+        self.callback.cancel();
+    }
+
+    /// Queues a call to the callback, returning a response that resolves once the owning element
+    /// has handled it. If the element is removed or the call is cancelled before then, the
+    /// response resolves to [`CallbackCancelled`](super::CallbackCancelled).
+    pub fn request(&self, arg: A) -> CallbackResponse<R> {
+        let (request, response) = CallbackRequest::new(arg);
+
+        self.callback.call(request);
+
+        response
+    }
+}
+
+impl<A, R> Clone for RequestCallback<A, R> {
+    fn clone(&self) -> Self {
+        Self {
+            callback: WidgetCallback {
+                phantom: PhantomData,
+
+                id: self.callback.id,
+
+                callback_queue: self.callback.callback_queue.clone(),
+            },
+        }
+    }
+}
+
+impl<A, R> PartialEq for RequestCallback<A, R> {
+    fn eq(&self, other: &Self) -> bool {
+        self.callback == other.callback
+    }
+}
+
+impl<A, R> std::fmt::Debug for RequestCallback<A, R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RequestCallback")
+            .field("id", &self.callback.id)
+            .finish()
+    }
+}
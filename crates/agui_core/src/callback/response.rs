@@ -0,0 +1,149 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll, Waker},
+};
+
+use parking_lot::{Condvar, Mutex};
+
+/// Creates a one-shot channel used to deliver the value returned by a callback back to its caller.
+pub(crate) fn channel<R>() -> (CallbackResponder<R>, CallbackResponse<R>) {
+    let inner = Arc::new(ResponseInner {
+        state: Mutex::new(ResponseState {
+            value: None,
+            is_closed: false,
+            waker: None,
+        }),
+        ready: Condvar::new(),
+    });
+
+    (
+        CallbackResponder {
+            inner: Some(Arc::clone(&inner)),
+        },
+        CallbackResponse { inner },
+    )
+}
+
+/// Returned when a callback was dropped before it produced a response, such as when its
+/// element was removed from the tree or the call was cancelled.
+#[derive(thiserror::Error, Debug, Clone, Copy, PartialEq, Eq)]
+#[error("the callback was dropped before it responded")]
+pub struct CallbackCancelled;
+
+struct ResponseInner<R> {
+    state: Mutex<ResponseState<R>>,
+    ready: Condvar,
+}
+
+struct ResponseState<R> {
+    value: Option<R>,
+    is_closed: bool,
+    waker: Option<Waker>,
+}
+
+/// The sending half of a callback response. If it is dropped without responding, the
+/// [`CallbackResponse`] resolves to [`CallbackCancelled`].
+pub struct CallbackResponder<R> {
+    inner: Option<Arc<ResponseInner<R>>>,
+}
+
+impl<R> CallbackResponder<R> {
+    pub fn respond(mut self, value: R) {
+        if let Some(inner) = self.inner.take() {
+            inner.close(Some(value));
+        }
+    }
+}
+
+impl<R> Drop for CallbackResponder<R> {
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.take() {
+            inner.close(None);
+        }
+    }
+}
+
+impl<R> std::fmt::Debug for CallbackResponder<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CallbackResponder").finish_non_exhaustive()
+    }
+}
+
+impl<R> ResponseInner<R> {
+    fn close(&self, value: Option<R>) {
+        let waker = {
+            let mut state = self.state.lock();
+
+            state.value = value;
+            state.is_closed = true;
+
+            state.waker.take()
+        };
+
+        self.ready.notify_all();
+
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+/// The receiving half of a callback response. It may be awaited, polled with
+/// [`try_recv`](Self::try_recv), or blocked on from a background thread with
+/// [`wait`](Self::wait).
+pub struct CallbackResponse<R> {
+    inner: Arc<ResponseInner<R>>,
+}
+
+impl<R> CallbackResponse<R> {
+    /// Returns the response if the callback has finished, or `None` if it is still pending.
+    pub fn try_recv(&mut self) -> Option<Result<R, CallbackCancelled>> {
+        let mut state = self.inner.state.lock();
+
+        if !state.is_closed {
+            return None;
+        }
+
+        Some(state.value.take().ok_or(CallbackCancelled))
+    }
+
+    /// Blocks the current thread until the callback responds.
+    ///
+    /// This must not be called from the thread that drives the engine, as the callback would
+    /// never be invoked.
+    pub fn wait(self) -> Result<R, CallbackCancelled> {
+        let mut state = self.inner.state.lock();
+
+        while !state.is_closed {
+            self.inner.ready.wait(&mut state);
+        }
+
+        state.value.take().ok_or(CallbackCancelled)
+    }
+}
+
+impl<R> Future for CallbackResponse<R> {
+    type Output = Result<R, CallbackCancelled>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.inner.state.lock();
+
+        if state.is_closed {
+            return Poll::Ready(state.value.take().ok_or(CallbackCancelled));
+        }
+
+        state.waker = Some(cx.waker().clone());
+
+        Poll::Pending
+    }
+}
+
+impl<R> std::fmt::Debug for CallbackResponse<R> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CallbackResponse")
+            .field("is_closed", &self.inner.state.lock().is_closed)
+            .finish()
+    }
+}
@@ -2,7 +2,7 @@ use std::any::Any;
 
 use crate::{callback::CallbackId, widget::Widget};
 
-use super::{widget::ElementWidget, ElementBuildContext, ElementCallbackContext};
+use super::{widget::ElementWidget, ElementBuildContext, ElementCallbackContext, ElementPanic};
 
 pub trait ElementBuild: ElementWidget {
     fn build(&mut self, ctx: ElementBuildContext) -> Widget;
@@ -14,6 +14,14 @@ pub trait ElementBuild: ElementWidget {
         callback_id: CallbackId,
         arg: Box<dyn Any>,
     ) -> bool;
+
+    /// Called when a descendant of this element panics while building or laying out. Returning
+    /// `true` catches the panic and causes this element to be rebuilt, otherwise the panic is
+    /// offered to the next ancestor up the tree.
+    #[allow(unused_variables)]
+    fn on_descendant_panic(&mut self, panic: &ElementPanic) -> bool {
+        false
+    }
 }
 
 impl std::fmt::Debug for Box<dyn ElementBuild> {
@@ -6,6 +6,7 @@ use crate::{
         context::{ContextPlugins, ContextPluginsMut},
         Plugins,
     },
+    task::{ContextTaskExecutor, TaskExecutor},
     util::tree::Tree,
 };
 
@@ -17,6 +18,7 @@ pub struct ElementBuildContext<'ctx> {
     pub element_tree: &'ctx Tree<ElementId, Element>,
     pub dirty: &'ctx mut DirtyElements,
     pub callback_queue: &'ctx CallbackQueue,
+    pub task_executor: &'ctx TaskExecutor,
 
     pub element_id: &'ctx ElementId,
 }
@@ -56,3 +58,9 @@ impl ContextCallbackQueue for ElementBuildContext<'_> {
         self.callback_queue
     }
 }
+
+impl ContextTaskExecutor for ElementBuildContext<'_> {
+    fn task_executor(&self) -> &TaskExecutor {
+        self.task_executor
+    }
+}
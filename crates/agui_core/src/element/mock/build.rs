@@ -4,8 +4,8 @@ use crate::{
     callback::CallbackId,
     element::{
         build::ElementBuild, widget::ElementWidget, ElementBuildContext, ElementBuilder,
-        ElementCallbackContext, ElementMountContext, ElementType, ElementUnmountContext,
-        ElementUpdate,
+        ElementCallbackContext, ElementMountContext, ElementPanic, ElementType,
+        ElementUnmountContext, ElementUpdate,
     },
     widget::{IntoWidget, Widget},
 };
@@ -33,6 +33,8 @@ pub trait BuildElement {
         callback_id: CallbackId,
         arg: Box<dyn Any>,
     ) -> bool;
+
+    fn on_descendant_panic(&mut self, panic: &ElementPanic) -> bool;
 }
 
 #[derive(Default)]
@@ -97,4 +99,8 @@ impl ElementBuild for MockElement {
     ) -> bool {
         self.widget.mock.borrow_mut().call(ctx, callback_id, arg)
     }
+
+    fn on_descendant_panic(&mut self, panic: &ElementPanic) -> bool {
+        self.widget.mock.borrow_mut().on_descendant_panic(panic)
+    }
 }
@@ -61,6 +61,10 @@ impl From<DummyRenderObject> for RenderObject {
                 .expect_render_object_name()
                 .returning(|| "DummyRenderObject");
 
+            render_object_mock
+                .expect_sized_by_parent()
+                .returning(|| false);
+
             render_object_mock
                 .expect_intrinsic_size()
                 .returning(|_, _, _| 0.0);
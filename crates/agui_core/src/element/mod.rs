@@ -15,6 +15,7 @@ mod builder;
 mod context;
 #[cfg(any(test, feature = "mocks"))]
 pub mod mock;
+mod panic;
 pub mod proxy;
 pub mod render;
 mod update;
@@ -22,6 +23,7 @@ pub mod widget;
 
 pub use builder::*;
 pub use context::*;
+pub use panic::*;
 pub use update::*;
 
 slotmap::new_key_type! {
@@ -110,6 +112,33 @@ impl Element {
         }
     }
 
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn deactivate(&mut self) {
+        match self.inner {
+            ElementType::Proxy(ref mut widget) => widget.deactivate(),
+            ElementType::Widget(ref mut widget) => widget.deactivate(),
+            ElementType::Render(ref mut widget) => widget.deactivate(),
+        }
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn activate(&mut self) {
+        match self.inner {
+            ElementType::Proxy(ref mut widget) => widget.activate(),
+            ElementType::Widget(ref mut widget) => widget.activate(),
+            ElementType::Render(ref mut widget) => widget.activate(),
+        }
+    }
+
+    /// Offers a panic that occurred within one of this element's descendants to the element,
+    /// returning `true` if it was caught.
+    pub fn on_descendant_panic(&mut self, panic: &ElementPanic) -> bool {
+        match self.inner {
+            ElementType::Widget(ref mut widget) => widget.on_descendant_panic(panic),
+            ElementType::Proxy(_) | ElementType::Render(_) => false,
+        }
+    }
+
     pub fn create_render_object(&self) -> RenderObject {
         match self.inner {
             // Use the default render object for proxies and widgets
@@ -136,6 +165,12 @@ impl Element {
             return ElementUpdate::Noop;
         }
 
+        // An element may only be reused by a widget with the same key, otherwise keyed elements
+        // could be silently claimed by (or handed to) the wrong widget.
+        if self.widget.key() != new_widget.key() {
+            return ElementUpdate::Invalid;
+        }
+
         let result = match self.inner {
             ElementType::Proxy(ref mut widget) => widget.update(new_widget),
             ElementType::Widget(ref mut widget) => widget.update(new_widget),
@@ -181,9 +216,15 @@ impl Element {
         self.render_object_id = Some(id);
     }
 
-    pub(crate) fn update_render_object(&self, render_object: &mut RenderObject) {
+    /// Updates the given render object with the element's current configuration, returning
+    /// `true` if the render object may have changed as a result.
+    pub(crate) fn update_render_object(&self, render_object: &mut RenderObject) -> bool {
         if let ElementType::Render(ref widget) = self.inner {
             widget.update_render_object(render_object);
+
+            true
+        } else {
+            false
         }
     }
 }
@@ -0,0 +1,34 @@
+use std::any::Any;
+
+use super::ElementId;
+
+/// Describes a panic that occurred while building or laying out an element.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElementPanic {
+    /// The element that panicked.
+    pub element_id: ElementId,
+
+    /// The message the element panicked with.
+    pub message: String,
+
+    /// The names of the widgets from the root of the tree down to the one that panicked.
+    pub path: Vec<&'static str>,
+}
+
+impl ElementPanic {
+    pub(crate) fn message_of(payload: &(dyn Any + Send)) -> String {
+        if let Some(message) = payload.downcast_ref::<&str>() {
+            (*message).to_owned()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            String::from("Box<dyn Any>")
+        }
+    }
+}
+
+impl std::fmt::Display for ElementPanic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} panicked: {}", self.path.join(" > "), self.message)
+    }
+}
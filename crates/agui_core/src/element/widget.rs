@@ -11,6 +11,13 @@ pub trait ElementWidget: AsAny {
     #[allow(unused_variables)]
     fn unmount(&mut self, ctx: ElementUnmountContext) {}
 
+    /// Called when the element is removed from the tree. It may be reinserted into the tree
+    /// before the end of the update, otherwise it will be unmounted.
+    fn deactivate(&mut self) {}
+
+    /// Called when a deactivated element is reinserted into the tree.
+    fn activate(&mut self) {}
+
     /// Returns true if the widget is of the same type as the other widget.
     fn update(&mut self, new_widget: &Widget) -> ElementUpdate;
 }
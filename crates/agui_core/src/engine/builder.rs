@@ -1,23 +1,33 @@
 use std::{collections::VecDeque, sync::mpsc};
 
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 
 use crate::{
     callback::CallbackQueue,
     element::ElementId,
     listenable::EventBus,
     plugin::{Plugin, Plugins},
+    render::LayoutDriver,
+    task::TaskExecutor,
     util::tree::Tree,
     widget::{IntoWidget, Widget},
 };
 
-use super::{DirtyElements, Engine};
+use super::{dirty::RebuildQueue, DirtyElements, Engine, ReconcileStrategy};
+
+/// The number of times the engine will attempt to settle the tree in a single update before
+/// giving up, unless otherwise configured.
+pub const DEFAULT_ITERATION_LIMIT: usize = 10_000;
 
 pub struct EngineBuilder<P> {
     update_notifier_tx: Option<mpsc::Sender<()>>,
 
     root: Option<Widget>,
 
+    reconcile_strategy: ReconcileStrategy,
+    iteration_limit: usize,
+    warn_on_dropped_callbacks: bool,
+
     plugins: P,
 }
 
@@ -28,6 +38,10 @@ impl EngineBuilder<()> {
 
             root: None,
 
+            reconcile_strategy: ReconcileStrategy::default(),
+            iteration_limit: DEFAULT_ITERATION_LIMIT,
+            warn_on_dropped_callbacks: false,
+
             plugins: (),
         }
     }
@@ -47,6 +61,28 @@ where
         self
     }
 
+    /// Sets the strategy used to match unkeyed children across rebuilds.
+    pub fn with_reconcile_strategy(mut self, reconcile_strategy: ReconcileStrategy) -> Self {
+        self.reconcile_strategy = reconcile_strategy;
+        self
+    }
+
+    /// Sets the maximum number of times the engine will flush changes during a single update
+    /// before giving up and returning an error. This guards against widgets that continuously
+    /// mark themselves dirty or fire callbacks, which would otherwise hang the update forever.
+    pub fn with_iteration_limit(mut self, iteration_limit: usize) -> Self {
+        self.iteration_limit = iteration_limit;
+        self
+    }
+
+    /// Sets whether callbacks invoked on elements that no longer exist are logged as warnings.
+    /// They're dropped either way, but are only logged at the debug level by default since it's
+    /// routine for background work to outlive the widget that started it.
+    pub fn with_dropped_callback_warnings(mut self, enabled: bool) -> Self {
+        self.warn_on_dropped_callbacks = enabled;
+        self
+    }
+
     pub fn add_plugin<T>(self, plugin: T) -> EngineBuilder<(T, P)>
     where
         T: Plugin,
@@ -56,11 +92,17 @@ where
 
             root: None,
 
+            reconcile_strategy: self.reconcile_strategy,
+            iteration_limit: self.iteration_limit,
+            warn_on_dropped_callbacks: self.warn_on_dropped_callbacks,
+
             plugins: (plugin, self.plugins),
         }
     }
 
     pub fn build(self) -> Engine {
+        let update_notifier_tx = self.update_notifier_tx.unwrap_or_else(|| mpsc::channel().0);
+
         let mut engine = Engine {
             plugins: Plugins::new(self.plugins),
 
@@ -71,15 +113,28 @@ where
 
             dirty: DirtyElements::new(),
             callback_queue: CallbackQueue::new(
-                self.update_notifier_tx.unwrap_or_else(|| mpsc::channel().0),
+                update_notifier_tx.clone(),
+                self.warn_on_dropped_callbacks,
             ),
+            task_executor: TaskExecutor::new(update_notifier_tx),
+
+            rebuild_queue: RebuildQueue::default(),
+            rebuilds_saved: 0,
+            inactive_elements: FxHashSet::default(),
+            inactive_dirty: FxHashSet::default(),
 
-            rebuild_queue: VecDeque::default(),
-            removal_queue: FxHashSet::default(),
+            global_keys: FxHashMap::default(),
+            reparented_global_keys: FxHashSet::default(),
 
             sync_render_object_children: FxHashSet::default(),
             create_render_object: VecDeque::<ElementId>::default(),
             update_render_object: FxHashSet::default(),
+
+            reconcile_strategy: self.reconcile_strategy,
+            iteration_limit: self.iteration_limit,
+
+            relayout_queue: FxHashSet::default(),
+            layout_driver: LayoutDriver::default(),
         };
 
         engine.init(self.root.expect("root is not set"));
@@ -1,3 +1,5 @@
+use std::{cmp::Reverse, collections::BinaryHeap};
+
 use rustc_hash::FxHashSet;
 
 use crate::{element::ElementId, util::map::ElementSet};
@@ -28,7 +30,38 @@ impl DirtyElements {
         self.inner.insert(element_id);
     }
 
+    pub(super) fn iter(&self) -> impl Iterator<Item = ElementId> + '_ {
+        self.inner.iter().copied()
+    }
+
     pub(super) fn drain(&mut self) -> impl Iterator<Item = ElementId> + '_ {
         self.inner.drain()
     }
 }
+
+/// Elements waiting to be rebuilt, yielded shallowest first so that an element is always rebuilt
+/// after any of its queued ancestors.
+#[derive(Default)]
+pub(super) struct RebuildQueue {
+    inner: BinaryHeap<Reverse<(usize, ElementId)>>,
+}
+
+impl RebuildQueue {
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_empty()
+    }
+
+    pub fn push(&mut self, depth: usize, element_id: ElementId) {
+        self.inner.push(Reverse((depth, element_id)));
+    }
+
+    pub fn pop(&mut self) -> Option<ElementId> {
+        self.inner.pop().map(|Reverse((_, element_id))| element_id)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = ElementId> + '_ {
+        self.inner
+            .iter()
+            .map(|Reverse((_, element_id))| *element_id)
+    }
+}
@@ -0,0 +1,56 @@
+use thiserror::Error;
+
+use crate::{element::ElementId, render::RenderObjectId, unit::Key};
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum EngineError {
+    /// The tree failed to settle within the engine's iteration limit, which almost always means
+    /// that one or more widgets mark themselves dirty or fire callbacks every time they're built.
+    ///
+    /// `dirty` lists the elements that were still waiting to be rebuilt and `callbacks` lists the
+    /// elements that still had callbacks waiting to be invoked when the limit was reached, along
+    /// with the names of their widgets.
+    #[error(
+        "update did not settle after {limit} iterations (dirty: {dirty:?}, callbacks: {callbacks:?})"
+    )]
+    IterationLimitReached {
+        limit: usize,
+        dirty: Vec<(ElementId, &'static str)>,
+        callbacks: Vec<(ElementId, &'static str)>,
+    },
+
+    #[error("element {element_id:?} does not exist in the tree")]
+    MissingElement { element_id: ElementId },
+
+    #[error("render object {render_object_id:?} does not exist in the tree")]
+    MissingRenderObject { render_object_id: RenderObjectId },
+
+    #[error(
+        "render object {render_object_id:?} ({render_object_name}) is missing its child {child_id:?}"
+    )]
+    MissingChildRenderObject {
+        render_object_id: RenderObjectId,
+        render_object_name: &'static str,
+        child_id: RenderObjectId,
+    },
+
+    #[error("element {element_id:?} ({widget_name}) has no render object")]
+    ElementMissingRenderObject {
+        element_id: ElementId,
+        widget_name: &'static str,
+    },
+
+    #[error("render object {render_object_name} has not been laid out")]
+    NotLaidOut { render_object_name: &'static str },
+
+    #[error("global key {key} is used by more than one widget")]
+    DuplicateGlobalKey { key: Key },
+
+    #[error("global key {key} cannot be moved into its own subtree")]
+    GlobalKeyInOwnSubtree { key: Key },
+
+    /// The children of an element could not be reconciled with the widgets it built, which means
+    /// the element tree has been left in an inconsistent state.
+    #[error("element {element_id:?} failed to reconcile its children")]
+    InconsistentChildren { element_id: ElementId },
+}
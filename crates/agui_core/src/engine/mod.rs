@@ -1,27 +1,35 @@
-use std::collections::VecDeque;
+use std::{
+    any::Any,
+    collections::VecDeque,
+    panic::{self, AssertUnwindSafe},
+};
 
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 
 use crate::{
     callback::{CallbackInvoke, CallbackQueue},
     element::{
         Element, ElementBuildContext, ElementCallbackContext, ElementId, ElementMountContext,
-        ElementUnmountContext, ElementUpdate,
+        ElementPanic, ElementUnmountContext, ElementUpdate,
     },
-    engine::event::{ElementDestroyedEvent, ElementSpawnedEvent},
+    engine::event::{ElementDestroyedEvent, ElementReparentEvent, ElementSpawnedEvent},
     listenable::EventBus,
     plugin::{
         context::{
             ContextPlugins, PluginAfterUpdateContext, PluginBeforeUpdateContext,
-            PluginElementBuildContext, PluginElementMountContext, PluginElementUnmountContext,
-            PluginInitContext,
+            PluginElementBuildContext, PluginElementDeactivateContext, PluginElementMountContext,
+            PluginElementRemountContext, PluginElementUnmountContext, PluginInitContext,
         },
         Plugins,
     },
     query::WidgetQuery,
-    render::{RenderObject, RenderObjectContextMut, RenderObjectId},
-    unit::{Constraints, Key},
-    util::{map::ElementSet, tree::Tree},
+    render::{LayoutDriver, RenderObject, RenderObjectContext, RenderObjectId},
+    task::TaskExecutor,
+    unit::{Constraints, HitTestResult, Key, Offset},
+    util::{
+        map::{ElementSet, TypeMap},
+        tree::Tree,
+    },
     widget::Widget,
 };
 
@@ -29,9 +37,15 @@ use self::{builder::EngineBuilder, event::ElementRebuiltEvent};
 
 pub mod builder;
 mod dirty;
+mod error;
 pub mod event;
+mod reconcile;
+
+use self::dirty::RebuildQueue;
 
 pub use dirty::DirtyElements;
+pub use error::EngineError;
+pub use reconcile::ReconcileStrategy;
 
 pub struct Engine {
     plugins: Plugins,
@@ -43,13 +57,27 @@ pub struct Engine {
 
     dirty: DirtyElements,
     callback_queue: CallbackQueue,
+    task_executor: TaskExecutor,
+
+    rebuild_queue: RebuildQueue,
+    rebuilds_saved: usize,
+    inactive_elements: ElementSet,
+    /// Elements that were marked dirty while inactive, which must be rebuilt if they're
+    /// reactivated.
+    inactive_dirty: ElementSet,
 
-    rebuild_queue: VecDeque<ElementId>,
-    removal_queue: ElementSet,
+    global_keys: FxHashMap<Key, ElementId>,
+    reparented_global_keys: FxHashSet<Key>,
 
     sync_render_object_children: ElementSet,
     create_render_object: VecDeque<ElementId>,
     update_render_object: ElementSet,
+
+    reconcile_strategy: ReconcileStrategy,
+    iteration_limit: usize,
+
+    relayout_queue: FxHashSet<RenderObjectId>,
+    layout_driver: LayoutDriver,
 }
 
 impl ContextPlugins<'_> for Engine {
@@ -95,12 +123,53 @@ impl Engine {
         WidgetQuery::new(&self.element_tree)
     }
 
+    /// Determines which render objects lie under the given position, which must be in the
+    /// global coordinate space.
+    ///
+    /// The tree must have been laid out for anything to be hit.
+    pub fn hit_test(&self, position: Offset) -> HitTestResult {
+        let mut result = HitTestResult::new();
+
+        let Some(root_id) = self.render_object_tree.root() else {
+            return result;
+        };
+
+        if let Some(render_object) = self.render_object_tree.get(root_id) {
+            render_object.hit_test(
+                RenderObjectContext {
+                    plugins: &self.plugins,
+
+                    render_object_tree: &self.render_object_tree,
+
+                    render_object_id: &root_id,
+                },
+                &mut result,
+                position,
+            );
+        }
+
+        result
+    }
+
     pub fn callback_queue(&self) -> &CallbackQueue {
         &self.callback_queue
     }
 
+    pub fn task_executor(&self) -> &TaskExecutor {
+        &self.task_executor
+    }
+
+    /// Get the number of rebuilds that were skipped because the element had already been rebuilt
+    /// by one of its ancestors earlier in the same pass.
+    pub fn rebuilds_saved(&self) -> usize {
+        self.rebuilds_saved
+    }
+
     pub fn has_changes(&self) -> bool {
-        !self.rebuild_queue.is_empty() || !self.dirty.is_empty() || !self.callback_queue.is_empty()
+        !self.rebuild_queue.is_empty()
+            || !self.dirty.is_empty()
+            || !self.callback_queue.is_empty()
+            || !self.task_executor.is_empty()
     }
 
     /// Mark a widget as dirty, causing it to be rebuilt on the next update.
@@ -108,6 +177,41 @@ impl Engine {
         self.dirty.insert(element_id);
     }
 
+    /// Mark a render object as needing layout, causing it and each of its ancestors up to the
+    /// nearest relayout boundary to be laid out on the next update.
+    pub fn mark_needs_layout(&mut self, render_object_id: RenderObjectId) {
+        let mut current_id = render_object_id;
+
+        loop {
+            let Some(render_object) = self.render_object_tree.get_mut(current_id) else {
+                return;
+            };
+
+            // If an ancestor is already waiting on layout, then so is everything between it and
+            // its relayout boundary.
+            if current_id != render_object_id && render_object.needs_layout() {
+                return;
+            }
+
+            // A relayout boundary's size can't change, but an ancestor that read its intrinsic
+            // size may still need to lay out differently.
+            let is_relayout_boundary =
+                render_object.is_relayout_boundary() && !render_object.parent_uses_intrinsic_size();
+
+            render_object.mark_needs_layout();
+
+            match self.render_object_tree.get_parent(current_id) {
+                Some(parent_id) if !is_relayout_boundary => current_id = parent_id,
+
+                _ => {
+                    self.relayout_queue.insert(current_id);
+
+                    return;
+                }
+            }
+        }
+    }
+
     /// Initializes plugins and sets the initial root widget, but does not build it or spawn
     /// any children.
     ///
@@ -124,76 +228,200 @@ impl Engine {
 
         let root_id = self.process_spawn(None, root);
 
-        self.rebuild_queue.push_back(root_id);
+        self.rebuild_queue.push(0, root_id);
     }
 
     /// Update the UI tree.
+    ///
+    /// Returns an error if the tree fails to settle within the engine's iteration limit, or if the
+    /// element or render object trees are found to be in an inconsistent state.
     #[tracing::instrument(level = "trace", skip(self))]
-    pub fn update(&mut self) {
+    pub fn update(&mut self) -> Result<(), EngineError> {
         tracing::debug!("updating widget tree");
 
         self.plugins
             .on_before_update(&mut PluginBeforeUpdateContext {
                 element_tree: &self.element_tree,
+
+                callback_queue: &self.callback_queue,
             });
 
-        // Update everything until all widgets fall into a stable state. Incorrectly set up widgets may
-        // cause an infinite loop, so be careful.
+        let mut iterations = 0;
+
+        // Update everything until all widgets fall into a stable state. Incorrectly set up widgets
+        // may never settle, so we give up once the iteration limit has been reached.
         'layout: loop {
             'changes: loop {
-                self.flush_rebuilds();
+                self.flush_rebuilds()?;
+
+                self.flush_dirty()?;
 
-                self.flush_dirty();
+                self.flush_tasks();
 
-                self.flush_callbacks();
+                self.flush_callbacks()?;
 
                 if !self.has_changes() {
                     break 'changes;
                 }
+
+                iterations += 1;
+
+                if iterations >= self.iteration_limit {
+                    self.reparented_global_keys.clear();
+
+                    return Err(self.iteration_limit_reached());
+                }
             }
 
             // We sync render after the rebuild loop to prevent unnecessary work keeping the render
-            // tree up-to-date. This is done before `flush_removals` so that we can steal any render
-            // objects that would otherwise be removed.
-            self.sync_render_objects();
+            // tree up-to-date.
+            self.sync_render_objects()?;
 
-            self.flush_removals();
-
-            self.flush_layout();
+            self.flush_layout()?;
 
             if !self.has_changes() {
-                break 'layout;
+                // Any element that was deactivated and has not been reclaimed by the time the tree
+                // has settled will never be, so it's safe to destroy them.
+                self.flush_removals()?;
+
+                if !self.has_changes() {
+                    break 'layout;
+                }
+            }
+
+            // Laying out the tree may cause further changes, such as a widget that rebuilds itself
+            // whenever it's laid out, so each pass through layout counts towards the limit too.
+            iterations += 1;
+
+            if iterations >= self.iteration_limit {
+                self.reparented_global_keys.clear();
+
+                return Err(self.iteration_limit_reached());
             }
         }
 
+        self.reparented_global_keys.clear();
+
         self.plugins.on_after_update(&mut PluginAfterUpdateContext {
             element_tree: &self.element_tree,
         });
+
+        Ok(())
+    }
+
+    /// Builds an error naming the elements that prevented the tree from settling.
+    fn iteration_limit_reached(&self) -> EngineError {
+        let describe = |element_ids: FxHashSet<ElementId>| {
+            let mut elements = element_ids
+                .into_iter()
+                .filter_map(|element_id| {
+                    self.element_tree
+                        .get(element_id)
+                        .map(|element| (element_id, element.widget_name()))
+                })
+                .collect::<Vec<_>>();
+
+            elements.sort_by_key(|(element_id, _)| *element_id);
+
+            elements
+        };
+
+        let dirty = describe(self.dirty.iter().chain(self.rebuild_queue.iter()).collect());
+
+        let callbacks = describe(
+            self.callback_queue
+                .pending()
+                .into_iter()
+                .map(|callback_id| callback_id.element_id())
+                .collect(),
+        );
+
+        tracing::error!(
+            limit = self.iteration_limit,
+            ?dirty,
+            ?callbacks,
+            "update did not settle within the iteration limit"
+        );
+
+        EngineError::IterationLimitReached {
+            limit: self.iteration_limit,
+            dirty,
+            callbacks,
+        }
     }
 
     #[tracing::instrument(level = "trace", skip(self))]
-    pub fn flush_rebuilds(&mut self) {
+    pub fn flush_rebuilds(&mut self) -> Result<(), EngineError> {
+        let mut rebuilt = ElementSet::default();
+
         // Apply any queued modifications
-        while let Some(element_id) = self.rebuild_queue.pop_front() {
-            self.process_rebuild(element_id);
+        while let Some(element_id) = self.rebuild_queue.pop() {
+            // An element may be queued more than once, such as when it's marked dirty and then
+            // rebuilt by one of its ancestors. Since ancestors are always rebuilt first, it will
+            // already be up to date.
+            if !rebuilt.insert(element_id) {
+                tracing::trace!(?element_id, "element was already rebuilt, skipping");
+
+                self.rebuilds_saved += 1;
+
+                continue;
+            }
+
+            // An ancestor may have removed the element since it was queued.
+            if self.is_inactive(element_id) {
+                tracing::trace!(?element_id, "element is inactive, deferring rebuild");
+
+                self.inactive_dirty.insert(element_id);
+
+                continue;
+            }
+
+            self.process_build(element_id)?;
         }
+
+        Ok(())
     }
 
     #[tracing::instrument(level = "trace", skip(self))]
-    pub fn flush_dirty(&mut self) {
-        for element_id in self.dirty.drain() {
+    pub fn flush_dirty(&mut self) -> Result<(), EngineError> {
+        for element_id in self.dirty.drain().collect::<Vec<_>>() {
+            // Elements may be marked dirty while they're being destroyed.
+            let Some(element) = self.element_tree.get(element_id) else {
+                continue;
+            };
+
+            if self.is_inactive(element_id) {
+                tracing::trace!(?element_id, "element is inactive, deferring rebuild");
+
+                self.inactive_dirty.insert(element_id);
+
+                continue;
+            }
+
             tracing::trace!(
                 ?element_id,
-                widget = self.element_tree.get(element_id).unwrap().widget_name(),
+                widget = element.widget_name(),
                 "queueing widget for rebuild"
             );
 
-            self.rebuild_queue.push_back(element_id);
+            let depth = self
+                .element_tree
+                .get_depth(element_id)
+                .ok_or(EngineError::MissingElement { element_id })?;
+
+            self.rebuild_queue.push(depth, element_id);
         }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    pub fn flush_tasks(&mut self) {
+        self.task_executor.poll();
     }
 
     #[tracing::instrument(level = "trace", skip(self))]
-    pub fn flush_callbacks(&mut self) {
+    pub fn flush_callbacks(&mut self) -> Result<(), EngineError> {
         let callback_invokes = self.callback_queue.take();
 
         for CallbackInvoke {
@@ -203,6 +431,14 @@ impl Engine {
         {
             let element_id = callback_id.element_id();
 
+            // Callbacks may be invoked from other threads at any time, so it's entirely possible
+            // that the element has been removed since the callback was queued.
+            if !self.element_tree.contains(element_id) || self.is_inactive(element_id) {
+                self.callback_queue.log_dropped(callback_id);
+
+                continue;
+            }
+
             self.element_tree
                 .with(element_id, |element_tree, element| {
                     let changed = element.call(
@@ -225,35 +461,96 @@ impl Engine {
                             "element updated, queueing for rebuild"
                         );
 
-                        self.rebuild_queue.push_back(element_id);
+                        let depth = element_tree
+                            .get_depth(element_id)
+                            .ok_or(EngineError::MissingElement { element_id })?;
+
+                        self.rebuild_queue.push(depth, element_id);
                     }
+
+                    Ok(())
                 })
-                .expect("cannot call a callback on a widget that does not exist");
+                .unwrap_or(Err(EngineError::MissingElement { element_id }))?;
         }
+
+        Ok(())
     }
 
     #[tracing::instrument(level = "trace", skip(self))]
-    pub fn flush_layout(&mut self) {
-        let Some(root_id) = self.render_object_tree.root() else {
-            return;
-        };
+    pub fn flush_layout(&mut self) -> Result<(), EngineError> {
+        let mut relayout_queue = self
+            .relayout_queue
+            .drain()
+            .filter(|render_object_id| self.render_object_tree.contains(*render_object_id))
+            .collect::<Vec<_>>();
 
-        // TODO: Layout using a loop rather than deeply recursively
-        self.render_object_tree
-            .with(root_id, |render_object_tree, render_object| {
-                render_object.layout(
-                    RenderObjectContextMut {
-                        plugins: &mut self.plugins,
+        // Lay out boundaries closest to the root first, since doing so may lay out any boundaries
+        // nested within them.
+        relayout_queue.sort_by_cached_key(|render_object_id| {
+            self.render_object_tree.get_depth(*render_object_id)
+        });
 
-                        render_object_tree,
+        for render_object_id in relayout_queue {
+            let render_object = self
+                .render_object_tree
+                .get(render_object_id)
+                .ok_or(EngineError::MissingRenderObject { render_object_id })?;
 
-                        render_object_id: &root_id,
-                    },
-                    // The root element is always unbounded
-                    Constraints::expand(),
-                );
-            })
-            .expect("cannot layout a widget that doesn't exist");
+            // It may have already been laid out by an ancestor boundary.
+            if !render_object.needs_layout() {
+                continue;
+            }
+
+            let constraints = if self.render_object_tree.root() == Some(render_object_id) {
+                // The root element is always unbounded
+                Constraints::expand()
+            } else if self
+                .render_object_tree
+                .get_parent(render_object_id)
+                .is_none()
+            {
+                // Render objects of inactive elements are detached from the tree, and will be laid
+                // out by their new parent if they're ever reclaimed.
+                continue;
+            } else if let Some(constraints) = render_object.constraints() {
+                constraints
+            } else {
+                // A render object that has never been laid out can only be laid out by its parent.
+                continue;
+            };
+
+            tracing::trace!(
+                ?render_object_id,
+                render_object = render_object.render_object_name(),
+                "laying out relayout boundary"
+            );
+
+            self.layout_driver.layout(
+                &mut self.plugins,
+                &mut self.render_object_tree,
+                render_object_id,
+                constraints,
+            )?;
+
+            if let Some((panicked_id, payload)) = self.layout_driver.take_panic() {
+                let element_id = self
+                    .render_object_tree
+                    .get(panicked_id)
+                    .and_then(RenderObject::element_id);
+
+                let Some(element_id) = element_id else {
+                    panic::resume_unwind(payload);
+                };
+
+                self.catch_panic(element_id, payload);
+
+                // The ancestors of the render object that panicked were left waiting on layout,
+                // so the boundary must be laid out again once the error boundary has rebuilt.
+                self.relayout_queue.insert(render_object_id);
+            }
+        }
+
+        Ok(())
     }
 
     #[tracing::instrument(level = "trace", name = "spawn", skip(self))]
@@ -264,6 +561,13 @@ impl Engine {
 
         let element_id = self.element_tree.add(parent_id, element);
 
+        self.callback_queue.insert_element(element_id);
+
+        // Any previous holder of the key has already been checked to be on its way out.
+        if let Some(key @ Key::Global(_)) = widget.key() {
+            self.global_keys.insert(key, element_id);
+        }
+
         self.element_tree.with(element_id, |element_tree, element| {
             self.plugins
                 .on_element_mount(&mut PluginElementMountContext {
@@ -297,13 +601,13 @@ impl Engine {
     }
 
     #[tracing::instrument(level = "trace", name = "build", skip(self, element_id))]
-    fn process_build(&mut self, element_id: ElementId) {
+    fn process_build(&mut self, element_id: ElementId) -> Result<(), EngineError> {
         let mut build_queue = VecDeque::new();
 
         build_queue.push_back(element_id);
 
         while let Some(element_id) = build_queue.pop_front() {
-            let new_widgets = self
+            let result = self
                 .element_tree
                 .with(element_id, |element_tree, element| {
                     self.plugins
@@ -316,30 +620,51 @@ impl Engine {
                             element,
                         });
 
-                    element.build(ElementBuildContext {
-                        plugins: &mut self.plugins,
+                    panic::catch_unwind(AssertUnwindSafe(|| {
+                        element.build(ElementBuildContext {
+                            plugins: &mut self.plugins,
 
-                        element_tree,
-                        dirty: &mut self.dirty,
-                        callback_queue: &self.callback_queue,
+                            element_tree,
+                            dirty: &mut self.dirty,
+                            callback_queue: &self.callback_queue,
+                            task_executor: &self.task_executor,
 
-                        element_id: &element_id,
-                    })
+                            element_id: &element_id,
+                        })
+                    }))
                 })
-                .expect("cannot build a widget that doesn't exist");
+                .ok_or(EngineError::MissingElement { element_id })?;
 
-            self.bus.emit(&ElementRebuiltEvent { element_id });
+            let new_widgets = match result {
+                Ok(new_widgets) => new_widgets,
 
-            if new_widgets.is_empty() {
-                continue;
-            }
+                Err(payload) => {
+                    // The element's existing children are left in place until the error boundary
+                    // that caught the panic rebuilds and replaces them.
+                    self.catch_panic(element_id, payload);
+
+                    continue;
+                }
+            };
+
+            self.bus.emit(&ElementRebuiltEvent { element_id });
 
             let old_children = self
                 .element_tree
                 .get_children(element_id)
-                .expect("newly created element does not exist in the tree")
+                .ok_or(EngineError::MissingElement { element_id })?
                 .clone();
 
+            if new_widgets.is_empty() && old_children.is_empty() {
+                continue;
+            }
+
+            let child_depth = self
+                .element_tree
+                .get_depth(element_id)
+                .ok_or(EngineError::MissingElement { element_id })?
+                + 1;
+
             let mut new_children_top = 0;
             let mut old_children_top = 0;
             let mut new_children_bottom = new_widgets.len() as i32 - 1;
@@ -355,10 +680,11 @@ impl Engine {
                 let new_widget = new_widgets.get(new_children_top as usize);
 
                 if let Some((old_child_id, new_widget)) = old_child_id.zip(new_widget) {
-                    let old_child = self
-                        .element_tree
-                        .get_mut(old_child_id)
-                        .expect("child element does not exist in the tree");
+                    let old_child = self.element_tree.get_mut(old_child_id).ok_or(
+                        EngineError::MissingElement {
+                            element_id: old_child_id,
+                        },
+                    )?;
 
                     match old_child.update(new_widget) {
                         ElementUpdate::Noop => {
@@ -382,7 +708,7 @@ impl Engine {
                                 "element was retained but must be rebuilt"
                             );
 
-                            self.rebuild_queue.push_back(old_child_id);
+                            self.rebuild_queue.push(child_depth, old_child_id);
                             self.update_render_object.insert(old_child_id);
                         }
 
@@ -406,10 +732,11 @@ impl Engine {
                 let new_widget = new_widgets.get(new_children_bottom as usize);
 
                 if let Some((old_child_id, new_widget)) = old_child_id.zip(new_widget) {
-                    let old_child = self
-                        .element_tree
-                        .get_mut(old_child_id)
-                        .expect("child element does not exist in the tree");
+                    let old_child = self.element_tree.get_mut(old_child_id).ok_or(
+                        EngineError::MissingElement {
+                            element_id: old_child_id,
+                        },
+                    )?;
 
                     match old_child.update(new_widget) {
                         ElementUpdate::Noop => {
@@ -432,7 +759,7 @@ impl Engine {
                                 "element was retained but must be rebuilt"
                             );
 
-                            self.rebuild_queue.push_back(old_child_id);
+                            self.rebuild_queue.push(child_depth, old_child_id);
 
                             // If the child has a render object, we need to update it.
                             if old_child.render_object_id().is_some() {
@@ -453,18 +780,25 @@ impl Engine {
             // Scan the old children in the middle of the list.
             let have_old_children = old_children_top <= old_children_bottom;
             let mut old_keyed_children = FxHashMap::<Key, ElementId>::default();
+            let mut old_unkeyed_children = TypeMap::<VecDeque<ElementId>>::default();
 
             while old_children_top <= old_children_bottom {
                 if let Some(old_child_id) = old_children.get(old_children_top as usize) {
-                    let old_child = self
-                        .element_tree
-                        .get(*old_child_id)
-                        .expect("child element does not exist in the tree");
+                    let old_child = self.element_tree.get(*old_child_id).ok_or(
+                        EngineError::MissingElement {
+                            element_id: *old_child_id,
+                        },
+                    )?;
 
                     if let Some(key) = old_child.widget().key() {
                         old_keyed_children.insert(key, *old_child_id);
+                    } else if self.reconcile_strategy == ReconcileStrategy::ByType {
+                        old_unkeyed_children
+                            .entry(old_child.widget().widget_type_id())
+                            .or_default()
+                            .push_back(*old_child_id);
                     } else {
-                        // unmount / deactivate the child
+                        self.deactivate_element(*old_child_id)?;
                     }
                 }
 
@@ -478,10 +812,11 @@ impl Engine {
                 if have_old_children {
                     if let Some(key) = new_widget.key() {
                         if let Some(old_child_id) = old_keyed_children.get(&key).copied() {
-                            let old_child = self
-                                .element_tree
-                                .get_mut(old_child_id)
-                                .expect("child element does not exist in the tree");
+                            let old_child = self.element_tree.get_mut(old_child_id).ok_or(
+                                EngineError::MissingElement {
+                                    element_id: old_child_id,
+                                },
+                            )?;
 
                             match old_child.update(new_widget) {
                                 ElementUpdate::Noop => {
@@ -505,7 +840,7 @@ impl Engine {
                                         "keyed element was retained but must be rebuilt"
                                     );
 
-                                    self.rebuild_queue.push_back(old_child_id);
+                                    self.rebuild_queue.push(child_depth, old_child_id);
 
                                     // If the child has a render object, we need to update it.
                                     if old_child.render_object_id().is_some() {
@@ -527,6 +862,83 @@ impl Engine {
                     }
                 }
 
+                // Unkeyed widgets may reuse the first remaining old child of the same type.
+                if new_widget.key().is_none() {
+                    if let Some(old_child_id) = old_unkeyed_children
+                        .get_mut(&new_widget.widget_type_id())
+                        .and_then(VecDeque::pop_front)
+                    {
+                        let old_child = self.element_tree.get_mut(old_child_id).ok_or(
+                            EngineError::MissingElement {
+                                element_id: old_child_id,
+                            },
+                        )?;
+
+                        let is_retained = match old_child.update(new_widget) {
+                            ElementUpdate::Noop => {
+                                tracing::trace!(
+                                    parent_id = ?element_id,
+                                    element_id = ?old_child_id,
+                                    widget = ?new_widget,
+                                    new_position = new_children_top,
+                                    "element of the same type was retained"
+                                );
+
+                                true
+                            }
+
+                            ElementUpdate::RebuildNecessary => {
+                                tracing::trace!(
+                                    parent_id = ?element_id,
+                                    element_id = ?old_child_id,
+                                    widget = ?new_widget,
+                                    new_position = new_children_top,
+                                    "element of the same type was retained but must be rebuilt"
+                                );
+
+                                self.rebuild_queue.push(child_depth, old_child_id);
+
+                                // If the child has a render object, we need to update it.
+                                if old_child.render_object_id().is_some() {
+                                    self.update_render_object.insert(old_child_id);
+                                }
+
+                                true
+                            }
+
+                            ElementUpdate::Invalid => {
+                                self.deactivate_element(old_child_id)?;
+
+                                false
+                            }
+                        };
+
+                        if is_retained {
+                            new_children[new_children_top as usize] = Some(old_child_id);
+                            new_children_top += 1;
+
+                            continue;
+                        }
+                    }
+                }
+
+                // If the widget has a global key, its element may exist elsewhere in the tree.
+                if let Some(old_child_id) = self.reclaim_global_key(element_id, new_widget)? {
+                    new_children[new_children_top as usize] = Some(old_child_id);
+                    new_children_top += 1;
+
+                    continue;
+                }
+
+                // The previous holder of the key is only allowed to linger if it's on its way out.
+                if let Some(key @ Key::Global(_)) = new_widget.key() {
+                    if let Some(existing_id) = self.global_keys.get(&key).copied() {
+                        if !self.is_inactive(existing_id) {
+                            return Err(EngineError::DuplicateGlobalKey { key });
+                        }
+                    }
+                }
+
                 let new_child_id = self.process_spawn(Some(element_id), new_widget.clone());
 
                 new_children[new_children_top as usize] = Some(new_child_id);
@@ -536,12 +948,13 @@ impl Engine {
             }
 
             // We've scanned the whole list.
-            assert!(old_children_top == old_children_bottom + 1);
-            assert!(new_children_top == new_children_bottom + 1);
-            assert!(
-                new_widgets.len() as i32 - new_children_top
-                    == old_children.len() as i32 - old_children_top
-            );
+            if old_children_top != old_children_bottom + 1
+                || new_children_top != new_children_bottom + 1
+                || new_widgets.len() as i32 - new_children_top
+                    != old_children.len() as i32 - old_children_top
+            {
+                return Err(EngineError::InconsistentChildren { element_id });
+            }
 
             new_children_bottom = new_widgets.len() as i32 - 1;
             old_children_bottom = old_children.len() as i32 - 1;
@@ -557,15 +970,18 @@ impl Engine {
             }
 
             // Clean up any of the remaining middle nodes from the old list.
-            // for old_keyed_child_id in old_keyed_children {
-            //     // deactivate the child
-            // }
+            for old_child_id in old_keyed_children
+                .into_values()
+                .chain(old_unkeyed_children.into_values().flatten())
+            {
+                self.deactivate_element(old_child_id)?;
+            }
 
             // The list of new children should never have any holes in it.
             let new_children = new_children
                 .into_iter()
-                .map(Option::unwrap)
-                .collect::<Vec<_>>();
+                .collect::<Option<Vec<_>>>()
+                .ok_or(EngineError::InconsistentChildren { element_id })?;
 
             // If the list of children has changed, we need to make sure the parent has its
             // render object child order updated as well.
@@ -574,130 +990,446 @@ impl Engine {
             }
 
             for child_id in new_children {
-                self.removal_queue.remove(&child_id);
-
-                // reparent each child
+                // Reparenting each child in order sorts them. They should have all remained as
+                // children of the same parent.
                 if self.element_tree.reparent(Some(element_id), child_id) {
-                    panic!("element should have remained as a child of the same parent")
+                    return Err(EngineError::InconsistentChildren { element_id });
                 }
             }
         }
-    }
-
-    #[tracing::instrument(level = "trace", name = "rebuild", skip(self))]
-    fn process_rebuild(&mut self, element_id: ElementId) {
-        // Grab the current children so we know which ones to remove post-build
-        let children = self
-            .element_tree
-            .get_children(element_id)
-            .map(Vec::clone)
-            .unwrap_or_default();
-
-        // Add the children to the removal queue. If any wish to be retained, they will be
-        // removed from the queue during `process_build`.
-        for child_id in children {
-            self.removal_queue.insert(child_id);
-        }
 
-        self.process_build(element_id);
+        Ok(())
     }
 
-    #[tracing::instrument(level = "trace", skip(self))]
-    fn flush_removals(&mut self) {
-        let mut destroy_queue = self.removal_queue.drain().collect::<VecDeque<_>>();
-
-        while let Some(element_id) = destroy_queue.pop_front() {
-            // Queue the element's children for removal
-            if let Some(children) = self.element_tree.get_children(element_id) {
-                for child_id in children {
-                    destroy_queue.push_back(*child_id);
-                }
-            }
+    /// Offers a panic raised by an element to each of its ancestors in turn, marking the first one
+    /// that catches it as dirty so that it can replace the broken subtree. If no ancestor catches
+    /// the panic, it's resumed.
+    fn catch_panic(&mut self, element_id: ElementId, payload: Box<dyn Any + Send>) {
+        let mut ancestors = self
+            .element_tree
+            .iter_parents(element_id)
+            .collect::<Vec<_>>();
+
+        let path = ancestors
+            .iter()
+            .rev()
+            .copied()
+            .chain(Some(element_id))
+            .filter_map(|element_id| self.element_tree.get(element_id))
+            .map(Element::widget_name)
+            .collect::<Vec<_>>();
+
+        let element_panic = ElementPanic {
+            element_id,
+            message: ElementPanic::message_of(payload.as_ref()),
+            path,
+        };
 
-            self.element_tree
-                .with(element_id, |element_tree, element| {
-                    self.plugins
-                        .on_element_unmount(&mut PluginElementUnmountContext {
-                            element_tree,
-                            dirty: &mut self.dirty,
+        ancestors.retain(|ancestor_id| !self.inactive_elements.contains(ancestor_id));
 
-                            element_id: &element_id,
-                            element,
-                        });
+        for ancestor_id in ancestors {
+            let Some(ancestor) = self.element_tree.get_mut(ancestor_id) else {
+                continue;
+            };
+
+            if ancestor.on_descendant_panic(&element_panic) {
+                tracing::error!(
+                    ?element_id,
+                    boundary_id = ?ancestor_id,
+                    boundary = ancestor.widget_name(),
+                    "{}",
+                    element_panic
+                );
 
-                    element.unmount(ElementUnmountContext {
-                        plugins: &mut self.plugins,
+                self.dirty.insert(ancestor_id);
 
-                        element_tree,
-                        dirty: &mut self.dirty,
+                return;
+            }
+        }
 
-                        element_id: &element_id,
-                    });
-                })
-                .expect("cannot destroy an element that doesn't exist");
+        panic::resume_unwind(payload);
+    }
 
-            self.bus.emit(&ElementDestroyedEvent { element_id });
+    /// Moves the element registered to the widget's global key, if any, from wherever it is in
+    /// the tree to become a child of `parent_id`, retaining its state and render object.
+    #[tracing::instrument(level = "trace", skip(self, new_widget))]
+    fn reclaim_global_key(
+        &mut self,
+        parent_id: ElementId,
+        new_widget: &Widget,
+    ) -> Result<Option<ElementId>, EngineError> {
+        let Some(key @ Key::Global(_)) = new_widget.key() else {
+            return Ok(None);
+        };
 
-            let element = self.element_tree.remove(element_id, false).unwrap();
+        let Some(element_id) = self.global_keys.get(&key).copied() else {
+            return Ok(None);
+        };
 
-            let widget = element.widget();
+        let old_parent_id = self.element_tree.get_parent(element_id);
 
-            tracing::trace!(?element_id, ?widget, "destroyed widget");
+        // If the element is already a child of this parent, then it was claimed by another widget
+        // in this same build.
+        if old_parent_id == Some(parent_id) || self.reparented_global_keys.contains(&key) {
+            return Err(EngineError::DuplicateGlobalKey { key });
         }
-    }
 
-    #[tracing::instrument(level = "trace", skip(self))]
-    fn create_render_object(&mut self, element_id: ElementId) -> Option<RenderObjectId> {
-        // No point in creating a render object for an element that is being removed.
-        if self.removal_queue.contains(&element_id) {
-            return None;
+        if element_id == parent_id
+            || self
+                .element_tree
+                .iter_parents(parent_id)
+                .any(|ancestor_id| ancestor_id == element_id)
+        {
+            return Err(EngineError::GlobalKeyInOwnSubtree { key });
         }
 
-        let parent_render_object_id =
-            self.element_tree
-                .get_parent(element_id)
-                .map(|parent_element_id| {
-                    self.element_tree
-                        .get(parent_element_id)
-                        .expect("parent element missing while creating render objects")
-                        .render_object_id()
-                        .expect("parent element has no render object")
-                });
+        let depth = self
+            .element_tree
+            .get_depth(parent_id)
+            .ok_or(EngineError::MissingElement {
+                element_id: parent_id,
+            })?
+            + 1;
 
         let element = self
             .element_tree
             .get_mut(element_id)
-            .expect("element missing while creating render objects");
+            .ok_or(EngineError::MissingElement { element_id })?;
+
+        match element.update(new_widget) {
+            ElementUpdate::Noop => {
+                tracing::trace!(
+                    ?parent_id,
+                    ?element_id,
+                    widget = ?new_widget,
+                    key = ?key,
+                    "globally keyed element was reparented"
+                );
+            }
 
-        // If we've already created a render object for this element, skip it.
-        if let Some(render_object_id) = element.render_object_id() {
-            return Some(render_object_id);
-        }
+            ElementUpdate::RebuildNecessary => {
+                tracing::trace!(
+                    ?parent_id,
+                    ?element_id,
+                    widget = ?new_widget,
+                    key = ?key,
+                    "globally keyed element was reparented but must be rebuilt"
+                );
 
-        let render_object_id = self
-            .render_object_tree
-            .add(parent_render_object_id, element.create_render_object());
+                self.rebuild_queue.push(depth, element_id);
+
+                // If the child has a render object, we need to update it.
+                if element.render_object_id().is_some() {
+                    self.update_render_object.insert(element_id);
+                }
+            }
+
+            ElementUpdate::Invalid => return Ok(None),
+        }
+
+        let render_object_id = element.render_object_id();
+
+        self.reparented_global_keys.insert(key);
+
+        let was_inactive = self.is_inactive(element_id);
+
+        if let Some(old_parent_id) = old_parent_id {
+            // If the old parent still expects the element to be its child, it needs to be rebuilt
+            // so that it can reconcile the loss. Should it still build the same key, it'll be
+            // caught as a duplicate.
+            if !was_inactive {
+                self.dirty.insert(old_parent_id);
+            }
+        }
+
+        // Detach the render object from its old parent. It will be attached to its new parent's
+        // render object when render objects are synced.
+        if let Some(render_object_id) = render_object_id {
+            if let Some(parent_render_object_id) =
+                self.render_object_tree.get_parent(render_object_id)
+            {
+                self.mark_needs_layout(parent_render_object_id);
+            }
+
+            self.render_object_tree.reparent(None, render_object_id);
+        }
+
+        self.element_tree.reparent(Some(parent_id), element_id);
+
+        if was_inactive {
+            self.activate_element(element_id)?;
+        }
+
+        self.element_tree.with(element_id, |element_tree, element| {
+            self.plugins
+                .on_element_remount(&mut PluginElementRemountContext {
+                    element_tree,
+                    dirty: &mut self.dirty,
+
+                    parent_element_id: Some(&parent_id),
+                    element_id: &element_id,
+                    element,
+                });
+        });
+
+        self.bus.emit(&ElementReparentEvent {
+            parent_id: Some(parent_id),
+            element_id,
+        });
+
+        Ok(Some(element_id))
+    }
+
+    /// Returns `true` if the element, or any of its ancestors, has been deactivated.
+    fn is_inactive(&self, element_id: ElementId) -> bool {
+        if self.inactive_elements.is_empty() {
+            return false;
+        }
+
+        self.inactive_elements.contains(&element_id)
+            || self
+                .element_tree
+                .iter_parents(element_id)
+                .any(|ancestor_id| self.inactive_elements.contains(&ancestor_id))
+    }
+
+    /// Removes an element from its parent, keeping it (and its subtree) alive until the end of the
+    /// update so that it may be reclaimed elsewhere in the tree.
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn deactivate_element(&mut self, element_id: ElementId) -> Result<(), EngineError> {
+        let subtree = self
+            .element_tree
+            .iter_down_from(element_id)
+            .collect::<Vec<_>>();
+
+        for subtree_element_id in subtree {
+            self.element_tree
+                .with(subtree_element_id, |element_tree, element| {
+                    self.plugins
+                        .on_element_deactivate(&mut PluginElementDeactivateContext {
+                            element_tree,
+                            dirty: &mut self.dirty,
+
+                            element_id: &subtree_element_id,
+                            element,
+                        });
+
+                    element.deactivate();
+                })
+                .ok_or(EngineError::MissingElement {
+                    element_id: subtree_element_id,
+                })?;
+
+            // It may be reclaimed before the end of the update, at which point it will receive
+            // callbacks again.
+            self.callback_queue.remove_element(subtree_element_id);
+        }
+
+        // Detach the render object so that it no longer takes part in layout.
+        if let Some(render_object_id) = self
+            .element_tree
+            .get(element_id)
+            .and_then(Element::render_object_id)
+        {
+            if let Some(parent_render_object_id) =
+                self.render_object_tree.get_parent(render_object_id)
+            {
+                self.mark_needs_layout(parent_render_object_id);
+            }
+
+            self.render_object_tree.reparent(None, render_object_id);
+        }
+
+        self.element_tree.reparent(None, element_id);
+
+        self.inactive_elements.insert(element_id);
+
+        tracing::trace!(?element_id, "deactivated element");
+
+        Ok(())
+    }
+
+    /// Reactivates a previously deactivated element (and its subtree) after it has been inserted
+    /// back into the tree.
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn activate_element(&mut self, element_id: ElementId) -> Result<(), EngineError> {
+        self.inactive_elements.remove(&element_id);
+
+        let subtree = self
+            .element_tree
+            .iter_down_from(element_id)
+            .collect::<Vec<_>>();
+
+        for subtree_element_id in subtree {
+            let element = self.element_tree.get_mut(subtree_element_id).ok_or(
+                EngineError::MissingElement {
+                    element_id: subtree_element_id,
+                },
+            )?;
+
+            element.activate();
+
+            self.callback_queue.insert_element(subtree_element_id);
+
+            if self.inactive_dirty.remove(&subtree_element_id) {
+                self.dirty.insert(subtree_element_id);
+            }
+
+            // Render objects are not created for inactive elements, so they may be missing.
+            if element.render_object_id().is_none() {
+                self.create_render_object.push_back(subtree_element_id);
+            }
+        }
+
+        tracing::trace!(?element_id, "activated element");
+
+        Ok(())
+    }
+
+    /// Destroys every element that was deactivated and not reclaimed, along with their subtrees.
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn flush_removals(&mut self) -> Result<(), EngineError> {
+        let mut destroy_queue = self.inactive_elements.drain().collect::<VecDeque<_>>();
+
+        while let Some(element_id) = destroy_queue.pop_front() {
+            // Queue the element's children for removal
+            if let Some(children) = self.element_tree.get_children(element_id) {
+                for child_id in children {
+                    destroy_queue.push_back(*child_id);
+                }
+            }
+
+            self.element_tree
+                .with(element_id, |element_tree, element| {
+                    self.plugins
+                        .on_element_unmount(&mut PluginElementUnmountContext {
+                            element_tree,
+                            dirty: &mut self.dirty,
+
+                            element_id: &element_id,
+                            element,
+                        });
+
+                    element.unmount(ElementUnmountContext {
+                        plugins: &mut self.plugins,
+
+                        element_tree,
+                        dirty: &mut self.dirty,
+
+                        element_id: &element_id,
+                    });
+                })
+                .ok_or(EngineError::MissingElement { element_id })?;
+
+            self.bus.emit(&ElementDestroyedEvent { element_id });
+
+            let element = self
+                .element_tree
+                .remove(element_id, false)
+                .ok_or(EngineError::MissingElement { element_id })?;
+
+            self.callback_queue.remove_element(element_id);
+            self.task_executor.remove_element(element_id);
+
+            self.inactive_dirty.remove(&element_id);
+
+            if let Some(render_object_id) = element.render_object_id() {
+                self.render_object_tree.remove(render_object_id, false);
+            }
+
+            let widget = element.widget();
+
+            if let Some(key @ Key::Global(_)) = widget.key() {
+                // The key may have already been taken over by a newly spawned element.
+                if self.global_keys.get(&key) == Some(&element_id) {
+                    self.global_keys.remove(&key);
+                }
+            }
+
+            tracing::trace!(?element_id, ?widget, "destroyed widget");
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "trace", skip(self))]
+    fn create_render_object(
+        &mut self,
+        element_id: ElementId,
+    ) -> Result<Option<RenderObjectId>, EngineError> {
+        // No point in creating a render object for an element that may be removed.
+        if self.inactive_elements.contains(&element_id) {
+            return Ok(None);
+        }
+
+        let parent_render_object_id = match self.element_tree.get_parent(element_id) {
+            Some(parent_element_id) => {
+                let parent_render_object_id = self
+                    .element_tree
+                    .get(parent_element_id)
+                    .ok_or(EngineError::MissingElement {
+                        element_id: parent_element_id,
+                    })?
+                    .render_object_id();
+
+                // Parents are always given a render object before their children, unless they're
+                // within an inactive subtree.
+                let Some(parent_render_object_id) = parent_render_object_id else {
+                    return Ok(None);
+                };
+
+                Some(parent_render_object_id)
+            }
+
+            None => None,
+        };
+
+        let element = self
+            .element_tree
+            .get_mut(element_id)
+            .ok_or(EngineError::MissingElement { element_id })?;
+
+        // If we've already created a render object for this element, skip it.
+        if let Some(render_object_id) = element.render_object_id() {
+            return Ok(Some(render_object_id));
+        }
+
+        let mut render_object = element.create_render_object();
+
+        render_object.set_element_id(element_id);
+
+        let render_object_id = self
+            .render_object_tree
+            .add(parent_render_object_id, render_object);
 
         element.set_render_object_id(render_object_id);
 
-        Some(render_object_id)
+        self.mark_needs_layout(render_object_id);
+
+        Ok(Some(render_object_id))
     }
 
     #[tracing::instrument(level = "trace", skip(self))]
-    fn sync_render_objects(&mut self) {
+    fn sync_render_objects(&mut self) -> Result<(), EngineError> {
+        // Create render objects first, so that every element whose children are synced below has
+        // a render object to attach them to. This includes newly spawned elements that have been
+        // given a globally keyed child from elsewhere in the tree.
+        while let Some(element_id) = self.create_render_object.pop_front() {
+            self.create_render_object(element_id)?;
+        }
+
         let mut sync_render_object_queue = self
             .sync_render_object_children
             .drain()
-            .filter(|element_id| !self.removal_queue.contains(element_id))
+            .filter(|element_id| !self.inactive_elements.contains(element_id))
             .collect::<VecDeque<_>>();
 
         while let Some(element_id) = sync_render_object_queue.pop_front() {
-            // Elements that were removed should still be available in the tree, so this should
-            // never fail.
+            // Elements are only destroyed once the tree has settled, so this should never fail.
             let element_node = self
                 .element_tree
                 .get_node(element_id)
-                .expect("element missing while syncing render object children");
+                .ok_or(EngineError::MissingElement { element_id })?;
 
             if let Some(render_object_id) = element_node.value().render_object_id() {
                 let mut first_child_render_object_id = None;
@@ -710,7 +1442,9 @@ impl Engine {
                     let child_render_object_id = self
                         .element_tree
                         .get(child_id)
-                        .expect("child element missing while syncing render object children")
+                        .ok_or(EngineError::MissingElement {
+                            element_id: child_id,
+                        })?
                         .render_object_id();
 
                     let child_render_object_id =
@@ -721,10 +1455,10 @@ impl Engine {
                             child_render_object_id
                         } else {
                             // If they don't already have a render object, create it.
-                            if let Some(render_object_id) = self.create_render_object(child_id) {
+                            if let Some(render_object_id) = self.create_render_object(child_id)? {
                                 render_object_id
                             } else {
-                                // If the child is being removed, it won't have a render object.
+                                // If the child is inactive, it won't have a render object.
                                 continue;
                             }
                         };
@@ -737,7 +1471,7 @@ impl Engine {
                 let children = self
                     .render_object_tree
                     .get_children(render_object_id)
-                    .expect("element has a render object but the render object is missing")
+                    .ok_or(EngineError::MissingRenderObject { render_object_id })?
                     .clone();
 
                 // Remove any render objects that were previously children but are no longer.
@@ -751,59 +1485,100 @@ impl Engine {
 
                     self.render_object_tree.remove(child_id, false);
                 }
-            }
-        }
-
-        while let Some(element_id) = self.create_render_object.pop_front() {
-            self.create_render_object(element_id);
-        }
 
-        // Remove any render objects owned by elements that are being removed.
-        for element_id in self.removal_queue.iter().copied() {
-            if let Some(render_object_id) = self
-                .element_tree
-                .get(element_id)
-                .expect("element missing while syncing render object children")
-                .render_object_id()
-            {
-                self.render_object_tree.remove(render_object_id, false);
+                self.mark_needs_layout(render_object_id);
             }
         }
 
-        for element_id in self.update_render_object.drain() {
+        let update_render_object = self.update_render_object.drain().collect::<Vec<_>>();
+
+        for element_id in update_render_object {
             let element = self
                 .element_tree
                 .get(element_id)
-                .expect("element missing while updating render objects");
+                .ok_or(EngineError::MissingElement { element_id })?;
 
-            let render_object_id = element
-                .render_object_id()
-                .expect("element has no render object to update");
+            let render_object_id =
+                element
+                    .render_object_id()
+                    .ok_or(EngineError::ElementMissingRenderObject {
+                        element_id,
+                        widget_name: element.widget_name(),
+                    })?;
 
             let render_object = self
                 .render_object_tree
                 .get_mut(render_object_id)
-                .expect("render object missing while updating");
+                .ok_or(EngineError::MissingRenderObject { render_object_id })?;
 
-            element.update_render_object(render_object);
+            if element.update_render_object(render_object) {
+                self.mark_needs_layout(render_object_id);
+            }
         }
+
+        Ok(())
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::{cell::RefCell, rc::Rc};
+    use std::{
+        any::TypeId,
+        cell::RefCell,
+        rc::Rc,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+    };
 
-    use rustc_hash::FxHashSet;
+    use futures::channel::oneshot;
+    use rustc_hash::{FxHashMap, FxHashSet};
 
     use crate::{
-        element::mock::{render::MockRenderWidget, DummyRenderObject, DummyWidget},
+        callback::WidgetCallback,
+        element::mock::{
+            build::MockBuildWidget,
+            proxy::MockProxyWidget,
+            render::{MockRenderObject, MockRenderWidget},
+            DummyRenderObject, DummyWidget,
+        },
+        element::{ContextElement, ContextMarkDirty, ElementId, ElementPanic, ElementUpdate},
         engine::event::{ElementDestroyedEvent, ElementRebuiltEvent, ElementSpawnedEvent},
-        plugin::{context::ContextPlugins, Plugin},
-        widget::IntoWidget,
+        plugin::{
+            context::{
+                ContextPlugins, PluginElementDeactivateContext, PluginElementUnmountContext,
+            },
+            Plugin,
+        },
+        render::{
+            RenderObject, RenderObjectImpl, RenderObjectIntrinsicSizeContext,
+            RenderObjectLayoutContext,
+        },
+        unit::{Constraints, HitTest, IntrinsicDimension, Key, Offset, Size},
+        widget::{IntoWidget, Widget},
     };
 
-    use super::Engine;
+    use super::{Engine, EngineError, ReconcileStrategy};
+
+    // Children are created lazily so that dropping the widget doesn't recurse through the
+    // entire chain.
+    fn nested_widget(depth: usize) -> Widget {
+        if depth == 0 {
+            return DummyWidget.into_widget();
+        }
+
+        let widget = MockProxyWidget::new("NestedWidget");
+        {
+            widget
+                .mock
+                .borrow_mut()
+                .expect_child()
+                .returning_st(move || nested_widget(depth - 1));
+        }
+
+        widget.into_widget()
+    }
 
     #[test]
     pub fn adding_a_root_widget() {
@@ -819,7 +1594,7 @@ mod tests {
             }
         });
 
-        engine.update();
+        engine.update().unwrap();
 
         let root_id = engine.root();
 
@@ -853,7 +1628,7 @@ mod tests {
     pub fn rebuilding_widgets() {
         let mut engine = Engine::builder().with_root(DummyWidget).build();
 
-        engine.update();
+        engine.update().unwrap();
 
         let root_id = engine.root();
 
@@ -873,7 +1648,7 @@ mod tests {
 
         engine.mark_dirty(root_id);
 
-        engine.update();
+        engine.update().unwrap();
 
         assert!(*did_rebuild.borrow(), "should have emitted a rebuild event");
     }
@@ -907,7 +1682,7 @@ mod tests {
             }
         });
 
-        engine.update();
+        engine.update().unwrap();
 
         let root_id = engine.root();
 
@@ -937,6 +1712,22 @@ mod tests {
             "should have emitted a spawn event for the second child"
         );
 
+        for (element_id, node) in engine.elements().iter() {
+            let render_object_id = node
+                .value()
+                .render_object_id()
+                .expect("element has no render object");
+
+            assert_eq!(
+                engine
+                    .render_objects()
+                    .get(render_object_id)
+                    .and_then(RenderObject::element_id),
+                Some(element_id),
+                "render object should know which element owns it"
+            );
+        }
+
         println!("{:?}", engine.element_tree);
         println!("{:?}", engine.render_object_tree);
     }
@@ -974,7 +1765,7 @@ mod tests {
 
         let mut engine = Engine::builder().with_root(root_widget).build();
 
-        engine.update();
+        engine.update().unwrap();
 
         assert_eq!(
             engine.elements().len(),
@@ -1004,7 +1795,7 @@ mod tests {
 
         engine.mark_dirty(root_id);
 
-        engine.update();
+        engine.update().unwrap();
 
         assert_eq!(
             engine.elements().len(),
@@ -1050,7 +1841,7 @@ mod tests {
 
         let mut engine = Engine::builder().with_root(root_widget).build();
 
-        engine.update();
+        engine.update().unwrap();
 
         let root_id = engine.root();
 
@@ -1068,7 +1859,7 @@ mod tests {
 
         *child.borrow_mut() = DummyWidget.into_widget();
 
-        engine.update();
+        engine.update().unwrap();
 
         assert!(
             widgets_rebuilt.borrow().contains(&root_id),
@@ -1083,52 +1874,1737 @@ mod tests {
     }
 
     #[test]
-    pub fn reuses_unchanged_widgets() {
-        let root_widget = MockRenderWidget::new("RootWidget");
-        {
-            root_widget
-                .mock
-                .borrow_mut()
-                .expect_children()
-                .returning_st(|| vec![DummyWidget.into_widget()]);
-
-            root_widget
-                .mock
-                .borrow_mut()
-                .expect_create_render_object()
-                .returning(|| DummyRenderObject.into());
-        }
+    pub fn skips_rebuilding_children_already_rebuilt_by_parent() {
+        let root_children = Rc::new(RefCell::new(vec![empty_container_widget()]));
 
-        let mut engine = Engine::builder().with_root(root_widget).build();
+        let mut engine = Engine::builder()
+            .with_root(container_widget("RootWidget", None, &root_children))
+            .build();
 
-        engine.update();
+        engine.update().unwrap();
 
         let root_id = engine.root();
-        let element_id = engine
-            .elements()
-            .get_children(root_id)
-            .cloned()
-            .expect("no children");
+        let child_id = engine.elements().get_children(root_id).unwrap()[0];
 
-        engine.mark_dirty(engine.root());
+        let rebuild_counts = Rc::new(RefCell::new(FxHashMap::default()));
+
+        let _handler = engine.events().add_listener::<ElementRebuiltEvent>({
+            let rebuild_counts = Rc::clone(&rebuild_counts);
+
+            move |event| {
+                *rebuild_counts
+                    .borrow_mut()
+                    .entry(event.element_id)
+                    .or_insert(0) += 1;
+            }
+        });
+
+        // Mark the child first, so it would be rebuilt before its parent if dirty elements were
+        // processed in the order they were marked.
+        engine.mark_dirty(child_id);
+        engine.mark_dirty(root_id);
 
-        engine.update();
+        *root_children.borrow_mut() = vec![empty_container_widget()];
+
+        engine.update().unwrap();
 
         assert_eq!(
-            root_id,
-            engine.root(),
-            "root widget should have remained unchanged"
+            rebuild_counts.borrow().get(&root_id),
+            Some(&1),
+            "should have rebuilt the root widget once"
         );
 
         assert_eq!(
-            element_id,
-            engine
-                .elements()
-                .get_children(root_id)
-                .cloned()
-                .expect("no children"),
-            "root widget should not have regenerated its child"
+            rebuild_counts.borrow().get(&child_id),
+            Some(&1),
+            "should have rebuilt the child widget once"
+        );
+
+        assert_eq!(
+            engine.rebuilds_saved(),
+            1,
+            "should have skipped one rebuild"
+        );
+    }
+
+    #[test]
+    pub fn drops_callbacks_to_removed_elements() {
+        let child_widget = MockBuildWidget::new("ChildWidget");
+        {
+            let mut widget_mock = child_widget.mock.borrow_mut();
+
+            widget_mock
+                .expect_build()
+                .returning(|_| DummyWidget.into_widget());
+
+            widget_mock.expect_call().never();
+        }
+
+        let root_children = Rc::new(RefCell::new(vec![child_widget.into_widget()]));
+
+        let mut engine = Engine::builder()
+            .with_root(container_widget("RootWidget", None, &root_children))
+            .build();
+
+        engine.update().unwrap();
+
+        let root_id = engine.root();
+        let child_id = engine.elements().get_children(root_id).unwrap()[0];
+
+        let callback = WidgetCallback::<()>::new_unchecked(
+            child_id,
+            TypeId::of::<()>(),
+            engine.callback_queue().clone(),
         );
+
+        assert!(callback.is_alive(), "callback should be alive");
+
+        root_children.borrow_mut().clear();
+
+        engine.mark_dirty(root_id);
+
+        engine.update().unwrap();
+
+        assert!(
+            !callback.is_alive(),
+            "callback should no longer be alive once its element is removed"
+        );
+
+        callback.call(());
+
+        assert!(
+            engine.update().is_ok(),
+            "should have dropped the callback instead of failing"
+        );
+    }
+
+    #[test]
+    pub fn polls_tasks_when_woken() {
+        let mut engine = Engine::builder()
+            .with_root(empty_container_widget())
+            .build();
+
+        engine.update().unwrap();
+
+        let (tx, rx) = oneshot::channel::<usize>();
+
+        let result = Rc::new(RefCell::new(None));
+
+        let task_id = engine.task_executor().spawn(engine.root(), {
+            let result = Rc::clone(&result);
+
+            async move {
+                *result.borrow_mut() = rx.await.ok();
+            }
+        });
+
+        engine.update().unwrap();
+
+        assert!(
+            engine.task_executor().is_running(task_id),
+            "task should be waiting on the channel"
+        );
+
+        tx.send(7).unwrap();
+
+        assert!(engine.has_changes(), "waking the task should be a change");
+
+        engine.update().unwrap();
+
+        assert_eq!(*result.borrow(), Some(7), "task should have completed");
+
+        assert!(
+            !engine.task_executor().is_running(task_id),
+            "task should be removed once complete"
+        );
+    }
+
+    #[test]
+    pub fn cancels_tasks_of_removed_elements() {
+        let root_children = Rc::new(RefCell::new(vec![empty_container_widget()]));
+
+        let mut engine = Engine::builder()
+            .with_root(container_widget("RootWidget", None, &root_children))
+            .build();
+
+        engine.update().unwrap();
+
+        let root_id = engine.root();
+        let child_id = engine.elements().get_children(root_id).unwrap()[0];
+
+        let (tx, rx) = oneshot::channel::<()>();
+
+        let task_id = engine.task_executor().spawn(child_id, async move {
+            let _ = rx.await;
+        });
+
+        engine.update().unwrap();
+
+        root_children.borrow_mut().clear();
+
+        engine.mark_dirty(root_id);
+
+        engine.update().unwrap();
+
+        assert!(
+            !engine.task_executor().is_running(task_id),
+            "task should be cancelled once its element is removed"
+        );
+
+        assert!(tx.is_canceled(), "task's future should have been dropped");
+    }
+
+    #[test]
+    pub fn stops_updating_at_iteration_limit() {
+        let looping_widget = MockBuildWidget::new("LoopingWidget");
+        {
+            looping_widget
+                .mock
+                .borrow_mut()
+                .expect_build()
+                .returning(|mut ctx| {
+                    let element_id = ctx.element_id();
+
+                    ctx.mark_dirty(element_id);
+
+                    DummyWidget.into_widget()
+                });
+        }
+
+        let root_children = Rc::new(RefCell::new(vec![
+            looping_widget.into_widget(),
+            DummyWidget.into_widget(),
+        ]));
+
+        let mut engine = Engine::builder()
+            .with_root(container_widget("RootWidget", None, &root_children))
+            .with_iteration_limit(10)
+            .build();
+
+        let err = engine.update().expect_err("update should not have settled");
+
+        let looping_id = engine.elements().get_children(engine.root()).unwrap()[0];
+
+        assert_eq!(
+            err,
+            EngineError::IterationLimitReached {
+                limit: 10,
+                dirty: vec![(looping_id, "LoopingWidget")],
+                callbacks: Vec::new(),
+            },
+            "should have named the element that kept marking itself dirty"
+        );
+    }
+
+    // Calls `on_layout` each time its render object is laid out.
+    fn on_layout_widget(on_layout: &Arc<parking_lot::Mutex<Option<WidgetCallback<()>>>>) -> Widget {
+        let widget = MockRenderWidget::new("ChildWidget");
+        {
+            let mut widget_mock = widget.mock.borrow_mut();
+
+            widget_mock.expect_children().returning(Vec::new);
+
+            widget_mock
+                .expect_update()
+                .returning(|_| ElementUpdate::RebuildNecessary);
+
+            widget_mock.expect_update_render_object().returning(|_| {});
+
+            widget_mock.expect_create_render_object().returning({
+                let on_layout = Arc::clone(on_layout);
+
+                move || {
+                    let render_object = MockRenderObject::new("ChildRenderObject");
+                    {
+                        let on_layout = Arc::clone(&on_layout);
+
+                        let mut render_object_mock = render_object.mock.lock();
+
+                        render_object_mock
+                            .expect_sized_by_parent()
+                            .returning(|| false);
+
+                        render_object_mock
+                            .expect_layout()
+                            .returning(move |_, constraints| {
+                                if let Some(on_layout) = on_layout.lock().as_ref() {
+                                    on_layout.call(());
+                                }
+
+                                constraints.smallest()
+                            });
+                    }
+                    render_object.into()
+                }
+            });
+        }
+
+        widget.into_widget()
+    }
+
+    #[test]
+    pub fn stops_updating_when_layout_keeps_causing_rebuilds() {
+        let on_layout = Arc::new(parking_lot::Mutex::new(None::<WidgetCallback<()>>));
+
+        // Rebuilds whenever its child is laid out, which lays the child out again.
+        let relayout_widget = MockBuildWidget::new("RelayoutWidget");
+        {
+            let mut widget_mock = relayout_widget.mock.borrow_mut();
+
+            widget_mock.expect_build().returning_st({
+                let on_layout = Arc::clone(&on_layout);
+
+                move |_| on_layout_widget(&on_layout)
+            });
+
+            widget_mock.expect_call().returning(|_, _, _| true);
+        }
+
+        let mut engine = Engine::builder()
+            .with_root(relayout_widget)
+            .with_iteration_limit(10)
+            .build();
+
+        let root_id = engine.root();
+
+        *on_layout.lock() = Some(WidgetCallback::new_unchecked(
+            root_id,
+            TypeId::of::<()>(),
+            engine.callback_queue().clone(),
+        ));
+
+        let err = engine.update().expect_err("update should not have settled");
+
+        let EngineError::IterationLimitReached {
+            limit,
+            dirty,
+            callbacks,
+        } = err
+        else {
+            panic!("expected the iteration limit to be reached, got {:?}", err);
+        };
+
+        assert_eq!(limit, 10);
+
+        assert!(
+            dirty
+                .iter()
+                .chain(callbacks.iter())
+                .any(|element| *element == (root_id, "RelayoutWidget")),
+            "should have named the element that kept rebuilding from layout"
+        );
+    }
+
+    /// Creates a widget that builds `child` until one of its descendants panics, after which it
+    /// stores the panic in `caught` and builds a `DummyWidget` in its place.
+    fn error_boundary_widget(child: Widget, caught: &Rc<RefCell<Option<ElementPanic>>>) -> Widget {
+        let widget = MockBuildWidget::new("BoundaryWidget");
+        {
+            let mut widget_mock = widget.mock.borrow_mut();
+
+            widget_mock.expect_build().returning_st({
+                let caught = Rc::clone(caught);
+
+                move |_| {
+                    if caught.borrow().is_some() {
+                        DummyWidget.into_widget()
+                    } else {
+                        child.clone()
+                    }
+                }
+            });
+
+            widget_mock.expect_on_descendant_panic().returning_st({
+                let caught = Rc::clone(caught);
+
+                move |panic| caught.borrow_mut().replace(panic.clone()).is_none()
+            });
+        }
+
+        widget.into_widget()
+    }
+
+    #[test]
+    pub fn catches_build_panics_in_error_boundaries() {
+        let panicking_widget = MockBuildWidget::new("PanickingWidget");
+        {
+            let mut widget_mock = panicking_widget.mock.borrow_mut();
+
+            widget_mock
+                .expect_build()
+                .returning(|_| panic!("failed to build"));
+
+            widget_mock
+                .expect_update()
+                .returning(|_| ElementUpdate::Invalid);
+        }
+
+        let caught = Rc::default();
+
+        let mut engine = Engine::builder()
+            .with_root(error_boundary_widget(
+                panicking_widget.into_widget(),
+                &caught,
+            ))
+            .build();
+
+        engine.update().unwrap();
+
+        let caught = caught.borrow();
+        let caught = caught.as_ref().expect("should have caught the panic");
+
+        assert_eq!(caught.message, "failed to build");
+        assert_eq!(caught.path, vec!["BoundaryWidget", "PanickingWidget"]);
+
+        assert!(
+            !engine.contains(caught.element_id),
+            "should have removed the element that panicked"
+        );
+
+        let child_id = engine.elements().get_children(engine.root()).unwrap()[0];
+
+        assert_eq!(
+            engine.elements().get(child_id).unwrap().widget_name(),
+            "DummyWidget",
+            "should have built the fallback widget"
+        );
+    }
+
+    #[test]
+    pub fn catches_layout_panics_in_error_boundaries() {
+        let panicking_widget = MockRenderWidget::new("PanickingWidget");
+        {
+            let mut widget_mock = panicking_widget.mock.borrow_mut();
+
+            widget_mock.expect_children().returning(Vec::new);
+
+            widget_mock.expect_create_render_object().returning(|| {
+                let render_object = MockRenderObject::new("PanickingRenderObject");
+                {
+                    let mut render_object_mock = render_object.mock.lock();
+
+                    render_object_mock
+                        .expect_layout()
+                        .returning(|_, _| panic!("failed to lay out"));
+                }
+
+                render_object.into()
+            });
+
+            widget_mock
+                .expect_update()
+                .returning(|_| ElementUpdate::Invalid);
+        }
+
+        let caught = Rc::default();
+
+        let mut engine = Engine::builder()
+            .with_root(error_boundary_widget(
+                panicking_widget.into_widget(),
+                &caught,
+            ))
+            .build();
+
+        engine.update().unwrap();
+
+        let caught = caught.borrow();
+        let caught = caught.as_ref().expect("should have caught the panic");
+
+        assert_eq!(caught.message, "failed to lay out");
+        assert_eq!(caught.path, vec!["BoundaryWidget", "PanickingWidget"]);
+
+        assert!(
+            engine
+                .render_objects()
+                .iter()
+                .all(|(_, node)| !node.value().needs_layout()),
+            "every render object should have been laid out"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "failed to build")]
+    pub fn resumes_panics_without_an_error_boundary() {
+        let panicking_widget = MockBuildWidget::new("PanickingWidget");
+        {
+            panicking_widget
+                .mock
+                .borrow_mut()
+                .expect_build()
+                .returning(|_| panic!("failed to build"));
+        }
+
+        let mut engine = Engine::builder().with_root(panicking_widget).build();
+
+        let _ = engine.update();
+    }
+
+    #[test]
+    pub fn reuses_unchanged_widgets() {
+        let root_widget = MockRenderWidget::new("RootWidget");
+        {
+            root_widget
+                .mock
+                .borrow_mut()
+                .expect_children()
+                .returning_st(|| vec![DummyWidget.into_widget()]);
+
+            root_widget
+                .mock
+                .borrow_mut()
+                .expect_create_render_object()
+                .returning(|| DummyRenderObject.into());
+        }
+
+        let mut engine = Engine::builder().with_root(root_widget).build();
+
+        engine.update().unwrap();
+
+        let root_id = engine.root();
+        let element_id = engine
+            .elements()
+            .get_children(root_id)
+            .cloned()
+            .expect("no children");
+
+        engine.mark_dirty(engine.root());
+
+        engine.update().unwrap();
+
+        assert_eq!(
+            root_id,
+            engine.root(),
+            "root widget should have remained unchanged"
+        );
+
+        assert_eq!(
+            element_id,
+            engine
+                .elements()
+                .get_children(root_id)
+                .cloned()
+                .expect("no children"),
+            "root widget should not have regenerated its child"
+        );
+    }
+
+    #[test]
+    pub fn only_lays_out_dirty_relayout_boundaries() {
+        let root_layouts = Arc::new(AtomicUsize::new(0));
+        let boundary_layouts = Arc::new(AtomicUsize::new(0));
+
+        let child = Rc::new(RefCell::new(DummyWidget.into_widget()));
+
+        let boundary_widget = MockRenderWidget::new("BoundaryWidget");
+        {
+            boundary_widget
+                .mock
+                .borrow_mut()
+                .expect_children()
+                .returning_st({
+                    let child = Rc::clone(&child);
+
+                    move || vec![child.borrow().clone()]
+                });
+
+            boundary_widget
+                .mock
+                .borrow_mut()
+                .expect_create_render_object()
+                .returning({
+                    let boundary_layouts = Arc::clone(&boundary_layouts);
+
+                    move || {
+                        let render_object = MockRenderObject::new("BoundaryRenderObject");
+                        {
+                            let mut render_object_mock = render_object.mock.lock();
+
+                            render_object_mock
+                                .expect_sized_by_parent()
+                                .returning(|| false);
+
+                            render_object_mock.expect_layout().returning({
+                                let boundary_layouts = Arc::clone(&boundary_layouts);
+
+                                move |mut ctx, constraints| {
+                                    boundary_layouts.fetch_add(1, Ordering::Relaxed);
+
+                                    let mut children = ctx.iter_children_mut();
+
+                                    while let Some(mut child) = children.next() {
+                                        child.compute_layout(constraints.loosen());
+                                    }
+
+                                    constraints.biggest()
+                                }
+                            });
+                        }
+                        render_object.into()
+                    }
+                });
+        }
+
+        let boundary_widget = boundary_widget.into_widget();
+
+        let root_widget = MockRenderWidget::new("RootWidget");
+        {
+            root_widget
+                .mock
+                .borrow_mut()
+                .expect_children()
+                .returning_st(move || vec![boundary_widget.clone()]);
+
+            root_widget
+                .mock
+                .borrow_mut()
+                .expect_create_render_object()
+                .returning({
+                    let root_layouts = Arc::clone(&root_layouts);
+
+                    move || {
+                        let render_object = MockRenderObject::new("RootRenderObject");
+                        {
+                            let mut render_object_mock = render_object.mock.lock();
+
+                            render_object_mock
+                                .expect_sized_by_parent()
+                                .returning(|| false);
+
+                            render_object_mock.expect_layout().returning({
+                                let root_layouts = Arc::clone(&root_layouts);
+
+                                move |mut ctx, constraints| {
+                                    root_layouts.fetch_add(1, Ordering::Relaxed);
+
+                                    let mut children = ctx.iter_children_mut();
+
+                                    while let Some(mut child) = children.next() {
+                                        // Tight constraints make the child a relayout boundary.
+                                        child.compute_layout(Constraints::tight(Size::new(
+                                            100.0, 100.0,
+                                        )));
+                                    }
+
+                                    constraints.smallest()
+                                }
+                            });
+                        }
+                        render_object.into()
+                    }
+                });
+        }
+
+        let mut engine = Engine::builder().with_root(root_widget).build();
+
+        engine.update().unwrap();
+
+        assert_eq!(root_layouts.load(Ordering::Relaxed), 1);
+        assert_eq!(boundary_layouts.load(Ordering::Relaxed), 1);
+
+        let boundary_id = engine
+            .elements()
+            .get_children(engine.root())
+            .and_then(|children| children.first().copied())
+            .expect("no children");
+
+        let boundary_render_object_id = engine
+            .elements()
+            .get(boundary_id)
+            .and_then(|element| element.render_object_id())
+            .expect("boundary has no render object");
+
+        assert!(
+            engine
+                .render_objects()
+                .get(boundary_render_object_id)
+                .unwrap()
+                .is_relayout_boundary(),
+            "tightly constrained render object should be a relayout boundary"
+        );
+
+        // Rebuilding without any changes should not cause any layout.
+        engine.mark_dirty(engine.root());
+
+        engine.update().unwrap();
+
+        assert_eq!(root_layouts.load(Ordering::Relaxed), 1);
+        assert_eq!(boundary_layouts.load(Ordering::Relaxed), 1);
+
+        // Changing the child of the boundary should only lay out the boundary's subtree.
+        *child.borrow_mut() = DummyWidget.into_widget();
+
+        engine.mark_dirty(boundary_id);
+
+        engine.update().unwrap();
+
+        assert_eq!(
+            root_layouts.load(Ordering::Relaxed),
+            1,
+            "root should not have been laid out again"
+        );
+
+        assert_eq!(
+            boundary_layouts.load(Ordering::Relaxed),
+            2,
+            "boundary should have been laid out again"
+        );
+    }
+
+    #[test]
+    pub fn relays_out_parents_that_read_the_intrinsic_size_of_a_boundary() {
+        let intrinsic_width = Arc::new(AtomicUsize::new(20));
+
+        let child_widget = MockRenderWidget::new("ChildWidget");
+        {
+            child_widget
+                .mock
+                .borrow_mut()
+                .expect_children()
+                .returning(Vec::default);
+
+            child_widget
+                .mock
+                .borrow_mut()
+                .expect_create_render_object()
+                .returning({
+                    let intrinsic_width = Arc::clone(&intrinsic_width);
+
+                    move || {
+                        let render_object = MockRenderObject::new("ChildRenderObject");
+                        {
+                            let mut render_object_mock = render_object.mock.lock();
+
+                            render_object_mock
+                                .expect_sized_by_parent()
+                                .returning(|| false);
+
+                            render_object_mock.expect_intrinsic_size().returning({
+                                let intrinsic_width = Arc::clone(&intrinsic_width);
+
+                                move |_, _, _| intrinsic_width.load(Ordering::Relaxed) as f32
+                            });
+
+                            render_object_mock
+                                .expect_layout()
+                                .returning(|_, constraints| constraints.biggest());
+                        }
+                        render_object.into()
+                    }
+                });
+        }
+
+        let child_widget = child_widget.into_widget();
+
+        let intrinsic_widget = MockRenderWidget::new("IntrinsicWidget");
+        {
+            intrinsic_widget
+                .mock
+                .borrow_mut()
+                .expect_children()
+                .returning_st(move || vec![child_widget.clone()]);
+
+            intrinsic_widget
+                .mock
+                .borrow_mut()
+                .expect_create_render_object()
+                .returning(|| {
+                    let render_object = MockRenderObject::new("IntrinsicRenderObject");
+                    {
+                        let mut render_object_mock = render_object.mock.lock();
+
+                        render_object_mock
+                            .expect_sized_by_parent()
+                            .returning(|| false);
+
+                        render_object_mock.expect_layout().returning(|mut ctx, _| {
+                            let mut size = Size::ZERO;
+
+                            let mut children = ctx.iter_children_mut();
+
+                            while let Some(mut child) = children.next() {
+                                let width = child
+                                    .compute_intrinsic_size(IntrinsicDimension::MaxWidth, 10.0);
+
+                                // Tight constraints make the child a relayout boundary.
+                                size = child
+                                    .compute_layout(Constraints::tight(Size::new(width, 10.0)));
+                            }
+
+                            size
+                        });
+                    }
+                    render_object.into()
+                });
+        }
+
+        let intrinsic_widget = intrinsic_widget.into_widget();
+
+        let root_widget = MockRenderWidget::new("RootWidget");
+        {
+            root_widget
+                .mock
+                .borrow_mut()
+                .expect_children()
+                .returning_st(move || vec![intrinsic_widget.clone()]);
+
+            root_widget
+                .mock
+                .borrow_mut()
+                .expect_create_render_object()
+                .returning(|| {
+                    let render_object = MockRenderObject::new("RootRenderObject");
+                    {
+                        let mut render_object_mock = render_object.mock.lock();
+
+                        render_object_mock
+                            .expect_sized_by_parent()
+                            .returning(|| false);
+
+                        render_object_mock
+                            .expect_layout()
+                            .returning(|mut ctx, constraints| {
+                                let mut children = ctx.iter_children_mut();
+
+                                while let Some(mut child) = children.next() {
+                                    child.compute_layout(Constraints::loose(Size::new(
+                                        100.0, 100.0,
+                                    )));
+                                }
+
+                                constraints.smallest()
+                            });
+                    }
+                    render_object.into()
+                });
+        }
+
+        let mut engine = Engine::builder().with_root(root_widget).build();
+
+        engine.update().unwrap();
+
+        let intrinsic_id = engine
+            .elements()
+            .get_children(engine.root())
+            .and_then(|children| children.first().copied())
+            .expect("no children");
+
+        let child_id = engine
+            .elements()
+            .get_children(intrinsic_id)
+            .and_then(|children| children.first().copied())
+            .expect("no children");
+
+        let size_of = |engine: &Engine, element_id| {
+            engine
+                .elements()
+                .get(element_id)
+                .and_then(|element| element.render_object_id())
+                .and_then(|render_object_id| engine.render_objects().get(render_object_id))
+                .and_then(|render_object| render_object.size())
+                .expect("render object was not laid out")
+        };
+
+        assert_eq!(size_of(&engine, intrinsic_id), Size::new(20.0, 10.0));
+
+        let child_render_object_id = engine
+            .elements()
+            .get(child_id)
+            .and_then(|element| element.render_object_id())
+            .expect("child has no render object");
+
+        let child_render_object = engine.render_objects().get(child_render_object_id).unwrap();
+
+        assert!(
+            child_render_object.is_relayout_boundary(),
+            "tightly constrained render object should be a relayout boundary"
+        );
+
+        assert!(
+            child_render_object.parent_uses_intrinsic_size(),
+            "child should know that its parent read its intrinsic size"
+        );
+
+        intrinsic_width.store(40, Ordering::Relaxed);
+
+        engine.mark_needs_layout(child_render_object_id);
+
+        engine.update().unwrap();
+
+        assert_eq!(
+            size_of(&engine, intrinsic_id),
+            Size::new(40.0, 10.0),
+            "parent should have been laid out with the new intrinsic size of the boundary"
+        );
+
+        assert_eq!(size_of(&engine, child_id), Size::new(40.0, 10.0));
+    }
+
+    #[test]
+    pub fn lays_out_deeply_nested_trees() {
+        const DEPTH: usize = 100_000;
+
+        let mut engine = Engine::builder().with_root(nested_widget(DEPTH)).build();
+
+        engine.update().unwrap();
+
+        assert_eq!(
+            engine.render_objects().len(),
+            DEPTH + 1,
+            "render objects should have been created for the entire chain"
+        );
+
+        assert!(
+            engine
+                .render_objects()
+                .iter()
+                .all(|(_, node)| !node.value().needs_layout()),
+            "every render object should have been laid out"
+        );
+    }
+
+    // Grows its only child by one unit on each side.
+    struct InsetRenderObject {
+        layouts: Arc<AtomicUsize>,
+    }
+
+    impl RenderObjectImpl for InsetRenderObject {
+        fn render_object_name(&self) -> &'static str {
+            "InsetRenderObject"
+        }
+
+        fn intrinsic_size(
+            &self,
+            _: RenderObjectIntrinsicSizeContext,
+            _: IntrinsicDimension,
+            _: f32,
+        ) -> f32 {
+            0.0
+        }
+
+        fn layout(&mut self, mut ctx: RenderObjectLayoutContext, _: Constraints) -> Size {
+            self.layouts.fetch_add(1, Ordering::Relaxed);
+
+            let mut children = ctx.iter_children_mut();
+
+            let mut size = Size::new(10.0, 10.0);
+
+            while let Some(mut child) = children.next() {
+                let child_size = child.compute_layout(Constraints::default());
+                child.set_offset(Offset::new(1.0, 1.0));
+
+                size = Size::new(child_size.width + 2.0, child_size.height + 2.0);
+            }
+
+            size
+        }
+    }
+
+    fn inset_widget(depth: usize, layouts: &Arc<AtomicUsize>) -> Widget {
+        let widget = MockRenderWidget::new("InsetWidget");
+        {
+            let mut widget_mock = widget.mock.borrow_mut();
+
+            widget_mock.expect_children().returning_st({
+                let layouts = Arc::clone(layouts);
+
+                move || {
+                    if depth == 0 {
+                        Vec::new()
+                    } else {
+                        vec![inset_widget(depth - 1, &layouts)]
+                    }
+                }
+            });
+
+            widget_mock.expect_create_render_object().returning({
+                let layouts = Arc::clone(layouts);
+
+                move || {
+                    RenderObject::new(InsetRenderObject {
+                        layouts: Arc::clone(&layouts),
+                    })
+                }
+            });
+        }
+
+        widget.into_widget()
+    }
+
+    #[test]
+    pub fn discards_layouts_computed_from_provisional_sizes() {
+        const DEPTH: usize = 300;
+
+        let layouts = Arc::new(AtomicUsize::new(0));
+
+        let mut engine = Engine::builder()
+            .with_root(inset_widget(DEPTH, &layouts))
+            .build();
+
+        engine.update().unwrap();
+
+        assert!(
+            layouts.load(Ordering::Relaxed) > DEPTH + 1,
+            "render objects beyond the recursion limit should have been laid out again"
+        );
+
+        // The root is forced to fill its unbounded constraints, so start from its child.
+        let mut render_object_id = engine
+            .render_objects()
+            .root()
+            .and_then(|root_id| engine.render_objects().get_children(root_id))
+            .and_then(|children| children.first().copied());
+
+        let mut expected_size = 10.0 + 2.0 * (DEPTH - 1) as f32;
+
+        while let Some(id) = render_object_id {
+            let render_object = engine.render_objects().get(id).unwrap();
+
+            assert_eq!(
+                render_object.size(),
+                Some(Size::new(expected_size, expected_size)),
+                "render object should have been sized from the real size of its child"
+            );
+
+            assert_eq!(
+                render_object.offset(),
+                Offset::new(1.0, 1.0),
+                "render object should have kept the offset given by its parent"
+            );
+
+            render_object_id = engine
+                .render_objects()
+                .get_children(id)
+                .and_then(|children| children.first().copied());
+
+            expected_size -= 2.0;
+        }
+
+        assert_eq!(expected_size, 8.0, "should have walked the entire chain");
+    }
+
+    fn container_widget(
+        name: &'static str,
+        key: Option<Key>,
+        children: &Rc<RefCell<Vec<Widget>>>,
+    ) -> Widget {
+        let widget = MockRenderWidget::new(name);
+        {
+            widget.mock.borrow_mut().expect_children().returning_st({
+                let children = Rc::clone(children);
+
+                move || children.borrow().clone()
+            });
+
+            widget
+                .mock
+                .borrow_mut()
+                .expect_create_render_object()
+                .returning(|| DummyRenderObject.into());
+
+            widget
+                .mock
+                .borrow_mut()
+                .expect_update()
+                .returning(|new_widget| {
+                    if new_widget.downcast::<MockRenderWidget>().is_some() {
+                        ElementUpdate::RebuildNecessary
+                    } else {
+                        ElementUpdate::Invalid
+                    }
+                });
+
+            widget
+                .mock
+                .borrow_mut()
+                .expect_update_render_object()
+                .returning(|_| {});
+        }
+
+        Widget::new_with_key(key, widget)
+    }
+
+    #[test]
+    pub fn moves_globally_keyed_elements_across_parents() {
+        let keyed_widget = Widget::new_with_key(Some(Key::global("panel")), DummyWidget);
+
+        let first_children = Rc::new(RefCell::new(vec![keyed_widget.clone()]));
+        let second_children = Rc::new(RefCell::new(vec![DummyWidget.into_widget()]));
+
+        let root_children = Rc::new(RefCell::new(vec![
+            container_widget("FirstWidget", None, &first_children),
+            container_widget("SecondWidget", None, &second_children),
+        ]));
+
+        let mut engine = Engine::builder()
+            .with_root(container_widget("RootWidget", None, &root_children))
+            .build();
+
+        engine.update().unwrap();
+
+        let root_id = engine.root();
+
+        let (first_id, second_id) = {
+            let children = engine.elements().get_children(root_id).unwrap();
+
+            (children[0], children[1])
+        };
+
+        let keyed_id = engine.elements().get_children(first_id).unwrap()[0];
+
+        let keyed_render_object_id = engine
+            .elements()
+            .get(keyed_id)
+            .and_then(|element| element.render_object_id())
+            .expect("keyed element has no render object");
+
+        let widgets_destroyed = Rc::new(RefCell::new(FxHashSet::default()));
+
+        let _handler = engine.events().add_listener::<ElementDestroyedEvent>({
+            let widgets_destroyed = Rc::clone(&widgets_destroyed);
+
+            move |event| {
+                widgets_destroyed.borrow_mut().insert(event.element_id);
+            }
+        });
+
+        first_children.borrow_mut().clear();
+        second_children.borrow_mut().push(keyed_widget);
+
+        engine.mark_dirty(first_id);
+        engine.mark_dirty(second_id);
+
+        engine.update().unwrap();
+
+        assert!(
+            !widgets_destroyed.borrow().contains(&keyed_id),
+            "keyed element should not have been destroyed"
+        );
+
+        assert_eq!(
+            engine.elements().get_parent(keyed_id),
+            Some(second_id),
+            "keyed element should have been moved to the second widget"
+        );
+
+        assert_eq!(
+            engine.elements().get_children(second_id).unwrap().last(),
+            Some(&keyed_id),
+            "keyed element should be the last child of the second widget"
+        );
+
+        assert_eq!(
+            engine
+                .elements()
+                .get(keyed_id)
+                .and_then(|element| element.render_object_id()),
+            Some(keyed_render_object_id),
+            "keyed element should have retained its render object"
+        );
+
+        assert_eq!(
+            engine.render_objects().get_parent(keyed_render_object_id),
+            engine
+                .elements()
+                .get(second_id)
+                .and_then(|element| element.render_object_id()),
+            "keyed render object should have been moved to the second widget's render object"
+        );
+
+        assert_eq!(engine.elements().len(), engine.render_objects().len());
+    }
+
+    #[test]
+    pub fn errors_on_duplicate_global_keys() {
+        let root_children = Rc::new(RefCell::new(vec![
+            Widget::new_with_key(Some(Key::global("panel")), DummyWidget),
+            Widget::new_with_key(Some(Key::global("panel")), DummyWidget),
+        ]));
+
+        let mut engine = Engine::builder()
+            .with_root(container_widget("RootWidget", None, &root_children))
+            .build();
+
+        assert_eq!(
+            engine.update(),
+            Err(EngineError::DuplicateGlobalKey {
+                key: Key::global("panel")
+            })
+        );
+    }
+
+    #[test]
+    pub fn reclaims_deactivated_globally_keyed_elements() {
+        let keyed_widget = Widget::new_with_key(Some(Key::global("panel")), DummyWidget);
+
+        let first_children = Rc::new(RefCell::new(vec![keyed_widget.clone()]));
+        let second_children = Rc::new(RefCell::new(vec![keyed_widget]));
+
+        let first_widget =
+            container_widget("FirstWidget", Some(Key::local("first")), &first_children);
+
+        let second_widget =
+            container_widget("SecondWidget", Some(Key::local("second")), &second_children);
+
+        let root_children = Rc::new(RefCell::new(vec![first_widget]));
+
+        let mut engine = Engine::builder()
+            .with_root(container_widget("RootWidget", None, &root_children))
+            .build();
+
+        engine.update().unwrap();
+
+        let root_id = engine.root();
+
+        let first_id = engine.elements().get_children(root_id).unwrap()[0];
+        let keyed_id = engine.elements().get_children(first_id).unwrap()[0];
+
+        let keyed_render_object_id = engine
+            .elements()
+            .get(keyed_id)
+            .and_then(|element| element.render_object_id())
+            .expect("keyed element has no render object");
+
+        // Replacing the first widget deactivates it, along with the keyed element, before the
+        // second widget is built and reclaims it.
+        *root_children.borrow_mut() = vec![second_widget];
+
+        engine.mark_dirty(root_id);
+
+        engine.update().unwrap();
+
+        assert!(
+            !engine.contains(first_id),
+            "first widget should have been destroyed"
+        );
+
+        let second_id = engine.elements().get_children(root_id).unwrap()[0];
+
+        assert_eq!(
+            engine.elements().get_parent(keyed_id),
+            Some(second_id),
+            "keyed element should have been reclaimed by the second widget"
+        );
+
+        assert_eq!(
+            engine.render_objects().get_parent(keyed_render_object_id),
+            engine
+                .elements()
+                .get(second_id)
+                .and_then(|element| element.render_object_id()),
+            "keyed render object should have been attached to the second widget's render object"
+        );
+
+        assert_eq!(engine.elements().len(), 3);
+        assert_eq!(engine.render_objects().len(), 3);
+    }
+
+    fn empty_container_widget() -> Widget {
+        container_widget("ContainerWidget", None, &Rc::default())
+    }
+
+    /// Builds `old_widgets` as the children of the root widget, then rebuilds the root with
+    /// `new_widgets` using type-based reconciliation. Returns the element IDs of the children
+    /// before and after the rebuild.
+    fn reconcile_by_type(
+        old_widgets: Vec<Widget>,
+        new_widgets: Vec<Widget>,
+    ) -> (Engine, Vec<ElementId>, Vec<ElementId>) {
+        let children = Rc::new(RefCell::new(old_widgets));
+
+        let mut engine = Engine::builder()
+            .with_reconcile_strategy(ReconcileStrategy::ByType)
+            .with_root(container_widget("RootWidget", None, &children))
+            .build();
+
+        engine.update().unwrap();
+
+        let root_id = engine.root();
+        let old_children = engine.elements().get_children(root_id).unwrap().clone();
+
+        *children.borrow_mut() = new_widgets;
+
+        engine.mark_dirty(root_id);
+
+        engine.update().unwrap();
+
+        let new_children = engine.elements().get_children(root_id).unwrap().clone();
+
+        // Render objects must be kept in the same order as their elements.
+        let root_render_object_id = engine.elements()[root_id].render_object_id().unwrap();
+
+        assert_eq!(
+            engine
+                .render_objects()
+                .get_children(root_render_object_id)
+                .unwrap(),
+            &new_children
+                .iter()
+                .map(|child_id| engine.elements()[*child_id].render_object_id().unwrap())
+                .collect::<Vec<_>>(),
+            "render objects should match the order of their elements"
+        );
+
+        (engine, old_children, new_children)
+    }
+
+    #[test]
+    pub fn reconciles_inserted_children_by_type() {
+        let (engine, old_children, new_children) = reconcile_by_type(
+            vec![DummyWidget.into_widget(), empty_container_widget()],
+            vec![
+                empty_container_widget(),
+                DummyWidget.into_widget(),
+                empty_container_widget(),
+                DummyWidget.into_widget(),
+            ],
+        );
+
+        assert_eq!(new_children.len(), 4);
+
+        assert_eq!(
+            new_children[0], old_children[1],
+            "the container widget should have been retained by the first container"
+        );
+
+        assert_eq!(
+            new_children[1], old_children[0],
+            "the dummy widget should have been retained by the first dummy"
+        );
+
+        assert_eq!(engine.elements().len(), 5);
+        assert_eq!(engine.render_objects().len(), 5);
+    }
+
+    #[test]
+    pub fn reconciles_removed_children_by_type() {
+        let (engine, old_children, new_children) = reconcile_by_type(
+            vec![
+                empty_container_widget(),
+                DummyWidget.into_widget(),
+                empty_container_widget(),
+                DummyWidget.into_widget(),
+            ],
+            vec![DummyWidget.into_widget(), empty_container_widget()],
+        );
+
+        assert_eq!(
+            new_children,
+            vec![old_children[1], old_children[0]],
+            "the first child of each type should have been retained"
+        );
+
+        assert!(!engine.contains(old_children[2]));
+        assert!(!engine.contains(old_children[3]));
+
+        assert_eq!(engine.elements().len(), 3);
+        assert_eq!(engine.render_objects().len(), 3);
+    }
+
+    #[test]
+    pub fn reconciles_reordered_children_by_type() {
+        let (engine, old_children, new_children) = reconcile_by_type(
+            vec![
+                DummyWidget.into_widget(),
+                empty_container_widget(),
+                empty_container_widget(),
+            ],
+            vec![
+                empty_container_widget(),
+                empty_container_widget(),
+                DummyWidget.into_widget(),
+            ],
+        );
+
+        assert_eq!(
+            new_children,
+            vec![old_children[1], old_children[2], old_children[0]],
+            "every child should have been retained"
+        );
+
+        assert_eq!(engine.elements().len(), 4);
+        assert_eq!(engine.render_objects().len(), 4);
+    }
+
+    #[test]
+    pub fn does_not_reconcile_unkeyed_children_by_default() {
+        let children = Rc::new(RefCell::new(vec![
+            DummyWidget.into_widget(),
+            empty_container_widget(),
+        ]));
+
+        let mut engine = Engine::builder()
+            .with_root(container_widget("RootWidget", None, &children))
+            .build();
+
+        engine.update().unwrap();
+
+        let root_id = engine.root();
+        let old_children = engine.elements().get_children(root_id).unwrap().clone();
+
+        *children.borrow_mut() = vec![empty_container_widget(), DummyWidget.into_widget()];
+
+        engine.mark_dirty(root_id);
+
+        engine.update().unwrap();
+
+        assert!(
+            old_children
+                .iter()
+                .all(|old_child_id| !engine.contains(*old_child_id)),
+            "unkeyed children should have been rebuilt from scratch"
+        );
+    }
+
+    #[derive(Default)]
+    struct LifecyclePlugin {
+        deactivated: FxHashSet<ElementId>,
+        unmounted: FxHashSet<ElementId>,
+    }
+
+    impl Plugin for LifecyclePlugin {
+        fn on_element_deactivate(&mut self, ctx: &mut PluginElementDeactivateContext) {
+            self.deactivated.insert(ctx.element_id());
+        }
+
+        fn on_element_unmount(&mut self, ctx: &mut PluginElementUnmountContext) {
+            self.unmounted.insert(ctx.element_id());
+        }
+    }
+
+    #[test]
+    pub fn does_not_rebuild_removed_children() {
+        let builds = Rc::new(RefCell::new(0));
+
+        let child_widget = MockBuildWidget::new("ChildWidget");
+        {
+            let mut widget_mock = child_widget.mock.borrow_mut();
+
+            widget_mock.expect_build().returning_st({
+                let builds = Rc::clone(&builds);
+
+                move |_| {
+                    *builds.borrow_mut() += 1;
+
+                    DummyWidget.into_widget()
+                }
+            });
+        }
+
+        let root_children = Rc::new(RefCell::new(vec![child_widget.into_widget()]));
+
+        let mut engine = Engine::builder()
+            .add_plugin(LifecyclePlugin::default())
+            .with_root(container_widget("RootWidget", None, &root_children))
+            .build();
+
+        engine.update().unwrap();
+
+        assert_eq!(*builds.borrow(), 1);
+
+        let root_id = engine.root();
+        let child_id = engine.elements().get_children(root_id).unwrap()[0];
+
+        // The parent is rebuilt first, removing the child before its own rebuild is processed.
+        root_children.borrow_mut().clear();
+
+        engine.mark_dirty(root_id);
+        engine.mark_dirty(child_id);
+
+        engine.update().unwrap();
+
+        assert!(
+            engine
+                .plugins()
+                .get::<LifecyclePlugin>()
+                .unwrap()
+                .deactivated
+                .contains(&child_id),
+            "child should have been deactivated"
+        );
+
+        assert_eq!(
+            *builds.borrow(),
+            1,
+            "removed child should not have been rebuilt"
+        );
+    }
+
+    #[test]
+    pub fn deactivates_removed_children() {
+        let children = Rc::new(RefCell::new(vec![
+            DummyWidget.into_widget(),
+            DummyWidget.into_widget(),
+        ]));
+
+        let mut engine = Engine::builder()
+            .add_plugin(LifecyclePlugin::default())
+            .with_root(container_widget("RootWidget", None, &children))
+            .build();
+
+        engine.update().unwrap();
+
+        let root_id = engine.root();
+        let child_ids = engine.elements().get_children(root_id).unwrap().clone();
+
+        children.borrow_mut().clear();
+
+        engine.mark_dirty(root_id);
+
+        engine.update().unwrap();
+
+        let plugin = engine.plugins().get::<LifecyclePlugin>().unwrap();
+
+        for child_id in child_ids {
+            assert!(
+                plugin.deactivated.contains(&child_id),
+                "removed child should have been deactivated"
+            );
+
+            assert!(
+                plugin.unmounted.contains(&child_id),
+                "removed child should have been unmounted"
+            );
+
+            assert!(
+                !engine.contains(child_id),
+                "removed child should have been destroyed"
+            );
+        }
+
+        assert_eq!(engine.render_objects().len(), 1);
+    }
+
+    // Lays its children out side by side and relies on the default hit testing.
+    struct RowRenderObject;
+
+    impl RenderObjectImpl for RowRenderObject {
+        fn render_object_name(&self) -> &'static str {
+            "RowRenderObject"
+        }
+
+        fn intrinsic_size(
+            &self,
+            _: RenderObjectIntrinsicSizeContext,
+            _: IntrinsicDimension,
+            _: f32,
+        ) -> f32 {
+            0.0
+        }
+
+        fn layout(&mut self, mut ctx: RenderObjectLayoutContext, _: Constraints) -> Size {
+            let mut children = ctx.iter_children_mut();
+
+            let mut x = 0.0;
+
+            while let Some(mut child) = children.next() {
+                child.compute_layout(Size::new(10.0, 10.0));
+                child.set_offset(Offset::new(x, 0.0));
+
+                x += 10.0;
+            }
+
+            Size::new(x, 10.0)
+        }
+    }
+
+    #[test]
+    pub fn hit_tests_each_child_once() {
+        let tested = Arc::new(parking_lot::Mutex::new(Vec::new()));
+
+        let children = (0..3)
+            .map(|idx| {
+                let tested = Arc::clone(&tested);
+
+                let child_widget = MockRenderWidget::new("ChildWidget");
+                {
+                    let mut child_widget_mock = child_widget.mock.borrow_mut();
+
+                    child_widget_mock.expect_children().returning(Vec::new);
+
+                    child_widget_mock
+                        .expect_create_render_object()
+                        .returning(move || {
+                            let tested = Arc::clone(&tested);
+
+                            let render_object = MockRenderObject::new("ChildRenderObject");
+                            {
+                                let mut render_object_mock = render_object.mock.lock();
+
+                                render_object_mock
+                                    .expect_layout()
+                                    .returning(|_, constraints| constraints.biggest());
+
+                                render_object_mock.expect_hit_test().returning(
+                                    move |ctx, position| {
+                                        tested.lock().push(idx);
+
+                                        if ctx.size().contains(position) {
+                                            HitTest::Absorb
+                                        } else {
+                                            HitTest::Pass
+                                        }
+                                    },
+                                );
+                            }
+                            render_object.into()
+                        });
+                }
+
+                child_widget.into_widget()
+            })
+            .collect::<Vec<_>>();
+
+        let root_widget = MockRenderWidget::new("RootWidget");
+        {
+            let mut root_widget_mock = root_widget.mock.borrow_mut();
+
+            root_widget_mock
+                .expect_children()
+                .returning_st(move || children.clone());
+
+            root_widget_mock
+                .expect_create_render_object()
+                .returning(|| RenderObject::new(RowRenderObject));
+        }
+
+        let mut engine = Engine::builder().with_root(root_widget).build();
+
+        engine.update().unwrap();
+
+        let result = engine.hit_test(Offset::new(15.0, 5.0));
+
+        assert_eq!(
+            *tested.lock(),
+            vec![2, 1],
+            "should have tested the children from last to first, stopping at the one that was hit"
+        );
+
+        assert_eq!(
+            result.path().len(),
+            2,
+            "should have hit the middle child and the root"
+        );
+    }
+
+    #[test]
+    pub fn hit_tests_render_objects() {
+        let child_widget = MockRenderWidget::new("ChildWidget");
+        {
+            child_widget
+                .mock
+                .borrow_mut()
+                .expect_children()
+                .returning(Vec::new);
+
+            child_widget
+                .mock
+                .borrow_mut()
+                .expect_create_render_object()
+                .returning(|| {
+                    let render_object = MockRenderObject::new("ChildRenderObject");
+                    {
+                        let mut render_object_mock = render_object.mock.lock();
+
+                        render_object_mock
+                            .expect_layout()
+                            .returning(|_, constraints| constraints.biggest());
+
+                        render_object_mock
+                            .expect_hit_test()
+                            .returning(|ctx, position| {
+                                if ctx.size().contains(position) {
+                                    HitTest::Absorb
+                                } else {
+                                    HitTest::Pass
+                                }
+                            });
+                    }
+                    render_object.into()
+                });
+        }
+
+        let child_widget = child_widget.into_widget();
+
+        let root_widget = MockRenderWidget::new("RootWidget");
+        {
+            root_widget
+                .mock
+                .borrow_mut()
+                .expect_children()
+                .returning_st(move || vec![child_widget.clone()]);
+
+            root_widget
+                .mock
+                .borrow_mut()
+                .expect_create_render_object()
+                .returning(|| {
+                    let render_object = MockRenderObject::new("RootRenderObject");
+                    {
+                        let mut render_object_mock = render_object.mock.lock();
+
+                        render_object_mock.expect_layout().returning(|mut ctx, _| {
+                            let mut children = ctx.iter_children_mut();
+
+                            while let Some(mut child) = children.next() {
+                                child.compute_layout(Size::new(50.0, 50.0));
+                                child.set_offset(Offset::new(10.0, 20.0));
+                            }
+
+                            Size::new(100.0, 100.0)
+                        });
+
+                        render_object_mock
+                            .expect_hit_test()
+                            .returning(|ctx, position| {
+                                let mut children = ctx.iter_children();
+
+                                while let Some(mut child) = children.next_back() {
+                                    let offset = child.offset();
+
+                                    if child.hit_test_with_offset(offset, position)
+                                        == HitTest::Absorb
+                                    {
+                                        return HitTest::Absorb;
+                                    }
+                                }
+
+                                HitTest::Pass
+                            });
+                    }
+                    render_object.into()
+                });
+        }
+
+        let mut engine = Engine::builder().with_root(root_widget).build();
+
+        engine.update().unwrap();
+
+        let child_render_object_id = engine
+            .elements()
+            .get_children(engine.root())
+            .and_then(|children| children.first().copied())
+            .and_then(|child_id| engine.elements().get(child_id))
+            .and_then(|element| element.render_object_id())
+            .expect("child has no render object");
+
+        assert!(
+            engine.hit_test(Offset::new(5.0, 5.0)).is_empty(),
+            "should not have hit anything outside of the child"
+        );
+
+        let result = engine.hit_test(Offset::new(30.0, 40.0));
+
+        let entry = result.path().first().expect("should have hit the child");
+
+        assert_eq!(entry.render_object_id, child_render_object_id);
+
+        assert_eq!(
+            entry.to_local(Offset::new(30.0, 40.0)),
+            Offset::new(20.0, 20.0),
+            "should have been offset by the child's position"
+        );
+
+        assert_eq!(result.path().len(), 2, "should have also hit the root");
     }
 
     #[derive(Debug)]
@@ -1149,7 +3625,7 @@ mod tests {
             .with_root(DummyWidget)
             .build();
 
-        engine.update();
+        engine.update().unwrap();
 
         assert!(
             engine.plugins().get::<TestPlugin1>().is_some(),
@@ -0,0 +1,16 @@
+/// Determines how an element's children from its previous build are matched against the widgets
+/// it has just built.
+///
+/// Children at the start and end of the list are always matched up in order, and keyed children
+/// are always matched by their key. The strategy only affects unkeyed children that fall between
+/// those two regions.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ReconcileStrategy {
+    /// Unkeyed children in the middle of the list are never reused, and are rebuilt from scratch.
+    #[default]
+    KeyedOnly,
+
+    /// Unkeyed children in the middle of the list reuse the first remaining old child of the
+    /// same widget type, in the order they appeared in the previous build.
+    ByType,
+}
@@ -7,6 +7,7 @@
 
 // #![warn(missing_docs)]
 
+pub mod animation;
 pub mod callback;
 pub mod element;
 pub mod engine;
@@ -14,6 +15,9 @@ pub mod listenable;
 pub mod plugin;
 pub mod query;
 pub mod render;
+pub mod scheduler;
+pub mod task;
+pub mod timer;
 pub mod unit;
 pub mod util;
 pub mod widget;
@@ -1,10 +1,13 @@
 use crate::{
+    callback::{CallbackQueue, ContextCallbackQueue},
     element::{ContextElements, Element, ElementId},
     util::tree::Tree,
 };
 
 pub struct PluginBeforeUpdateContext<'ctx> {
     pub element_tree: &'ctx Tree<ElementId, Element>,
+
+    pub callback_queue: &'ctx CallbackQueue,
 }
 
 impl ContextElements for PluginBeforeUpdateContext<'_> {
@@ -12,3 +15,9 @@ impl ContextElements for PluginBeforeUpdateContext<'_> {
         self.element_tree
     }
 }
+
+impl ContextCallbackQueue for PluginBeforeUpdateContext<'_> {
+    fn callback_queue(&self) -> &CallbackQueue {
+        self.callback_queue
+    }
+}
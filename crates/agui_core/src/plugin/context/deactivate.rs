@@ -0,0 +1,31 @@
+use crate::{
+    element::{ContextElement, ContextElements, ContextMarkDirty, Element, ElementId},
+    engine::DirtyElements,
+    util::tree::Tree,
+};
+
+pub struct PluginElementDeactivateContext<'ctx> {
+    pub element_tree: &'ctx Tree<ElementId, Element>,
+    pub dirty: &'ctx mut DirtyElements,
+
+    pub element_id: &'ctx ElementId,
+    pub element: &'ctx Element,
+}
+
+impl ContextElements for PluginElementDeactivateContext<'_> {
+    fn elements(&self) -> &Tree<ElementId, Element> {
+        self.element_tree
+    }
+}
+
+impl ContextElement for PluginElementDeactivateContext<'_> {
+    fn element_id(&self) -> ElementId {
+        *self.element_id
+    }
+}
+
+impl ContextMarkDirty for PluginElementDeactivateContext<'_> {
+    fn mark_dirty(&mut self, element_id: ElementId) {
+        self.dirty.insert(element_id);
+    }
+}
@@ -1,6 +1,7 @@
 mod after_update;
 mod before_update;
 mod build;
+mod deactivate;
 mod init;
 mod mount;
 mod remount;
@@ -9,6 +10,7 @@ mod unmount;
 pub use after_update::*;
 pub use before_update::*;
 pub use build::*;
+pub use deactivate::*;
 pub use init::*;
 pub use mount::*;
 pub use remount::*;
@@ -2,8 +2,8 @@ use std::any::TypeId;
 
 use super::context::{
     PluginAfterUpdateContext, PluginBeforeUpdateContext, PluginElementBuildContext,
-    PluginElementMountContext, PluginElementRemountContext, PluginElementUnmountContext,
-    PluginInitContext,
+    PluginElementDeactivateContext, PluginElementMountContext, PluginElementRemountContext,
+    PluginElementUnmountContext, PluginInitContext,
 };
 use crate::unit::AsAny;
 
@@ -23,6 +23,10 @@ macros::impl_trait! {
 
         fn on_element_remount(&mut self, ctx: &mut PluginElementRemountContext);
 
+        /// Called when an element is removed from the tree. It may be remounted elsewhere before
+        /// the end of the update, otherwise it will be unmounted.
+        fn on_element_deactivate(&mut self, ctx: &mut PluginElementDeactivateContext);
+
         fn on_element_unmount(&mut self, ctx: &mut PluginElementUnmountContext);
 
         fn on_element_build(&mut self, ctx: &mut PluginElementBuildContext);
@@ -82,7 +82,7 @@ mod tests {
 
         let mut engine = Engine::builder().with_root(proxy_widget).build();
 
-        engine.update();
+        engine.update().unwrap();
 
         assert_eq!(
             engine.query().by_type::<MockProxyWidget>().count(),
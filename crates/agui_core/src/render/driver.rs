@@ -0,0 +1,337 @@
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    panic::{self, AssertUnwindSafe},
+};
+
+use rustc_hash::FxHashMap;
+
+use crate::{
+    engine::EngineError,
+    plugin::Plugins,
+    render::{RenderObject, RenderObjectContext, RenderObjectContextMut, RenderObjectId},
+    unit::{Constraints, IntrinsicDimension, Size},
+    util::tree::Tree,
+};
+
+/// The number of nested render objects that may be laid out recursively before the driver
+/// defers the remainder of the subtree to its work stack.
+const MAX_RECURSION_DEPTH: usize = 128;
+
+#[derive(Debug, Clone, Copy)]
+enum LayoutFrame {
+    Layout {
+        render_object_id: RenderObjectId,
+        constraints: Constraints,
+    },
+
+    IntrinsicSize {
+        render_object_id: RenderObjectId,
+        dimension: IntrinsicDimension,
+        cross_extent: f32,
+    },
+}
+
+/// Drives layout and intrinsic sizing of the render object tree without relying on unbounded
+/// recursion.
+///
+/// Render objects lay out their children by calling into the driver through their layout context.
+/// Up to [`MAX_RECURSION_DEPTH`] levels are handled recursively; past that point the driver records
+/// the request, hands back a placeholder result, and unwinds. Any render object whose layout
+/// depended on a placeholder is left dirty. The deferred request is then pushed onto an explicit
+/// work stack and processed from the top, after which the render objects beneath it are replayed,
+/// this time finding the deferred result cached.
+#[derive(Default)]
+pub(crate) struct LayoutDriver {
+    depth: Cell<usize>,
+
+    /// The first request that could not be satisfied during the current pass.
+    deferred: Cell<Option<LayoutFrame>>,
+
+    /// The first error encountered during the current run, which aborts the run once the stack
+    /// has unwound back to the driver.
+    error: RefCell<Option<EngineError>>,
+
+    /// The first render object that panicked during the current run, along with its payload. The
+    /// run is aborted and the panic is left for the engine to deliver to an error boundary.
+    panicked: RefCell<Option<(RenderObjectId, Box<dyn Any + Send>)>>,
+
+    /// Layout results computed during the current run, used to guarantee forward progress if a
+    /// render object lays out the same child multiple times with differing constraints.
+    layouts: RefCell<FxHashMap<RenderObjectId, Vec<(Constraints, Size)>>>,
+
+    intrinsic_sizes: RefCell<FxHashMap<(RenderObjectId, IntrinsicDimension, u32), f32>>,
+}
+
+impl LayoutDriver {
+    /// Lays out the given render object and its subtree with the given constraints.
+    pub(crate) fn layout(
+        &self,
+        plugins: &mut Plugins,
+        render_object_tree: &mut Tree<RenderObjectId, RenderObject>,
+        render_object_id: RenderObjectId,
+        constraints: Constraints,
+    ) -> Result<(), EngineError> {
+        self.layouts.borrow_mut().clear();
+        self.intrinsic_sizes.borrow_mut().clear();
+        self.error.borrow_mut().take();
+
+        let mut stack = vec![LayoutFrame::Layout {
+            render_object_id,
+            constraints,
+        }];
+
+        while let Some(frame) = stack.last().copied() {
+            match frame {
+                LayoutFrame::Layout {
+                    render_object_id,
+                    constraints,
+                } => {
+                    self.compute_layout(plugins, render_object_tree, render_object_id, constraints);
+                }
+
+                LayoutFrame::IntrinsicSize {
+                    render_object_id,
+                    dimension,
+                    cross_extent,
+                } => {
+                    self.compute_intrinsic_size(
+                        plugins,
+                        render_object_tree,
+                        render_object_id,
+                        dimension,
+                        cross_extent,
+                    );
+                }
+            }
+
+            if let Some(err) = self.error.borrow_mut().take() {
+                self.deferred.take();
+
+                return Err(err);
+            }
+
+            if self.panicked.borrow().is_some() {
+                self.deferred.take();
+
+                return Ok(());
+            }
+
+            if let Some(deferred) = self.deferred.take() {
+                tracing::trace!(?deferred, depth = stack.len(), "deferring layout");
+
+                stack.push(deferred);
+            } else {
+                stack.pop();
+            }
+        }
+
+        // Any render object whose intrinsic size was read during the run must notify the
+        // ancestor that read it when its layout changes, even past a relayout boundary.
+        for (render_object_id, ..) in self.intrinsic_sizes.borrow().keys() {
+            if let Some(render_object) = render_object_tree.get_mut(*render_object_id) {
+                render_object.mark_parent_uses_intrinsic_size();
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if a request was deferred or failed during the current pass, meaning any
+    /// results computed since then may be based on placeholder values.
+    pub(crate) fn is_incomplete(&self) -> bool {
+        self.deferred.get().is_some()
+            || self.error.borrow().is_some()
+            || self.panicked.borrow().is_some()
+    }
+
+    /// Takes the panic that aborted the most recent run, if any.
+    pub(crate) fn take_panic(&self) -> Option<(RenderObjectId, Box<dyn Any + Send>)> {
+        self.panicked.borrow_mut().take()
+    }
+
+    pub(crate) fn compute_layout(
+        &self,
+        plugins: &mut Plugins,
+        render_object_tree: &mut Tree<RenderObjectId, RenderObject>,
+        render_object_id: RenderObjectId,
+        constraints: Constraints,
+    ) -> Size {
+        let Some(render_object) = render_object_tree.get(render_object_id) else {
+            self.fail(EngineError::MissingRenderObject { render_object_id });
+
+            return constraints.smallest();
+        };
+
+        if !render_object.needs_layout() && render_object.constraints() == Some(constraints) {
+            if let Some(size) = render_object.size() {
+                return size;
+            }
+        }
+
+        if self.is_incomplete() || self.depth.get() >= MAX_RECURSION_DEPTH {
+            if let Some(size) = self.cached_layout(render_object_id, constraints) {
+                return size;
+            }
+
+            self.defer(LayoutFrame::Layout {
+                render_object_id,
+                constraints,
+            });
+
+            return constraints.smallest();
+        }
+
+        self.depth.set(self.depth.get() + 1);
+
+        let result = render_object_tree
+            .with(render_object_id, |render_object_tree, render_object| {
+                panic::catch_unwind(AssertUnwindSafe(|| {
+                    render_object.layout(
+                        RenderObjectContextMut {
+                            plugins,
+
+                            render_object_tree,
+
+                            render_object_id: &render_object_id,
+                        },
+                        self,
+                        constraints,
+                    )
+                }))
+            })
+            .unwrap_or(Ok(Err(EngineError::MissingRenderObject {
+                render_object_id,
+            })));
+
+        self.depth.set(self.depth.get() - 1);
+
+        let size = match result {
+            Ok(Ok(size)) => size,
+
+            Ok(Err(err)) => {
+                self.fail(err);
+
+                return constraints.smallest();
+            }
+
+            Err(payload) => {
+                self.catch_panic(render_object_id, payload);
+
+                return constraints.smallest();
+            }
+        };
+
+        if !self.is_incomplete() {
+            self.layouts
+                .borrow_mut()
+                .entry(render_object_id)
+                .or_default()
+                .push((constraints, size));
+        }
+
+        size
+    }
+
+    pub(crate) fn compute_intrinsic_size(
+        &self,
+        plugins: &Plugins,
+        render_object_tree: &Tree<RenderObjectId, RenderObject>,
+        render_object_id: RenderObjectId,
+        dimension: IntrinsicDimension,
+        cross_extent: f32,
+    ) -> f32 {
+        let key = (render_object_id, dimension, cross_extent.to_bits());
+
+        if let Some(intrinsic_size) = self.intrinsic_sizes.borrow().get(&key) {
+            return *intrinsic_size;
+        }
+
+        if self.is_incomplete() || self.depth.get() >= MAX_RECURSION_DEPTH {
+            self.defer(LayoutFrame::IntrinsicSize {
+                render_object_id,
+                dimension,
+                cross_extent,
+            });
+
+            return 0.0;
+        }
+
+        let Some(render_object) = render_object_tree.get(render_object_id) else {
+            self.fail(EngineError::MissingRenderObject { render_object_id });
+
+            return 0.0;
+        };
+
+        self.depth.set(self.depth.get() + 1);
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            render_object.intrinsic_size(
+                RenderObjectContext {
+                    plugins,
+
+                    render_object_tree,
+
+                    render_object_id: &render_object_id,
+                },
+                self,
+                dimension,
+                cross_extent,
+            )
+        }));
+
+        self.depth.set(self.depth.get() - 1);
+
+        let intrinsic_size = match result {
+            Ok(intrinsic_size) => intrinsic_size,
+
+            Err(payload) => {
+                self.catch_panic(render_object_id, payload);
+
+                return 0.0;
+            }
+        };
+
+        if !self.is_incomplete() {
+            self.intrinsic_sizes
+                .borrow_mut()
+                .insert(key, intrinsic_size);
+        }
+
+        intrinsic_size
+    }
+
+    fn cached_layout(
+        &self,
+        render_object_id: RenderObjectId,
+        constraints: Constraints,
+    ) -> Option<Size> {
+        self.layouts
+            .borrow()
+            .get(&render_object_id)?
+            .iter()
+            .find(|(cached_constraints, _)| *cached_constraints == constraints)
+            .map(|(_, size)| *size)
+    }
+
+    fn defer(&self, frame: LayoutFrame) {
+        // Only the first deferral is kept; everything after it is discarded and recomputed
+        // once the deferred frame has been processed.
+        if !self.is_incomplete() {
+            self.deferred.set(Some(frame));
+        }
+    }
+
+    fn fail(&self, err: EngineError) {
+        // Only the first error is kept, since anything after it is likely a consequence of it.
+        self.error.borrow_mut().get_or_insert(err);
+    }
+
+    fn catch_panic(&self, render_object_id: RenderObjectId, payload: Box<dyn Any + Send>) {
+        tracing::error!(?render_object_id, "render object panicked during layout");
+
+        self.panicked
+            .borrow_mut()
+            .get_or_insert((render_object_id, payload));
+    }
+}
@@ -1,7 +1,9 @@
 pub mod canvas;
+mod driver;
 mod object;
 mod paint;
 
+pub(crate) use driver::LayoutDriver;
 pub use object::*;
 pub use paint::*;
 
@@ -85,34 +85,42 @@ impl ChildHitTest<'_> {
         self.children[self.index]
     }
 
-    pub fn render_object(&self) -> &RenderObject {
-        let render_object_id = self.render_object_id();
-
-        self.render_object_tree
-            .get(render_object_id)
-            .expect("child  render object missing during hit test")
+    pub fn render_object(&self) -> Option<&RenderObject> {
+        self.render_object_tree.get(self.render_object_id())
     }
 
+    /// Returns the offset of the child, or [`Offset::ZERO`] if its render object is missing
+    /// from the tree.
     pub fn offset(&self) -> Offset {
-        let render_object_id = self.render_object_id();
+        let Some(render_object) = self.render_object() else {
+            tracing::error!(
+                render_object_id = ?self.render_object_id(),
+                "child render object missing during hit test"
+            );
 
-        self.render_object_tree
-            .get(render_object_id)
-            .expect("child render object missing during hit test")
-            .offset()
+            return Offset::ZERO;
+        };
+
+        render_object.offset()
     }
 
     /// Check if the given position "hits" this widget or any of its descendants.
     ///
     /// The given position must be in the widget's local coordinate space, not the global
     /// coordinate space.
+    ///
+    /// If the child's render object is missing from the tree, the child is skipped.
     pub fn hit_test(&mut self, position: Offset) -> HitTest {
         let render_object_id = self.render_object_id();
 
-        let render_object = self
-            .render_object_tree
-            .get(render_object_id)
-            .expect("child render object missing during hit test");
+        let Some(render_object) = self.render_object_tree.get(render_object_id) else {
+            tracing::error!(
+                ?render_object_id,
+                "child render object missing during hit test"
+            );
+
+            return HitTest::Pass;
+        };
 
         render_object.hit_test(
             RenderObjectContext {
@@ -1,6 +1,6 @@
 use crate::{
     plugin::{context::ContextPlugins, Plugins},
-    render::{object::context::IterChildrenLayout, RenderObject, RenderObjectId},
+    render::{object::context::IterChildrenLayout, LayoutDriver, RenderObject, RenderObjectId},
     util::tree::Tree,
 };
 
@@ -14,6 +14,8 @@ pub struct RenderObjectIntrinsicSizeContext<'ctx> {
     pub render_object_id: &'ctx RenderObjectId,
 
     pub children: &'ctx [RenderObjectId],
+
+    pub(crate) layout_driver: &'ctx LayoutDriver,
 }
 
 impl<'ctx> ContextPlugins<'ctx> for RenderObjectIntrinsicSizeContext<'ctx> {
@@ -52,6 +54,8 @@ impl<'ctx> RenderObjectIntrinsicSizeContext<'ctx> {
             render_object_tree: self.render_object_tree,
 
             children: self.children,
+
+            layout_driver: self.layout_driver,
         }
     }
 }
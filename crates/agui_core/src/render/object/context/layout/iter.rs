@@ -1,6 +1,6 @@
 use crate::{
     plugin::Plugins,
-    render::{RenderObject, RenderObjectContext, RenderObjectContextMut, RenderObjectId},
+    render::{LayoutDriver, RenderObject, RenderObjectId},
     unit::{Constraints, IntrinsicDimension, Offset, Size},
     util::tree::Tree,
 };
@@ -13,6 +13,8 @@ pub struct IterChildrenLayout<'ctx> {
     pub(crate) render_object_tree: &'ctx Tree<RenderObjectId, RenderObject>,
 
     pub(crate) children: &'ctx [RenderObjectId],
+
+    pub(crate) layout_driver: &'ctx LayoutDriver,
 }
 
 impl<'ctx> Iterator for IterChildrenLayout<'ctx> {
@@ -33,6 +35,8 @@ impl<'ctx> Iterator for IterChildrenLayout<'ctx> {
             index: self.index - 1,
 
             children: self.children,
+
+            layout_driver: self.layout_driver,
         })
     }
 }
@@ -45,6 +49,8 @@ pub struct ChildLayout<'ctx> {
     index: usize,
 
     children: &'ctx [RenderObjectId],
+
+    layout_driver: &'ctx LayoutDriver,
 }
 
 impl ChildLayout<'_> {
@@ -57,21 +63,10 @@ impl ChildLayout<'_> {
     }
 
     pub fn compute_intrinsic_size(&self, dimension: IntrinsicDimension, cross_extent: f32) -> f32 {
-        let render_object_id = self.render_object_id();
-
-        let render_object = self
-            .render_object_tree
-            .get(render_object_id)
-            .expect("child render object missing during layout");
-
-        render_object.intrinsic_size(
-            RenderObjectContext {
-                plugins: self.plugins,
-
-                render_object_tree: self.render_object_tree,
-
-                render_object_id: &render_object_id,
-            },
+        self.layout_driver.compute_intrinsic_size(
+            self.plugins,
+            self.render_object_tree,
+            self.render_object_id(),
             dimension,
             cross_extent,
         )
@@ -87,6 +82,8 @@ pub struct IterChildrenLayoutMut<'ctx> {
 
     pub(crate) children: &'ctx [RenderObjectId],
     pub(crate) offsets: &'ctx mut [Offset],
+
+    pub(crate) layout_driver: &'ctx LayoutDriver,
 }
 
 // TODO: refactor to LendingIterator when possible
@@ -108,6 +105,8 @@ impl IterChildrenLayoutMut<'_> {
 
             children: self.children,
             offsets: self.offsets,
+
+            layout_driver: self.layout_driver,
         })
     }
 }
@@ -121,6 +120,8 @@ pub struct ChildLayoutMut<'ctx> {
 
     children: &'ctx [RenderObjectId],
     offsets: &'ctx mut [Offset],
+
+    layout_driver: &'ctx LayoutDriver,
 }
 
 impl ChildLayoutMut<'_> {
@@ -133,45 +134,22 @@ impl ChildLayoutMut<'_> {
     }
 
     pub fn compute_intrinsic_size(&self, dimension: IntrinsicDimension, cross_extent: f32) -> f32 {
-        let render_object_id = self.render_object_id();
-
-        let render_object = self
-            .render_object_tree
-            .get(render_object_id)
-            .expect("child render object missing during layout");
-
-        render_object.intrinsic_size(
-            RenderObjectContext {
-                plugins: self.plugins,
-
-                render_object_tree: self.render_object_tree,
-
-                render_object_id: &render_object_id,
-            },
+        self.layout_driver.compute_intrinsic_size(
+            self.plugins,
+            self.render_object_tree,
+            self.render_object_id(),
             dimension,
             cross_extent,
         )
     }
 
     pub fn compute_layout(&mut self, constraints: impl Into<Constraints>) -> Size {
-        let constraints = constraints.into();
-
-        let render_object_id = self.render_object_id();
-
-        self.render_object_tree
-            .with(render_object_id, |render_object_tree, render_object| {
-                render_object.layout(
-                    RenderObjectContextMut {
-                        plugins: self.plugins,
-
-                        render_object_tree,
-
-                        render_object_id: &render_object_id,
-                    },
-                    constraints,
-                )
-            })
-            .expect("child render object missing during layout")
+        self.layout_driver.compute_layout(
+            self.plugins,
+            self.render_object_tree,
+            self.render_object_id(),
+            constraints.into(),
+        )
     }
 
     pub fn set_offset(&mut self, offset: impl Into<Offset>) {
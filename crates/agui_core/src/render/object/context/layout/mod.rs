@@ -1,6 +1,6 @@
 use crate::{
     plugin::{context::ContextPlugins, Plugins},
-    render::{RenderObject, RenderObjectId},
+    render::{LayoutDriver, RenderObject, RenderObjectId},
     unit::Offset,
     util::tree::Tree,
 };
@@ -20,6 +20,8 @@ pub struct RenderObjectLayoutContext<'ctx> {
 
     pub children: &'ctx [RenderObjectId],
     pub offsets: &'ctx mut [Offset],
+
+    pub(crate) layout_driver: &'ctx LayoutDriver,
 }
 
 impl<'ctx> ContextPlugins<'ctx> for RenderObjectLayoutContext<'ctx> {
@@ -58,6 +60,8 @@ impl RenderObjectLayoutContext<'_> {
             render_object_tree: self.render_object_tree,
 
             children: self.children,
+
+            layout_driver: self.layout_driver,
         }
     }
 
@@ -71,6 +75,8 @@ impl RenderObjectLayoutContext<'_> {
 
             children: self.children,
             offsets: self.offsets,
+
+            layout_driver: self.layout_driver,
         }
     }
 }
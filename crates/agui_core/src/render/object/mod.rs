@@ -1,6 +1,10 @@
-use crate::unit::{AsAny, Constraints, HitTest, HitTestResult, IntrinsicDimension, Offset, Size};
+use crate::{
+    element::ElementId,
+    engine::EngineError,
+    unit::{AsAny, Constraints, HitTest, HitTestResult, IntrinsicDimension, Offset, Size},
+};
 
-use super::canvas::Canvas;
+use super::{canvas::Canvas, driver::LayoutDriver};
 
 mod context;
 mod render_box;
@@ -13,9 +17,22 @@ slotmap::new_key_type! {
 }
 
 pub struct RenderObject {
+    /// The element that created this render object, once it has been added to the tree.
+    element_id: Option<ElementId>,
+
     size: Option<Size>,
     offset: Offset,
 
+    /// The constraints this render object was most recently laid out with.
+    constraints: Option<Constraints>,
+
+    needs_layout: bool,
+    is_relayout_boundary: bool,
+
+    /// Whether an ancestor's layout read this render object's intrinsic size since it was last
+    /// marked as needing layout.
+    parent_uses_intrinsic_size: bool,
+
     render_object: Box<dyn RenderObjectImpl>,
 }
 
@@ -25,13 +42,31 @@ impl RenderObject {
         R: RenderObjectImpl,
     {
         Self {
+            element_id: None,
+
             size: None,
             offset: Offset::ZERO,
 
+            constraints: None,
+
+            needs_layout: true,
+            is_relayout_boundary: false,
+
+            parent_uses_intrinsic_size: false,
+
             render_object: Box::new(render_object),
         }
     }
 
+    /// The element that owns this render object, if it was created by one.
+    pub fn element_id(&self) -> Option<ElementId> {
+        self.element_id
+    }
+
+    pub(crate) fn set_element_id(&mut self, element_id: ElementId) {
+        self.element_id = Some(element_id);
+    }
+
     pub fn size(&self) -> Option<Size> {
         self.size
     }
@@ -40,6 +75,41 @@ impl RenderObject {
         self.offset
     }
 
+    /// The constraints this render object was most recently laid out with, if any.
+    pub fn constraints(&self) -> Option<Constraints> {
+        self.constraints
+    }
+
+    /// Whether this render object must be laid out before its size can be trusted.
+    pub fn needs_layout(&self) -> bool {
+        self.needs_layout
+    }
+
+    /// Whether a change in this render object's layout can never affect its parent's layout.
+    ///
+    /// This is the case when the render object has no parent, when it was given tight
+    /// constraints, or when it is sized solely by the constraints it was given. Layout
+    /// changes stop propagating upwards once they reach a relayout boundary, unless the
+    /// parent [used its intrinsic size](Self::parent_uses_intrinsic_size).
+    pub fn is_relayout_boundary(&self) -> bool {
+        self.is_relayout_boundary
+    }
+
+    /// Whether the layout of an ancestor depends on this render object's intrinsic size, in
+    /// which case a change in its layout must propagate past any relayout boundary.
+    pub fn parent_uses_intrinsic_size(&self) -> bool {
+        self.parent_uses_intrinsic_size
+    }
+
+    pub(crate) fn mark_needs_layout(&mut self) {
+        self.needs_layout = true;
+        self.parent_uses_intrinsic_size = false;
+    }
+
+    pub(crate) fn mark_parent_uses_intrinsic_size(&mut self) {
+        self.parent_uses_intrinsic_size = true;
+    }
+
     pub fn downcast<R>(&self) -> Option<&R>
     where
         R: RenderObjectImpl,
@@ -47,14 +117,22 @@ impl RenderObject {
         (*self.render_object).as_any().downcast_ref::<R>()
     }
 
-    pub fn render_object_name(&self) -> &str {
+    pub fn downcast_mut<R>(&mut self) -> Option<&mut R>
+    where
+        R: RenderObjectImpl,
+    {
+        (*self.render_object).as_any_mut().downcast_mut::<R>()
+    }
+
+    pub fn render_object_name(&self) -> &'static str {
         self.render_object.render_object_name()
     }
 
-    #[tracing::instrument(level = "trace", skip(self, ctx))]
-    pub fn intrinsic_size(
+    #[tracing::instrument(level = "trace", skip(self, ctx, layout_driver))]
+    pub(crate) fn intrinsic_size(
         &self,
         ctx: RenderObjectContext,
+        layout_driver: &LayoutDriver,
         dimension: IntrinsicDimension,
         cross_extent: f32,
     ) -> f32 {
@@ -73,22 +151,21 @@ impl RenderObject {
                 render_object_id: ctx.render_object_id,
 
                 children,
+
+                layout_driver,
             },
             dimension,
             cross_extent,
         )
     }
 
-    #[tracing::instrument(level = "trace", skip(self, ctx))]
-    pub fn layout(&mut self, ctx: RenderObjectContextMut, constraints: Constraints) -> Size {
-        // TODO: technically if the constraints didn't change from the last layout, we shouldn't need to
-        // recompute. Is this assumption correct? if the child hasn't rebuilt, will their layout _ever_ be
-        // able to change?
-
-        // TODO: if `render_object.layout` calls `.layout()` on its children, we need to update this render
-        // object when the child indicates that its layout has changed. If they don't call it, we can skip
-        // layout on render objects that haven't changed.
-
+    #[tracing::instrument(level = "trace", skip(self, ctx, layout_driver))]
+    pub(crate) fn layout(
+        &mut self,
+        ctx: RenderObjectContextMut,
+        layout_driver: &LayoutDriver,
+        constraints: Constraints,
+    ) -> Result<Size, EngineError> {
         let children = ctx
             .render_object_tree
             .get_children(*ctx.render_object_id)
@@ -108,22 +185,46 @@ impl RenderObject {
                 children: &children,
 
                 offsets: &mut offsets,
+
+                layout_driver,
             },
             constraints,
         );
 
+        // If any part of the subtree was deferred, the result may have been computed from
+        // placeholder sizes. The render object remains dirty and will be laid out again once
+        // the deferred work has completed.
+        if layout_driver.is_incomplete() {
+            return Ok(size);
+        }
+
         for (child_id, offset) in children.iter().zip(offsets) {
             ctx.render_object_tree
                 .get_mut(*child_id)
-                .expect("child render object missing during layout")
+                .ok_or(EngineError::MissingChildRenderObject {
+                    render_object_id: *ctx.render_object_id,
+                    render_object_name: self.render_object_name(),
+                    child_id: *child_id,
+                })?
                 .offset = offset;
         }
 
         // The size of the render object may be larger than the constraints (currently, so we can determine intrinsic sizes),
         // so we have to ensure it's constrained, here.
-        self.size = Some(constraints.constrain(size));
+        let size = constraints.constrain(size);
 
-        size
+        self.size = Some(size);
+        self.constraints = Some(constraints);
+        self.needs_layout = false;
+
+        self.is_relayout_boundary = constraints.is_tight()
+            || self.render_object.sized_by_parent()
+            || ctx
+                .render_object_tree
+                .get_parent(*ctx.render_object_id)
+                .is_none();
+
+        Ok(size)
     }
 
     #[tracing::instrument(level = "trace", skip(self, ctx))]
@@ -167,10 +268,12 @@ impl RenderObject {
         hit
     }
 
-    pub fn paint(&self) -> Option<Canvas> {
-        let size = self.size.expect("render object not laid out");
+    pub fn paint(&self) -> Result<Option<Canvas>, EngineError> {
+        let size = self.size.ok_or(EngineError::NotLaidOut {
+            render_object_name: self.render_object_name(),
+        })?;
 
-        self.render_object.paint(size)
+        Ok(self.render_object.paint(size))
     }
 }
 
@@ -179,6 +282,13 @@ impl std::fmt::Debug for RenderObject {
         f.debug_struct("RenderObject")
             .field("size", &self.size)
             .field("offset", &self.offset)
+            .field("constraints", &self.constraints)
+            .field("needs_layout", &self.needs_layout)
+            .field("is_relayout_boundary", &self.is_relayout_boundary)
+            .field(
+                "parent_uses_intrinsic_size",
+                &self.parent_uses_intrinsic_size,
+            )
             .field("render_object", &self.render_object)
             .finish()
     }
@@ -187,9 +297,18 @@ impl std::fmt::Debug for RenderObject {
 #[cfg_attr(any(test, feature = "mocks"), mockall::automock)]
 #[allow(clippy::disallowed_types)]
 #[allow(clippy::needless_lifetimes)]
-pub trait RenderObjectImpl: AsAny + Send + Sync {
+pub trait RenderObjectImpl: AsAny {
     fn render_object_name(&self) -> &'static str;
 
+    /// Whether the size of this render object is determined solely by the constraints given
+    /// to it by its parent.
+    ///
+    /// Render objects that return `true` act as relayout boundaries, since changes to their
+    /// children can never change their own size.
+    fn sized_by_parent(&self) -> bool {
+        false
+    }
+
     fn intrinsic_size<'ctx>(
         &self,
         ctx: RenderObjectIntrinsicSizeContext<'ctx>,
@@ -197,6 +316,17 @@ pub trait RenderObjectImpl: AsAny + Send + Sync {
         cross_extent: f32,
     ) -> f32;
 
+    /// Lays out the render object's children and returns its own size.
+    ///
+    /// This may be called more than once for the same constraints within a single update. Trees
+    /// too deep to lay out recursively are laid out in stages: once the limit is reached, children
+    /// report a provisional size (the smallest size their constraints allow, or an intrinsic size
+    /// of zero) until their subtree has been laid out. Any result computed while a provisional
+    /// size was handed out is discarded, including child offsets, and the render object is laid
+    /// out again once the real sizes are known.
+    ///
+    /// As such, layout must not have side effects beyond the given context, and must not assume
+    /// that the child sizes it sees are final until the call has returned.
     fn layout<'ctx>(
         &mut self,
         ctx: RenderObjectLayoutContext<'ctx>,
@@ -209,8 +339,11 @@ pub trait RenderObjectImpl: AsAny + Send + Sync {
         position: Offset,
     ) -> HitTest {
         if ctx.size.contains(position) {
-            while let Some(mut child) = ctx.iter_children().next_back() {
-                let offset = position - child.offset();
+            let mut children = ctx.iter_children();
+
+            // Children are tested in reverse paint order, so the topmost child is hit first.
+            while let Some(mut child) = children.next_back() {
+                let offset = child.offset();
 
                 if child.hit_test_with_offset(offset, position) == HitTest::Absorb {
                     return HitTest::Absorb;
@@ -233,3 +366,23 @@ impl std::fmt::Debug for Box<dyn RenderObjectImpl> {
             .finish_non_exhaustive()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{element::mock::render::MockRenderObject, engine::EngineError};
+
+    use super::RenderObject;
+
+    #[test]
+    pub fn errors_when_painted_before_layout() {
+        let render_object = RenderObject::from(MockRenderObject::new("NotLaidOut"));
+
+        assert_eq!(
+            render_object.paint().err(),
+            Some(EngineError::NotLaidOut {
+                render_object_name: "NotLaidOut"
+            }),
+            "should not paint a render object that has no size"
+        );
+    }
+}
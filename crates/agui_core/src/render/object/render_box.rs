@@ -1,5 +1,5 @@
 use crate::{
-    render::{RenderObjectContext, RenderObjectContextMut, RenderObjectImpl},
+    render::RenderObjectImpl,
     unit::{Constraints, IntrinsicDimension, Size},
 };
 
@@ -26,29 +26,17 @@ impl RenderObjectImpl for RenderBox {
                 "RenderBox cannot have more than a single child"
             );
 
-            let child_id = *ctx.children.first().unwrap();
-
             // By default, we take the intrinsic size of the child.
-            ctx.render_object_tree
-                .get(child_id)
-                .expect("child element missing while computing intrinsic size")
-                .intrinsic_size(
-                    RenderObjectContext {
-                        plugins: ctx.plugins,
-
-                        render_object_tree: ctx.render_object_tree,
-
-                        render_object_id: &child_id,
-                    },
-                    dimension,
-                    cross_extent,
-                )
+            ctx.iter_children()
+                .next()
+                .unwrap()
+                .compute_intrinsic_size(dimension, cross_extent)
         } else {
             0.0
         }
     }
 
-    fn layout(&mut self, ctx: RenderObjectLayoutContext, constraints: Constraints) -> Size {
+    fn layout(&mut self, mut ctx: RenderObjectLayoutContext, constraints: Constraints) -> Size {
         if !ctx.children.is_empty() {
             assert_eq!(
                 ctx.children.len(),
@@ -56,23 +44,11 @@ impl RenderObjectImpl for RenderBox {
                 "RenderBox cannot have more than a single child"
             );
 
-            let child_id = *ctx.children.first().unwrap();
-
             // By default, we take the size of the child.
-            ctx.render_object_tree
-                .with(child_id, |render_object_tree, render_object| {
-                    render_object.layout(
-                        RenderObjectContextMut {
-                            plugins: ctx.plugins,
-
-                            render_object_tree,
-
-                            render_object_id: &child_id,
-                        },
-                        constraints,
-                    )
-                })
-                .expect("child render object missing during layout")
+            ctx.iter_children_mut()
+                .next()
+                .unwrap()
+                .compute_layout(constraints)
         } else {
             constraints.smallest()
         }
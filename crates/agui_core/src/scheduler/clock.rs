@@ -0,0 +1,50 @@
+use std::{cell::Cell, rc::Rc, time::Duration, time::Instant};
+
+/// A source of timestamps for the [`FrameSchedulerPlugin`](super::FrameSchedulerPlugin) and
+/// [`TimerPlugin`](crate::timer::TimerPlugin).
+pub trait FrameClock: 'static {
+    /// Returns the time elapsed since some fixed point, which must never decrease.
+    fn now(&self) -> Duration;
+}
+
+/// A clock that follows the system's monotonic time.
+pub struct SystemClock {
+    started_at: Instant,
+}
+
+impl Default for SystemClock {
+    fn default() -> Self {
+        Self {
+            started_at: Instant::now(),
+        }
+    }
+}
+
+impl FrameClock for SystemClock {
+    fn now(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+}
+
+/// A clock that only moves when told to, allowing frames to be stepped through deterministically
+/// in tests. Clones share the same time.
+#[derive(Debug, Default, Clone)]
+pub struct ManualClock {
+    now: Rc<Cell<Duration>>,
+}
+
+impl ManualClock {
+    pub fn advance(&self, duration: Duration) {
+        self.now.set(self.now.get() + duration);
+    }
+
+    pub fn set(&self, now: Duration) {
+        self.now.set(now);
+    }
+}
+
+impl FrameClock for ManualClock {
+    fn now(&self) -> Duration {
+        self.now.get()
+    }
+}
@@ -0,0 +1,231 @@
+use std::{cell::RefCell, rc::Rc, sync::mpsc, time::Duration};
+
+use slotmap::SlotMap;
+
+use crate::{
+    callback::Callback,
+    plugin::{context::PluginBeforeUpdateContext, Plugin},
+};
+
+mod clock;
+mod ticker;
+
+pub use clock::*;
+pub use ticker::*;
+
+use self::ticker::TickerEntry;
+
+slotmap::new_key_type! {
+    pub struct TickerId;
+}
+
+/// Gives the engine a notion of frames. Each engine update is treated as a frame, at the start
+/// of which every active [`Ticker`] is called with the time elapsed since it was started.
+///
+/// The event loop is responsible for updating the engine once per frame for as long as
+/// [`FrameSchedulerPlugin::has_scheduled_frame`] returns `true`.
+pub struct FrameSchedulerPlugin {
+    inner: Rc<RefCell<SchedulerInner>>,
+}
+
+struct SchedulerInner {
+    clock: Box<dyn FrameClock>,
+
+    update_notifier_tx: Option<mpsc::Sender<()>>,
+
+    tickers: SlotMap<TickerId, TickerEntry>,
+
+    frame_time: Option<Duration>,
+}
+
+impl SchedulerInner {
+    /// Wakes the event loop so that the next frame is produced.
+    fn schedule_frame(&self) {
+        if let Some(update_notifier_tx) = &self.update_notifier_tx {
+            let _ = update_notifier_tx.send(());
+        }
+    }
+}
+
+impl Default for FrameSchedulerPlugin {
+    fn default() -> Self {
+        Self::with_clock(SystemClock::default())
+    }
+}
+
+impl FrameSchedulerPlugin {
+    pub fn with_clock(clock: impl FrameClock) -> Self {
+        Self {
+            inner: Rc::new(RefCell::new(SchedulerInner {
+                clock: Box::new(clock),
+
+                update_notifier_tx: None,
+
+                tickers: SlotMap::default(),
+
+                frame_time: None,
+            })),
+        }
+    }
+
+    /// Sets the channel used to wake the event loop when a ticker is started while no frames
+    /// are being produced.
+    pub fn with_notifier(self, update_notifier_tx: mpsc::Sender<()>) -> Self {
+        self.inner.borrow_mut().update_notifier_tx = Some(update_notifier_tx);
+        self
+    }
+
+    /// Creates an inactive ticker that will call `callback` once per frame after it's started.
+    pub fn create_ticker(&self, callback: Callback<Duration>) -> Ticker {
+        self.create_ticker_with(move |elapsed| callback.call(elapsed))
+    }
+
+    /// Creates an inactive ticker that will call `func` once per frame after it's started.
+    ///
+    /// Unlike [`create_ticker`](Self::create_ticker), the function is called directly on the
+    /// engine's thread, so it need not be `Send`.
+    pub fn create_ticker_with(&self, func: impl Fn(Duration) + 'static) -> Ticker {
+        let ticker_id = self.inner.borrow_mut().tickers.insert(TickerEntry {
+            func: Rc::new(func),
+
+            is_active: false,
+            started_at: None,
+        });
+
+        Ticker::new(ticker_id, Rc::clone(&self.inner))
+    }
+
+    /// Check if any ticker is active, meaning another frame should be produced.
+    pub fn has_scheduled_frame(&self) -> bool {
+        self.inner
+            .borrow()
+            .tickers
+            .values()
+            .any(|ticker| ticker.is_active)
+    }
+
+    /// Returns the timestamp of the most recent frame.
+    pub fn frame_time(&self) -> Option<Duration> {
+        self.inner.borrow().frame_time
+    }
+
+    /// Begins a new frame, calling each active ticker with the time elapsed since it started.
+    pub fn tick(&self) {
+        let ticks = {
+            let mut inner = self.inner.borrow_mut();
+
+            let now = inner.clock.now();
+
+            inner.frame_time = Some(now);
+
+            inner
+                .tickers
+                .values_mut()
+                .filter(|ticker| ticker.is_active)
+                .map(|ticker| {
+                    let started_at = *ticker.started_at.get_or_insert(now);
+
+                    (Rc::clone(&ticker.func), now.saturating_sub(started_at))
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // The scheduler must not be borrowed while calling tickers, as they may start or stop
+        // tickers themselves.
+        for (func, elapsed) in ticks {
+            func(elapsed);
+        }
+    }
+}
+
+impl Plugin for FrameSchedulerPlugin {
+    fn on_before_update(&mut self, _: &mut PluginBeforeUpdateContext) {
+        self.tick();
+    }
+}
+
+impl std::fmt::Debug for FrameSchedulerPlugin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let inner = self.inner.borrow();
+
+        f.debug_struct("FrameSchedulerPlugin")
+            .field("tickers", &inner.tickers.len())
+            .field("frame_time", &inner.frame_time)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use crate::{element::mock::DummyWidget, engine::Engine, plugin::context::ContextPlugins};
+
+    use super::{FrameSchedulerPlugin, ManualClock};
+
+    #[test]
+    pub fn calls_active_tickers_each_frame() {
+        let clock = ManualClock::default();
+
+        let mut engine = Engine::builder()
+            .add_plugin(FrameSchedulerPlugin::with_clock(clock.clone()))
+            .with_root(DummyWidget)
+            .build();
+
+        let ticks = Arc::new(Mutex::new(Vec::new()));
+
+        let ticker = engine
+            .plugins()
+            .get::<FrameSchedulerPlugin>()
+            .expect("no frame scheduler")
+            .create_ticker({
+                let ticks = Arc::clone(&ticks);
+
+                (move |elapsed| ticks.lock().unwrap().push(elapsed)).into()
+            });
+
+        engine.update().unwrap();
+
+        assert!(
+            ticks.lock().unwrap().is_empty(),
+            "should not tick before being started"
+        );
+
+        ticker.start();
+
+        assert!(engine
+            .plugins()
+            .get::<FrameSchedulerPlugin>()
+            .unwrap()
+            .has_scheduled_frame());
+
+        clock.advance(Duration::from_millis(5));
+
+        engine.update().unwrap();
+
+        clock.advance(Duration::from_millis(16));
+
+        engine.update().unwrap();
+
+        ticker.stop();
+
+        clock.advance(Duration::from_millis(16));
+
+        engine.update().unwrap();
+
+        assert_eq!(
+            *ticks.lock().unwrap(),
+            vec![Duration::ZERO, Duration::from_millis(16)],
+            "should report the time since the first frame after starting"
+        );
+
+        assert!(!engine
+            .plugins()
+            .get::<FrameSchedulerPlugin>()
+            .unwrap()
+            .has_scheduled_frame());
+    }
+}
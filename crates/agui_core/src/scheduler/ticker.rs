@@ -0,0 +1,73 @@
+use std::{cell::RefCell, rc::Rc, time::Duration};
+
+use super::{SchedulerInner, TickerId};
+
+pub(super) struct TickerEntry {
+    pub(super) func: Rc<dyn Fn(Duration)>,
+
+    pub(super) is_active: bool,
+
+    /// The time of the first frame since the ticker was started.
+    pub(super) started_at: Option<Duration>,
+}
+
+/// Calls its callback once per frame with the time elapsed since it was started, for as long as
+/// it's active. The ticker is removed from the scheduler when dropped.
+pub struct Ticker {
+    id: TickerId,
+
+    scheduler: Rc<RefCell<SchedulerInner>>,
+}
+
+impl Ticker {
+    pub(super) fn new(id: TickerId, scheduler: Rc<RefCell<SchedulerInner>>) -> Self {
+        Self { id, scheduler }
+    }
+
+    /// Starts ticking from the next frame, which will report an elapsed time of zero. Does
+    /// nothing if the ticker is already active.
+    pub fn start(&self) {
+        let mut scheduler = self.scheduler.borrow_mut();
+
+        if let Some(ticker) = scheduler.tickers.get_mut(self.id) {
+            if !ticker.is_active {
+                ticker.is_active = true;
+                ticker.started_at = None;
+
+                scheduler.schedule_frame();
+            }
+        }
+    }
+
+    /// Stops ticking. The next time the ticker is started, its elapsed time will restart from
+    /// zero.
+    pub fn stop(&self) {
+        if let Some(ticker) = self.scheduler.borrow_mut().tickers.get_mut(self.id) {
+            ticker.is_active = false;
+            ticker.started_at = None;
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.scheduler
+            .borrow()
+            .tickers
+            .get(self.id)
+            .is_some_and(|ticker| ticker.is_active)
+    }
+}
+
+impl Drop for Ticker {
+    fn drop(&mut self) {
+        self.scheduler.borrow_mut().tickers.remove(self.id);
+    }
+}
+
+impl std::fmt::Debug for Ticker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Ticker")
+            .field("id", &self.id)
+            .field("is_active", &self.is_active())
+            .finish()
+    }
+}
@@ -0,0 +1,5 @@
+use super::TaskExecutor;
+
+pub trait ContextTaskExecutor {
+    fn task_executor(&self) -> &TaskExecutor;
+}
@@ -0,0 +1,160 @@
+use std::{
+    cell::RefCell,
+    future::Future,
+    rc::Rc,
+    sync::{mpsc, Arc},
+    task::{Context, Poll},
+};
+
+use futures::{
+    future::LocalBoxFuture,
+    task::{waker, ArcWake},
+    FutureExt,
+};
+use parking_lot::Mutex;
+use rustc_hash::FxHashSet;
+use slotmap::SlotMap;
+
+use crate::element::ElementId;
+
+mod context;
+
+pub use context::*;
+
+slotmap::new_key_type! {
+    pub struct TaskId;
+}
+
+/// Runs futures spawned by elements on the thread that owns the engine. Tasks are polled during
+/// [`Engine::update`](crate::engine::Engine::update) whenever they are woken, and are cancelled
+/// once the element that spawned them is removed from the tree.
+#[derive(Clone)]
+pub struct TaskExecutor(Rc<InnerTaskExecutor>);
+
+struct InnerTaskExecutor {
+    tasks: RefCell<SlotMap<TaskId, Task>>,
+
+    woken: Arc<Mutex<FxHashSet<TaskId>>>,
+    update_notifier_tx: mpsc::Sender<()>,
+}
+
+struct Task {
+    element_id: ElementId,
+
+    /// The task's future, which is taken while it is being polled.
+    future: Option<LocalBoxFuture<'static, ()>>,
+}
+
+impl TaskExecutor {
+    pub(crate) fn new(update_notifier_tx: mpsc::Sender<()>) -> Self {
+        Self(Rc::new(InnerTaskExecutor {
+            tasks: RefCell::default(),
+
+            woken: Arc::default(),
+            update_notifier_tx,
+        }))
+    }
+
+    /// Spawns a future owned by the given element. It will first be polled during the current
+    /// (or next) update.
+    pub fn spawn<Fut>(&self, element_id: ElementId, future: Fut) -> TaskId
+    where
+        Fut: Future<Output = ()> + 'static,
+    {
+        let task_id = self.0.tasks.borrow_mut().insert(Task {
+            element_id,
+
+            future: Some(future.boxed_local()),
+        });
+
+        self.0.woken.lock().insert(task_id);
+
+        let _ = self.0.update_notifier_tx.send(());
+
+        task_id
+    }
+
+    /// Check if the task has not yet completed or been cancelled.
+    pub fn is_running(&self, task_id: TaskId) -> bool {
+        self.0.tasks.borrow().contains_key(task_id)
+    }
+
+    /// Cancels the task, dropping its future without polling it again.
+    pub fn cancel(&self, task_id: TaskId) {
+        self.0.tasks.borrow_mut().remove(task_id);
+    }
+
+    /// Cancels every task spawned by the element.
+    pub(crate) fn remove_element(&self, element_id: ElementId) {
+        self.0
+            .tasks
+            .borrow_mut()
+            .retain(|_, task| task.element_id != element_id);
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.woken.lock().is_empty()
+    }
+
+    /// Polls each task that has been woken since the last time this was called.
+    pub(crate) fn poll(&self) {
+        let woken = std::mem::take(&mut *self.0.woken.lock());
+
+        for task_id in woken {
+            let Some(mut future) = self
+                .0
+                .tasks
+                .borrow_mut()
+                .get_mut(task_id)
+                .and_then(|task| task.future.take())
+            else {
+                continue;
+            };
+
+            let waker = waker(Arc::new(TaskWaker {
+                task_id,
+
+                woken: Arc::clone(&self.0.woken),
+                update_notifier_tx: self.0.update_notifier_tx.clone(),
+            }));
+
+            let mut tasks = match future.as_mut().poll(&mut Context::from_waker(&waker)) {
+                Poll::Ready(()) => {
+                    self.0.tasks.borrow_mut().remove(task_id);
+
+                    continue;
+                }
+
+                Poll::Pending => self.0.tasks.borrow_mut(),
+            };
+
+            // The task may have been cancelled while it was being polled
+            if let Some(task) = tasks.get_mut(task_id) {
+                task.future = Some(future);
+            }
+        }
+    }
+}
+
+impl std::fmt::Debug for TaskExecutor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TaskExecutor")
+            .field("tasks", &self.0.tasks.borrow().len())
+            .finish()
+    }
+}
+
+struct TaskWaker {
+    task_id: TaskId,
+
+    woken: Arc<Mutex<FxHashSet<TaskId>>>,
+    update_notifier_tx: mpsc::Sender<()>,
+}
+
+impl ArcWake for TaskWaker {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        arc_self.woken.lock().insert(arc_self.task_id);
+
+        let _ = arc_self.update_notifier_tx.send(());
+    }
+}
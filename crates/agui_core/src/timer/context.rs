@@ -0,0 +1,70 @@
+use std::{hash::Hash, time::Duration};
+
+use crate::{
+    callback::Callback,
+    element::{ContextElement, ElementId},
+    plugin::context::ContextPlugins,
+};
+
+use super::{TimerId, TimerPlugin};
+
+/// Creates timers owned by the current element. Each method returns `None` if the
+/// [`TimerPlugin`] has not been added to the engine.
+pub trait ContextTimers {
+    /// Calls the callback once, after the delay has elapsed.
+    fn set_timeout(&self, delay: Duration, callback: Callback<()>) -> Option<TimerId>;
+
+    /// Calls the callback repeatedly, each time the interval elapses.
+    fn set_interval(&self, interval: Duration, callback: Callback<()>) -> Option<TimerId>;
+
+    /// Calls the callback once the delay has elapsed without being debounced again with the same
+    /// `key`.
+    fn debounce(&self, key: impl Hash, delay: Duration, callback: Callback<()>) -> Option<TimerId>;
+
+    fn cancel_timer(&self, timer_id: TimerId);
+}
+
+impl<'ctx, C> ContextTimers for C
+where
+    C: ContextElement + ContextPlugins<'ctx>,
+{
+    fn set_timeout(&self, delay: Duration, callback: Callback<()>) -> Option<TimerId> {
+        with_timer_plugin(self, |plugin, element_id| {
+            plugin.set_timeout(element_id, delay, callback)
+        })
+    }
+
+    fn set_interval(&self, interval: Duration, callback: Callback<()>) -> Option<TimerId> {
+        with_timer_plugin(self, |plugin, element_id| {
+            plugin.set_interval(element_id, interval, callback)
+        })
+    }
+
+    fn debounce(&self, key: impl Hash, delay: Duration, callback: Callback<()>) -> Option<TimerId> {
+        with_timer_plugin(self, |plugin, element_id| {
+            plugin.debounce(element_id, key, delay, callback)
+        })
+    }
+
+    fn cancel_timer(&self, timer_id: TimerId) {
+        with_timer_plugin(self, |plugin, _| plugin.cancel(timer_id));
+    }
+}
+
+fn with_timer_plugin<'ctx, C, R>(
+    ctx: &C,
+    func: impl FnOnce(&TimerPlugin, ElementId) -> R,
+) -> Option<R>
+where
+    C: ContextElement + ContextPlugins<'ctx>,
+{
+    let element_id = ctx.element_id();
+
+    let Some(timer_plugin) = ctx.plugins().get::<TimerPlugin>() else {
+        tracing::warn!("TimerPlugin not found in the context");
+
+        return None;
+    };
+
+    Some(func(timer_plugin, element_id))
+}
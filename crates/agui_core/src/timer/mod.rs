@@ -0,0 +1,508 @@
+use std::{
+    cell::RefCell,
+    hash::{Hash, Hasher},
+    time::Duration,
+};
+
+use rustc_hash::FxHasher;
+use slotmap::SlotMap;
+
+use crate::{
+    callback::{Callback, CallbackQueue, ContextCallbackQueue},
+    element::{ContextElement, ElementId},
+    plugin::{
+        context::{PluginBeforeUpdateContext, PluginElementUnmountContext},
+        Plugin,
+    },
+    scheduler::{FrameClock, SystemClock},
+};
+
+mod context;
+
+pub use context::*;
+
+slotmap::new_key_type! {
+    pub struct TimerId;
+}
+
+/// The shortest interval a timer may repeat at. Shorter intervals are clamped to it, since a zero
+/// interval would leave the timer permanently expired and keep the event loop spinning.
+pub const MIN_INTERVAL: Duration = Duration::from_millis(1);
+
+/// Calls callbacks after a delay or at a regular interval. Expired timers are fired at the start
+/// of each engine update, and each timer belongs to the element that created it, so it's
+/// cancelled once that element is unmounted.
+///
+/// The event loop is responsible for updating the engine once [`TimerPlugin::next_timeout`] has
+/// elapsed.
+pub struct TimerPlugin {
+    clock: Box<dyn FrameClock>,
+
+    timers: RefCell<SlotMap<TimerId, Timer>>,
+}
+
+struct Timer {
+    element_id: ElementId,
+
+    kind: TimerKind,
+    deadline: Duration,
+
+    callback: Callback<()>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum TimerKind {
+    Timeout,
+    Interval(Duration),
+    Debounce(u64),
+}
+
+impl Default for TimerPlugin {
+    fn default() -> Self {
+        Self::with_clock(SystemClock::default())
+    }
+}
+
+impl TimerPlugin {
+    pub fn with_clock(clock: impl FrameClock) -> Self {
+        Self {
+            clock: Box::new(clock),
+
+            timers: RefCell::default(),
+        }
+    }
+
+    /// Calls the callback once, after the delay has elapsed.
+    pub fn set_timeout(
+        &self,
+        element_id: ElementId,
+        delay: Duration,
+        callback: Callback<()>,
+    ) -> TimerId {
+        self.insert(element_id, TimerKind::Timeout, delay, callback)
+    }
+
+    /// Calls the callback repeatedly, each time the interval elapses. If an update is late, any
+    /// missed calls are skipped rather than made all at once.
+    ///
+    /// The interval is clamped to at least [`MIN_INTERVAL`].
+    pub fn set_interval(
+        &self,
+        element_id: ElementId,
+        interval: Duration,
+        callback: Callback<()>,
+    ) -> TimerId {
+        let interval = interval.max(MIN_INTERVAL);
+
+        self.insert(
+            element_id,
+            TimerKind::Interval(interval),
+            interval,
+            callback,
+        )
+    }
+
+    /// Calls the callback once the delay has elapsed without the same element debouncing with
+    /// the same `key` again, which restarts the delay and replaces the callback.
+    pub fn debounce(
+        &self,
+        element_id: ElementId,
+        key: impl Hash,
+        delay: Duration,
+        callback: Callback<()>,
+    ) -> TimerId {
+        let mut hasher = FxHasher::default();
+        key.hash(&mut hasher);
+
+        let kind = TimerKind::Debounce(hasher.finish());
+
+        let mut timers = self.timers.borrow_mut();
+
+        let existing = timers
+            .iter_mut()
+            .find(|(_, timer)| timer.kind == kind && timer.element_id == element_id);
+
+        if let Some((timer_id, timer)) = existing {
+            timer.deadline = self.clock.now() + delay;
+            timer.callback = callback;
+
+            return timer_id;
+        }
+
+        drop(timers);
+
+        self.insert(element_id, kind, delay, callback)
+    }
+
+    /// Cancels the timer, so that its callback will not be called again.
+    pub fn cancel(&self, timer_id: TimerId) {
+        self.timers.borrow_mut().remove(timer_id);
+    }
+
+    /// Check if the timer has not yet fired or been cancelled. Intervals remain active until
+    /// they're cancelled.
+    pub fn is_active(&self, timer_id: TimerId) -> bool {
+        self.timers.borrow().contains_key(timer_id)
+    }
+
+    /// Returns the time remaining until the next timer fires, if any.
+    pub fn next_timeout(&self) -> Option<Duration> {
+        let now = self.clock.now();
+
+        self.timers
+            .borrow()
+            .values()
+            .map(|timer| timer.deadline.saturating_sub(now))
+            .min()
+    }
+
+    fn insert(
+        &self,
+        element_id: ElementId,
+        kind: TimerKind,
+        delay: Duration,
+        callback: Callback<()>,
+    ) -> TimerId {
+        self.timers.borrow_mut().insert(Timer {
+            element_id,
+
+            kind,
+            deadline: self.clock.now() + delay,
+
+            callback,
+        })
+    }
+
+    /// Calls the callback of every timer whose deadline has passed, in the order they expired.
+    ///
+    /// Timers belonging to elements that are no longer alive in the `callback_queue` are dropped
+    /// without being called, whether their callback is bound to a widget or to a function.
+    pub fn fire_expired(&self, callback_queue: &CallbackQueue) {
+        let now = self.clock.now();
+
+        let mut expired = Vec::new();
+
+        // The timers must not be borrowed while calling callbacks, as they may create timers
+        // themselves.
+        self.timers.borrow_mut().retain(|_, timer| {
+            if !callback_queue.is_alive(timer.element_id) {
+                return false;
+            }
+
+            if timer.deadline > now {
+                return true;
+            }
+
+            expired.push((timer.deadline, timer.callback.clone()));
+
+            if let TimerKind::Interval(interval) = timer.kind {
+                timer.deadline += interval;
+
+                if timer.deadline <= now {
+                    timer.deadline = now + interval;
+                }
+
+                true
+            } else {
+                false
+            }
+        });
+
+        expired.sort_by_key(|(deadline, _)| *deadline);
+
+        for (_, callback) in expired {
+            callback.call(());
+        }
+    }
+}
+
+impl Plugin for TimerPlugin {
+    fn on_before_update(&mut self, ctx: &mut PluginBeforeUpdateContext) {
+        self.fire_expired(ctx.callback_queue());
+    }
+
+    fn on_element_unmount(&mut self, ctx: &mut PluginElementUnmountContext) {
+        let element_id = ctx.element_id();
+
+        self.timers
+            .borrow_mut()
+            .retain(|_, timer| timer.element_id != element_id);
+    }
+}
+
+impl std::fmt::Debug for TimerPlugin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TimerPlugin")
+            .field("timers", &self.timers.borrow().len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        cell::RefCell,
+        rc::Rc,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use crate::{
+        callback::Callback,
+        element::mock::{build::MockBuildWidget, DummyWidget},
+        engine::Engine,
+        plugin::context::ContextPlugins,
+        scheduler::ManualClock,
+        widget::IntoWidget,
+    };
+
+    use super::{TimerPlugin, MIN_INTERVAL};
+
+    fn counter() -> (Arc<AtomicUsize>, Callback<()>) {
+        let count = Arc::new(AtomicUsize::new(0));
+
+        let callback = Callback::from({
+            let count = Arc::clone(&count);
+
+            move |()| {
+                count.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+
+        (count, callback)
+    }
+
+    fn frame(engine: &mut Engine, clock: &ManualClock, millis: u64) {
+        clock.advance(Duration::from_millis(millis));
+
+        engine.update().unwrap();
+    }
+
+    #[test]
+    pub fn fires_timeouts_and_intervals() {
+        let clock = ManualClock::default();
+
+        let mut engine = Engine::builder()
+            .add_plugin(TimerPlugin::with_clock(clock.clone()))
+            .with_root(DummyWidget)
+            .build();
+
+        engine.update().unwrap();
+
+        let timers = engine.plugins().get::<TimerPlugin>().unwrap();
+
+        let (timeout_count, timeout) = counter();
+        let (interval_count, interval) = counter();
+
+        let timeout_id = timers.set_timeout(engine.root(), Duration::from_millis(100), timeout);
+        let interval_id = timers.set_interval(engine.root(), Duration::from_millis(40), interval);
+
+        assert_eq!(timers.next_timeout(), Some(Duration::from_millis(40)));
+
+        frame(&mut engine, &clock, 50);
+
+        assert_eq!(timeout_count.load(Ordering::Relaxed), 0);
+        assert_eq!(interval_count.load(Ordering::Relaxed), 1);
+
+        frame(&mut engine, &clock, 50);
+
+        assert_eq!(timeout_count.load(Ordering::Relaxed), 1);
+        assert_eq!(interval_count.load(Ordering::Relaxed), 2);
+
+        let timers = engine.plugins().get::<TimerPlugin>().unwrap();
+
+        assert!(
+            !timers.is_active(timeout_id),
+            "timeouts should only fire once"
+        );
+        assert!(timers.is_active(interval_id));
+
+        timers.cancel(interval_id);
+
+        frame(&mut engine, &clock, 50);
+
+        assert_eq!(
+            interval_count.load(Ordering::Relaxed),
+            2,
+            "should not fire once cancelled"
+        );
+    }
+
+    #[test]
+    pub fn debouncing_restarts_the_delay() {
+        let clock = ManualClock::default();
+
+        let mut engine = Engine::builder()
+            .add_plugin(TimerPlugin::with_clock(clock.clone()))
+            .with_root(DummyWidget)
+            .build();
+
+        engine.update().unwrap();
+
+        let (count, callback) = counter();
+        let (other_count, other_callback) = counter();
+        let (unrelated_count, unrelated_callback) = counter();
+
+        let debounce = |engine: &Engine, key: &str, callback: &Callback<()>| {
+            engine.plugins().get::<TimerPlugin>().unwrap().debounce(
+                engine.root(),
+                key,
+                Duration::from_millis(100),
+                callback.clone(),
+            )
+        };
+
+        let timer_id = debounce(&engine, "search", &other_callback);
+
+        assert_ne!(
+            debounce(&engine, "other", &unrelated_callback),
+            timer_id,
+            "should not share a timer with a different key"
+        );
+
+        frame(&mut engine, &clock, 60);
+
+        assert_eq!(
+            debounce(&engine, "search", &callback),
+            timer_id,
+            "should reuse the timer"
+        );
+
+        frame(&mut engine, &clock, 60);
+
+        assert_eq!(
+            count.load(Ordering::Relaxed),
+            0,
+            "should have been restarted"
+        );
+        assert_eq!(unrelated_count.load(Ordering::Relaxed), 1);
+
+        frame(&mut engine, &clock, 60);
+
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+        assert_eq!(
+            other_count.load(Ordering::Relaxed),
+            0,
+            "should have replaced the callback"
+        );
+    }
+
+    #[test]
+    pub fn clamps_zero_intervals() {
+        let clock = ManualClock::default();
+
+        let mut engine = Engine::builder()
+            .add_plugin(TimerPlugin::with_clock(clock.clone()))
+            .with_root(DummyWidget)
+            .build();
+
+        engine.update().unwrap();
+
+        let (count, callback) = counter();
+
+        let timers = engine.plugins().get::<TimerPlugin>().unwrap();
+
+        timers.set_interval(engine.root(), Duration::ZERO, callback);
+
+        assert_eq!(timers.next_timeout(), Some(MIN_INTERVAL));
+
+        frame(&mut engine, &clock, 1);
+
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+        assert_eq!(
+            engine
+                .plugins()
+                .get::<TimerPlugin>()
+                .unwrap()
+                .next_timeout(),
+            Some(MIN_INTERVAL),
+            "should not be expired again immediately"
+        );
+    }
+
+    #[test]
+    pub fn cancels_timers_of_unmounted_elements() {
+        let clock = ManualClock::default();
+
+        let child = Rc::new(RefCell::new(DummyWidget.into_widget()));
+
+        let root_widget = MockBuildWidget::new("RootWidget");
+        {
+            root_widget.mock.borrow_mut().expect_build().returning_st({
+                let child = Rc::clone(&child);
+
+                move |_| child.borrow().clone()
+            });
+        }
+
+        let mut engine = Engine::builder()
+            .add_plugin(TimerPlugin::with_clock(clock.clone()))
+            .with_root(root_widget)
+            .build();
+
+        engine.update().unwrap();
+
+        let root_id = engine.root();
+        let child_id = engine.elements().get_children(root_id).unwrap()[0];
+
+        let (count, callback) = counter();
+
+        let timer_id = engine.plugins().get::<TimerPlugin>().unwrap().set_timeout(
+            child_id,
+            Duration::from_millis(100),
+            callback,
+        );
+
+        let other_widget = MockBuildWidget::new("OtherWidget");
+        {
+            other_widget
+                .mock
+                .borrow_mut()
+                .expect_build()
+                .returning(|_| DummyWidget.into_widget());
+        }
+
+        *child.borrow_mut() = other_widget.into_widget();
+
+        engine.mark_dirty(root_id);
+
+        engine.update().unwrap();
+
+        frame(&mut engine, &clock, 200);
+
+        assert!(
+            !engine
+                .plugins()
+                .get::<TimerPlugin>()
+                .unwrap()
+                .is_active(timer_id),
+            "timer should be cancelled once its element is unmounted"
+        );
+
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+
+        let (count, callback) = counter();
+
+        let timer_id = engine.plugins().get::<TimerPlugin>().unwrap().set_timeout(
+            child_id,
+            Duration::from_millis(100),
+            callback,
+        );
+
+        frame(&mut engine, &clock, 200);
+
+        assert!(
+            !engine
+                .plugins()
+                .get::<TimerPlugin>()
+                .unwrap()
+                .is_active(timer_id),
+            "timer should be dropped if its element no longer exists"
+        );
+
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+    }
+}
@@ -28,15 +28,36 @@ impl HitTestEntry {
     pub fn global_transform(&self) -> Mat4 {
         self.transform
     }
+
+    /// Converts a position in the global coordinate space into the local coordinate space of
+    /// the render object that was hit.
+    pub fn to_local(&self, position: Offset) -> Offset {
+        let local_position = self.transform.inverse().project_point3(position.into());
+
+        Offset::new(local_position.x, local_position.y)
+    }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct HitTestResult {
     path: Vec<HitTestEntry>,
     transforms: Vec<Mat4>,
 }
 
 impl HitTestResult {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The render objects that were hit, ordered from the deepest to the shallowest.
+    pub fn path(&self) -> &[HitTestEntry] {
+        &self.path
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.path.is_empty()
+    }
+
     fn current_transform(&self) -> Mat4 {
         self.transforms.last().copied().unwrap_or_default()
     }
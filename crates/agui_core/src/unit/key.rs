@@ -20,7 +20,9 @@ pub enum Key {
 
     /// A global key.
     ///
-    /// This should be unique throughout the entire system.
+    /// This should be unique throughout the entire system. If a widget with a global key moves to
+    /// a different parent within a single update, its element (along with any state it holds) and
+    /// render object will be reparented instead of rebuilt.
     Global(u64),
 }
 
@@ -65,7 +65,7 @@ impl Shape {
                 builder.add_rectangle(
                     &lyon::math::Box2D {
                         min: Point2D::new(rect.left, rect.top),
-                        max: Point2D::new(rect.width, rect.height),
+                        max: Point2D::new(rect.left + rect.width, rect.top + rect.height),
                     },
                     Winding::Positive,
                 );
@@ -84,7 +84,7 @@ impl Shape {
                 builder.add_rounded_rectangle(
                     &lyon::math::Box2D {
                         min: Point2D::new(rect.left, rect.top),
-                        max: Point2D::new(rect.width, rect.height),
+                        max: Point2D::new(rect.left + rect.width, rect.top + rect.height),
                     },
                     &BorderRadii {
                         top_left: top_left.max(f32::EPSILON), // Lyon sucks ass
@@ -102,8 +102,8 @@ impl Shape {
                 let mut builder = Path::builder();
 
                 builder.add_ellipse(
-                    Point2D::new(rect.left + rect.width, rect.top + rect.height),
-                    Vector::new(rect.width, rect.height),
+                    Point2D::new(rect.left + rect.width / 2.0, rect.top + rect.height / 2.0),
+                    Vector::new(rect.width / 2.0, rect.height / 2.0),
                     Angle::radians(0.0),
                     Winding::Positive,
                 );
@@ -115,3 +115,58 @@ impl Shape {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use lyon::path::{math::Point, Event};
+
+    use crate::unit::Rect;
+
+    use super::Shape;
+
+    fn bounds(shape: &Shape, rect: Rect) -> (Point, Point) {
+        let mut min = Point::new(f32::INFINITY, f32::INFINITY);
+        let mut max = Point::new(f32::NEG_INFINITY, f32::NEG_INFINITY);
+
+        for event in shape.build_path(rect).iter() {
+            let point = match event {
+                Event::Begin { at } => at,
+                Event::Line { to, .. } | Event::Quadratic { to, .. } | Event::Cubic { to, .. } => {
+                    to
+                }
+                Event::End { .. } => continue,
+            };
+
+            min = min.min(point);
+            max = max.max(point);
+        }
+
+        (min, max)
+    }
+
+    #[test]
+    pub fn builds_paths_within_the_rect() {
+        let rect = Rect::new(10.0, 20.0, 30.0, 40.0);
+
+        for shape in [
+            Shape::Rect,
+            Shape::RoundedRect {
+                top_left: 5.0,
+                top_right: 5.0,
+                bottom_right: 5.0,
+                bottom_left: 5.0,
+            },
+            Shape::Circle,
+        ] {
+            let (min, max) = bounds(&shape, rect);
+
+            assert!(
+                (min.x - 10.0).abs() < 0.01
+                    && (min.y - 20.0).abs() < 0.01
+                    && (max.x - 40.0).abs() < 0.01
+                    && (max.y - 60.0).abs() < 0.01,
+                "{shape:?} should have filled the rect, got {min:?} to {max:?}"
+            );
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use std::{
+    any::TypeId,
     cell::RefCell,
     hash::{Hash, Hasher},
     rc::Rc,
@@ -44,6 +45,11 @@ impl Widget {
         self.key
     }
 
+    /// Returns the `TypeId` of the underlying widget.
+    pub fn widget_type_id(&self) -> TypeId {
+        (*Rc::clone(&self.widget).as_any()).type_id()
+    }
+
     pub fn downcast<W>(&self) -> Option<Rc<W>>
     where
         W: AnyWidget,
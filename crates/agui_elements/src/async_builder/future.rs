@@ -0,0 +1,273 @@
+use std::{future::Future, rc::Rc};
+
+use agui_core::{
+    element::{ElementBuilder, ElementType},
+    task::TaskId,
+    widget::{IntoWidget, Widget},
+};
+use futures::{future::LocalBoxFuture, FutureExt};
+
+use crate::stateful::{StatefulBuildContext, StatefulElement, StatefulWidget, WidgetState};
+
+use super::{AsyncSnapshot, ConnectionState};
+
+/// Runs a future on the engine's executor, building its child from the future's most recent
+/// [`AsyncSnapshot`].
+///
+/// A future is created and started each time an element is created from the widget, and is
+/// cancelled if that element is removed from the tree. If the widget is replaced by a new
+/// `FutureBuilder`, the previous future is cancelled in favor of one created by the new widget.
+pub struct FutureBuilder<T> {
+    #[allow(clippy::type_complexity)]
+    future: Rc<dyn Fn() -> LocalBoxFuture<'static, T>>,
+
+    #[allow(clippy::type_complexity)]
+    pub builder: Box<dyn Fn(&AsyncSnapshot<T>) -> Widget>,
+}
+
+impl<T> FutureBuilder<T>
+where
+    T: 'static,
+{
+    /// Creates a builder that runs the future returned by `future`, which may be called more
+    /// than once if the widget is used to create multiple elements.
+    pub fn new<Fut, F>(future: impl Fn() -> Fut + 'static, builder: F) -> Self
+    where
+        Fut: Future<Output = T> + 'static,
+        F: Fn(&AsyncSnapshot<T>) -> Widget + 'static,
+    {
+        Self {
+            future: Rc::new(move || future().boxed_local()),
+
+            builder: Box::new(builder),
+        }
+    }
+}
+
+impl<T> StatefulWidget for FutureBuilder<T>
+where
+    T: 'static,
+{
+    type State = FutureBuilderState<T>;
+
+    fn create_state(&self) -> Self::State {
+        FutureBuilderState {
+            snapshot: AsyncSnapshot::waiting(),
+
+            task_id: None,
+        }
+    }
+}
+
+impl<T> IntoWidget for FutureBuilder<T>
+where
+    T: 'static,
+{
+    fn into_widget(self) -> Widget {
+        Widget::new(self)
+    }
+}
+
+impl<T> ElementBuilder for FutureBuilder<T>
+where
+    T: 'static,
+{
+    fn create_element(self: Rc<Self>) -> ElementType {
+        ElementType::Widget(Box::new(StatefulElement::new(self)))
+    }
+}
+
+impl<T> std::fmt::Debug for FutureBuilder<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FutureBuilder").finish_non_exhaustive()
+    }
+}
+
+pub struct FutureBuilderState<T> {
+    snapshot: AsyncSnapshot<T>,
+
+    task_id: Option<TaskId>,
+}
+
+impl<T> FutureBuilderState<T>
+where
+    T: 'static,
+{
+    fn subscribe(&mut self, ctx: &mut StatefulBuildContext<Self>) {
+        let future = (ctx.widget.future)();
+
+        if let Some(task_id) = self.task_id.take() {
+            ctx.cancel_task(task_id);
+        }
+
+        self.snapshot.restart();
+
+        self.task_id = Some(ctx.spawn(future, |state: &mut Self, data| {
+            state.snapshot = AsyncSnapshot {
+                state: ConnectionState::Done,
+                data: Some(data),
+            };
+
+            state.task_id = None;
+        }));
+    }
+}
+
+impl<T> WidgetState for FutureBuilderState<T>
+where
+    T: 'static,
+{
+    type Widget = FutureBuilder<T>;
+
+    fn init_state(&mut self, ctx: &mut StatefulBuildContext<Self>) {
+        self.subscribe(ctx);
+    }
+
+    fn updated(&mut self, ctx: &mut StatefulBuildContext<Self>, old_widget: &Self::Widget) {
+        // Widgets cloned from the same `FutureBuilder` share their future, so there's no need to
+        // restart it.
+        if !Rc::ptr_eq(&old_widget.future, &ctx.widget.future) {
+            self.subscribe(ctx);
+        }
+    }
+
+    fn build(&mut self, ctx: &mut StatefulBuildContext<Self>) -> Widget {
+        (ctx.widget.builder)(&self.snapshot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use agui_core::{
+        element::mock::{build::MockBuildWidget, DummyWidget},
+        engine::Engine,
+        widget::{IntoWidget, Widget},
+    };
+    use futures::{channel::oneshot, FutureExt};
+
+    use crate::async_builder::{AsyncSnapshot, ConnectionState};
+
+    use super::FutureBuilder;
+
+    /// Creates a `FutureBuilder` that records each snapshot it's built with. Each time its future
+    /// is started, the sender used to complete it is pushed to `senders`.
+    fn future_builder(
+        senders: &Rc<RefCell<Vec<oneshot::Sender<usize>>>>,
+        snapshots: &Rc<RefCell<Vec<AsyncSnapshot<usize>>>>,
+    ) -> Widget {
+        let senders = Rc::clone(senders);
+        let snapshots = Rc::clone(snapshots);
+
+        FutureBuilder::new(
+            move || {
+                let (tx, rx) = oneshot::channel();
+
+                senders.borrow_mut().push(tx);
+
+                rx.map(|result| result.unwrap_or_default())
+            },
+            move |snapshot| {
+                snapshots.borrow_mut().push(snapshot.clone());
+
+                DummyWidget.into_widget()
+            },
+        )
+        .into_widget()
+    }
+
+    #[test]
+    pub fn builds_from_the_result_of_the_future() {
+        let senders = Rc::default();
+        let snapshots = Rc::default();
+
+        let mut engine = Engine::builder()
+            .with_root(future_builder(&senders, &snapshots))
+            .build();
+
+        engine.update().unwrap();
+
+        assert_eq!(*snapshots.borrow(), [AsyncSnapshot::waiting()]);
+
+        senders.borrow_mut().remove(0).send(7).unwrap();
+
+        engine.update().unwrap();
+
+        assert_eq!(
+            *snapshots.borrow(),
+            [
+                AsyncSnapshot::waiting(),
+                AsyncSnapshot {
+                    state: ConnectionState::Done,
+                    data: Some(7),
+                }
+            ]
+        );
+    }
+
+    #[test]
+    pub fn restarts_the_future_for_each_element() {
+        let senders = Rc::<RefCell<Vec<oneshot::Sender<usize>>>>::default();
+        let snapshots = Rc::default();
+
+        let builder = future_builder(&senders, &snapshots);
+
+        let child = Rc::new(RefCell::new(builder.clone()));
+
+        let root_widget = MockBuildWidget::default();
+        {
+            root_widget.mock.borrow_mut().expect_build().returning_st({
+                let child = Rc::clone(&child);
+
+                move |_| child.borrow().clone()
+            });
+        }
+
+        let mut engine = Engine::builder().with_root(root_widget).build();
+
+        engine.update().unwrap();
+
+        engine.mark_dirty(engine.root());
+        engine.update().unwrap();
+
+        assert_eq!(
+            senders.borrow().len(),
+            1,
+            "rebuilding with the same widget should not restart the future"
+        );
+
+        *child.borrow_mut() = DummyWidget.into_widget();
+
+        engine.mark_dirty(engine.root());
+        engine.update().unwrap();
+
+        assert!(
+            senders.borrow()[0].is_canceled(),
+            "future should be cancelled once its element is removed"
+        );
+
+        *child.borrow_mut() = builder;
+
+        engine.mark_dirty(engine.root());
+        engine.update().unwrap();
+
+        assert_eq!(
+            senders.borrow().len(),
+            2,
+            "a new element created from the same widget should start its own future"
+        );
+
+        senders.borrow_mut().remove(1).send(3).unwrap();
+
+        engine.update().unwrap();
+
+        assert_eq!(
+            snapshots.borrow().last(),
+            Some(&AsyncSnapshot {
+                state: ConnectionState::Done,
+                data: Some(3),
+            })
+        );
+    }
+}
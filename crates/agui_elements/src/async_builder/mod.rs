@@ -0,0 +1,53 @@
+mod future;
+mod stream;
+
+pub use future::*;
+pub use stream::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// Waiting for the first value to arrive.
+    Waiting,
+
+    /// At least one value has arrived, and more may follow.
+    Active,
+
+    /// No more values will arrive.
+    Done,
+}
+
+/// The most recent state of an asynchronous computation, passed to the builder of a
+/// [`FutureBuilder`] or [`StreamBuilder`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AsyncSnapshot<T> {
+    pub state: ConnectionState,
+
+    /// The most recent value, if any. This is retained when the computation is restarted, so a
+    /// widget may continue to show stale data while it waits for the new value.
+    pub data: Option<T>,
+}
+
+impl<T> AsyncSnapshot<T> {
+    pub fn waiting() -> Self {
+        Self {
+            state: ConnectionState::Waiting,
+            data: None,
+        }
+    }
+
+    pub fn is_waiting(&self) -> bool {
+        self.state == ConnectionState::Waiting
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.state == ConnectionState::Done
+    }
+
+    pub fn has_data(&self) -> bool {
+        self.data.is_some()
+    }
+
+    fn restart(&mut self) {
+        self.state = ConnectionState::Waiting;
+    }
+}
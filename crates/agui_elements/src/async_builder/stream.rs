@@ -0,0 +1,284 @@
+use std::rc::Rc;
+
+use agui_core::{
+    element::{ElementBuilder, ElementType},
+    task::TaskId,
+    widget::{IntoWidget, Widget},
+};
+use futures::{stream::LocalBoxStream, Stream, StreamExt};
+
+use crate::stateful::{StatefulBuildContext, StatefulElement, StatefulWidget, WidgetState};
+
+use super::{AsyncSnapshot, ConnectionState};
+
+/// Drives a stream on the engine's executor, rebuilding its child from an [`AsyncSnapshot`] of
+/// the most recent item each time one arrives.
+///
+/// A stream is created each time an element is created from the widget, and is polled until it
+/// is exhausted or that element is removed from the tree. If the widget is replaced by a new
+/// `StreamBuilder`, the previous stream is dropped in favor of one created by the new widget.
+pub struct StreamBuilder<T> {
+    #[allow(clippy::type_complexity)]
+    stream: Rc<dyn Fn() -> LocalBoxStream<'static, T>>,
+
+    #[allow(clippy::type_complexity)]
+    pub builder: Box<dyn Fn(&AsyncSnapshot<T>) -> Widget>,
+}
+
+impl<T> StreamBuilder<T>
+where
+    T: 'static,
+{
+    /// Creates a builder that drives the stream returned by `stream`, which may be called more
+    /// than once if the widget is used to create multiple elements.
+    pub fn new<St, F>(stream: impl Fn() -> St + 'static, builder: F) -> Self
+    where
+        St: Stream<Item = T> + 'static,
+        F: Fn(&AsyncSnapshot<T>) -> Widget + 'static,
+    {
+        Self {
+            stream: Rc::new(move || stream().boxed_local()),
+
+            builder: Box::new(builder),
+        }
+    }
+}
+
+impl<T> StatefulWidget for StreamBuilder<T>
+where
+    T: 'static,
+{
+    type State = StreamBuilderState<T>;
+
+    fn create_state(&self) -> Self::State {
+        StreamBuilderState {
+            snapshot: AsyncSnapshot::waiting(),
+
+            task_id: None,
+        }
+    }
+}
+
+impl<T> IntoWidget for StreamBuilder<T>
+where
+    T: 'static,
+{
+    fn into_widget(self) -> Widget {
+        Widget::new(self)
+    }
+}
+
+impl<T> ElementBuilder for StreamBuilder<T>
+where
+    T: 'static,
+{
+    fn create_element(self: Rc<Self>) -> ElementType {
+        ElementType::Widget(Box::new(StatefulElement::new(self)))
+    }
+}
+
+impl<T> std::fmt::Debug for StreamBuilder<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamBuilder").finish_non_exhaustive()
+    }
+}
+
+pub struct StreamBuilderState<T> {
+    snapshot: AsyncSnapshot<T>,
+
+    task_id: Option<TaskId>,
+}
+
+impl<T> StreamBuilderState<T>
+where
+    T: 'static,
+{
+    fn subscribe(&mut self, ctx: &mut StatefulBuildContext<Self>) {
+        let stream = (ctx.widget.stream)();
+
+        if let Some(task_id) = self.task_id.take() {
+            ctx.cancel_task(task_id);
+        }
+
+        self.snapshot.restart();
+
+        self.task_id = Some(ctx.spawn_stream(
+            stream,
+            |state: &mut Self, data| {
+                state.snapshot = AsyncSnapshot {
+                    state: ConnectionState::Active,
+                    data: Some(data),
+                };
+            },
+            |state: &mut Self| {
+                state.snapshot.state = ConnectionState::Done;
+
+                state.task_id = None;
+            },
+        ));
+    }
+}
+
+impl<T> WidgetState for StreamBuilderState<T>
+where
+    T: 'static,
+{
+    type Widget = StreamBuilder<T>;
+
+    fn init_state(&mut self, ctx: &mut StatefulBuildContext<Self>) {
+        self.subscribe(ctx);
+    }
+
+    fn updated(&mut self, ctx: &mut StatefulBuildContext<Self>, old_widget: &Self::Widget) {
+        // Widgets cloned from the same `StreamBuilder` share their stream, so there's no need to
+        // restart it.
+        if !Rc::ptr_eq(&old_widget.stream, &ctx.widget.stream) {
+            self.subscribe(ctx);
+        }
+    }
+
+    fn build(&mut self, ctx: &mut StatefulBuildContext<Self>) -> Widget {
+        (ctx.widget.builder)(&self.snapshot)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use agui_core::{
+        element::mock::{build::MockBuildWidget, DummyWidget},
+        engine::Engine,
+        widget::{IntoWidget, Widget},
+    };
+    use futures::channel::mpsc;
+
+    use crate::async_builder::{AsyncSnapshot, ConnectionState};
+
+    use super::StreamBuilder;
+
+    /// Creates a `StreamBuilder` that records each snapshot it's built with. Each time its stream
+    /// is started, the sender used to feed it is pushed to `senders`.
+    fn stream_builder(
+        senders: &Rc<RefCell<Vec<mpsc::UnboundedSender<usize>>>>,
+        snapshots: &Rc<RefCell<Vec<AsyncSnapshot<usize>>>>,
+    ) -> Widget {
+        let senders = Rc::clone(senders);
+        let snapshots = Rc::clone(snapshots);
+
+        StreamBuilder::new(
+            move || {
+                let (tx, rx) = mpsc::unbounded();
+
+                senders.borrow_mut().push(tx);
+
+                rx
+            },
+            move |snapshot| {
+                snapshots.borrow_mut().push(snapshot.clone());
+
+                DummyWidget.into_widget()
+            },
+        )
+        .into_widget()
+    }
+
+    #[test]
+    pub fn builds_from_each_item_of_the_stream() {
+        let senders = Rc::<RefCell<Vec<mpsc::UnboundedSender<usize>>>>::default();
+        let snapshots = Rc::default();
+
+        let mut engine = Engine::builder()
+            .with_root(stream_builder(&senders, &snapshots))
+            .build();
+
+        engine.update().unwrap();
+
+        senders.borrow()[0].unbounded_send(1).unwrap();
+
+        engine.update().unwrap();
+
+        senders.borrow()[0].unbounded_send(2).unwrap();
+
+        engine.update().unwrap();
+
+        senders.borrow_mut().clear();
+
+        engine.update().unwrap();
+
+        assert_eq!(
+            *snapshots.borrow(),
+            [
+                AsyncSnapshot::waiting(),
+                AsyncSnapshot {
+                    state: ConnectionState::Active,
+                    data: Some(1),
+                },
+                AsyncSnapshot {
+                    state: ConnectionState::Active,
+                    data: Some(2),
+                },
+                AsyncSnapshot {
+                    state: ConnectionState::Done,
+                    data: Some(2),
+                }
+            ]
+        );
+    }
+
+    #[test]
+    pub fn restarts_the_stream_for_each_element() {
+        let senders = Rc::<RefCell<Vec<mpsc::UnboundedSender<usize>>>>::default();
+        let snapshots = Rc::default();
+
+        let builder = stream_builder(&senders, &snapshots);
+
+        let child = Rc::new(RefCell::new(builder.clone()));
+
+        let root_widget = MockBuildWidget::default();
+        {
+            root_widget.mock.borrow_mut().expect_build().returning_st({
+                let child = Rc::clone(&child);
+
+                move |_| child.borrow().clone()
+            });
+        }
+
+        let mut engine = Engine::builder().with_root(root_widget).build();
+
+        engine.update().unwrap();
+
+        *child.borrow_mut() = DummyWidget.into_widget();
+
+        engine.mark_dirty(engine.root());
+        engine.update().unwrap();
+
+        assert!(
+            senders.borrow()[0].is_closed(),
+            "stream should be dropped once its element is removed"
+        );
+
+        *child.borrow_mut() = builder;
+
+        engine.mark_dirty(engine.root());
+        engine.update().unwrap();
+
+        assert_eq!(
+            senders.borrow().len(),
+            2,
+            "a new element created from the same widget should start its own stream"
+        );
+
+        senders.borrow()[1].unbounded_send(4).unwrap();
+
+        engine.update().unwrap();
+
+        assert_eq!(
+            snapshots.borrow().last(),
+            Some(&AsyncSnapshot {
+                state: ConnectionState::Active,
+                data: Some(4),
+            })
+        );
+    }
+}
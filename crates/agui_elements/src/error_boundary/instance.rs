@@ -0,0 +1,89 @@
+use std::{any::Any, rc::Rc};
+
+use agui_core::{
+    callback::CallbackId,
+    element::{
+        build::ElementBuild, widget::ElementWidget, ElementBuildContext, ElementCallbackContext,
+        ElementPanic, ElementUpdate,
+    },
+    widget::{AnyWidget, Widget},
+};
+
+use super::ErrorBoundary;
+
+pub struct ErrorBoundaryElement {
+    widget: Rc<ErrorBoundary>,
+
+    panic: Option<ElementPanic>,
+}
+
+impl ErrorBoundaryElement {
+    pub fn new(widget: Rc<ErrorBoundary>) -> Self {
+        Self {
+            widget,
+
+            panic: None,
+        }
+    }
+
+    /// The panic that caused the fallback widget to be built, if any.
+    pub fn panic(&self) -> Option<&ElementPanic> {
+        self.panic.as_ref()
+    }
+}
+
+impl ElementWidget for ErrorBoundaryElement {
+    fn widget_name(&self) -> &'static str {
+        self.widget.widget_name()
+    }
+
+    fn update(&mut self, new_widget: &Widget) -> ElementUpdate {
+        if let Some(new_widget) = new_widget.downcast::<ErrorBoundary>() {
+            self.widget = new_widget;
+
+            // Give the new child a chance to build successfully.
+            self.panic = None;
+
+            ElementUpdate::RebuildNecessary
+        } else {
+            ElementUpdate::Invalid
+        }
+    }
+}
+
+impl ElementBuild for ErrorBoundaryElement {
+    fn build(&mut self, _: ElementBuildContext) -> Widget {
+        match self.panic {
+            Some(ref panic) => (self.widget.fallback)(panic),
+            None => self.widget.child.clone(),
+        }
+    }
+
+    fn call(&mut self, _: ElementCallbackContext, _: CallbackId, _: Box<dyn Any>) -> bool {
+        tracing::warn!("attempted to call a callback on an error boundary");
+
+        false
+    }
+
+    fn on_descendant_panic(&mut self, panic: &ElementPanic) -> bool {
+        // If the fallback panicked, there's nothing more this boundary can do.
+        if self.panic.is_some() {
+            return false;
+        }
+
+        self.panic = Some(panic.clone());
+
+        true
+    }
+}
+
+impl std::fmt::Debug for ErrorBoundaryElement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut dbg = f.debug_struct("ErrorBoundaryElement");
+
+        dbg.field("widget", &self.widget);
+        dbg.field("panic", &self.panic);
+
+        dbg.finish()
+    }
+}
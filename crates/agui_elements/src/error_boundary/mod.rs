@@ -0,0 +1,273 @@
+use std::rc::Rc;
+
+use agui_core::{
+    element::{ElementBuilder, ElementPanic, ElementType},
+    widget::{IntoWidget, Widget},
+};
+
+mod instance;
+
+pub use instance::*;
+
+/// Catches panics raised while any of its descendants are being built or laid out, tearing down
+/// the broken subtree and building a fallback widget in its place.
+///
+/// The fallback remains in place until the boundary is given a new widget, at which point it will
+/// attempt to build its child again. Panics raised by the fallback itself are passed on to the
+/// next error boundary up the tree.
+pub struct ErrorBoundary {
+    pub child: Widget,
+
+    #[allow(clippy::type_complexity)]
+    pub fallback: Box<dyn Fn(&ElementPanic) -> Widget>,
+}
+
+impl ErrorBoundary {
+    pub fn new<F>(child: impl IntoWidget, fallback: F) -> Self
+    where
+        F: Fn(&ElementPanic) -> Widget + 'static,
+    {
+        Self {
+            child: child.into_widget(),
+
+            fallback: Box::new(fallback),
+        }
+    }
+}
+
+impl IntoWidget for ErrorBoundary {
+    fn into_widget(self) -> Widget {
+        Widget::new(self)
+    }
+}
+
+impl ElementBuilder for ErrorBoundary {
+    fn create_element(self: Rc<Self>) -> ElementType {
+        ElementType::Widget(Box::new(ErrorBoundaryElement::new(self)))
+    }
+}
+
+impl std::fmt::Debug for ErrorBoundary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ErrorBoundary")
+            .field("child", &self.child)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use agui_core::{
+        element::{
+            mock::{build::MockBuildWidget, DummyWidget},
+            ElementBuilder, ElementPanic, ElementType,
+        },
+        engine::Engine,
+        unit::{Constraints, IntrinsicDimension, Size},
+        widget::{IntoWidget, Widget},
+    };
+
+    use crate::{
+        layout::{IntrinsicSizeContext, LayoutContext, LayoutElement, WidgetLayout},
+        stateless::{StatelessBuildContext, StatelessElement, StatelessWidget},
+    };
+
+    use super::ErrorBoundary;
+
+    struct FlakyWidget {
+        should_panic: bool,
+    }
+
+    impl StatelessWidget for FlakyWidget {
+        fn build(&self, _: &mut StatelessBuildContext<Self>) -> Widget {
+            if self.should_panic {
+                panic!("failed to build");
+            }
+
+            DummyWidget.into_widget()
+        }
+    }
+
+    impl IntoWidget for FlakyWidget {
+        fn into_widget(self) -> Widget {
+            Widget::new(self)
+        }
+    }
+
+    impl ElementBuilder for FlakyWidget {
+        fn create_element(self: Rc<Self>) -> ElementType {
+            ElementType::Widget(Box::new(StatelessElement::new(self)))
+        }
+    }
+
+    struct FlakyLayout {
+        should_panic: bool,
+    }
+
+    impl WidgetLayout for FlakyLayout {
+        fn children(&self) -> Vec<Widget> {
+            Vec::new()
+        }
+
+        fn intrinsic_size(
+            &self,
+            _: &mut IntrinsicSizeContext,
+            _: IntrinsicDimension,
+            _: f32,
+        ) -> f32 {
+            0.0
+        }
+
+        fn layout(&self, _: &mut LayoutContext, constraints: Constraints) -> Size {
+            if self.should_panic {
+                panic!("failed to lay out");
+            }
+
+            constraints.smallest()
+        }
+    }
+
+    impl IntoWidget for FlakyLayout {
+        fn into_widget(self) -> Widget {
+            Widget::new(self)
+        }
+    }
+
+    impl ElementBuilder for FlakyLayout {
+        fn create_element(self: Rc<Self>) -> ElementType {
+            ElementType::Render(Box::new(LayoutElement::new(self)))
+        }
+    }
+
+    /// Creates a widget that wraps whatever widget `child` holds in an error boundary each time
+    /// it's built. Every panic passed to the boundary's fallback is pushed to `caught`.
+    fn boundary_root(
+        child: &Rc<RefCell<Widget>>,
+        caught: &Rc<RefCell<Vec<ElementPanic>>>,
+    ) -> Widget {
+        let widget = MockBuildWidget::new("RootWidget");
+        {
+            widget.mock.borrow_mut().expect_build().returning_st({
+                let child = Rc::clone(child);
+                let caught = Rc::clone(caught);
+
+                move |_| {
+                    let caught = Rc::clone(&caught);
+
+                    ErrorBoundary::new(child.borrow().clone(), move |panic| {
+                        caught.borrow_mut().push(panic.clone());
+
+                        DummyWidget.into_widget()
+                    })
+                    .into_widget()
+                }
+            });
+        }
+
+        widget.into_widget()
+    }
+
+    /// Returns the name of the widget built by the error boundary beneath the root.
+    fn boundary_child_name(engine: &Engine) -> &str {
+        let boundary_id = engine.elements().get_children(engine.root()).unwrap()[0];
+        let child_id = engine.elements().get_children(boundary_id).unwrap()[0];
+
+        engine.elements().get(child_id).unwrap().widget_name()
+    }
+
+    #[test]
+    pub fn builds_the_fallback_when_a_child_panics_while_building() {
+        let child = Rc::new(RefCell::new(
+            FlakyWidget { should_panic: true }.into_widget(),
+        ));
+        let caught = Rc::default();
+
+        let mut engine = Engine::builder()
+            .with_root(boundary_root(&child, &caught))
+            .build();
+
+        engine.update().unwrap();
+
+        {
+            let caught = caught.borrow();
+
+            assert_eq!(caught.len(), 1, "should have built the fallback once");
+            assert_eq!(caught[0].message, "failed to build");
+            assert_eq!(
+                caught[0].path,
+                vec!["RootWidget", "ErrorBoundary", "FlakyWidget"]
+            );
+        }
+
+        assert_eq!(boundary_child_name(&engine), "DummyWidget");
+
+        *child.borrow_mut() = FlakyWidget {
+            should_panic: false,
+        }
+        .into_widget();
+
+        engine.mark_dirty(engine.root());
+        engine.update().unwrap();
+
+        assert_eq!(
+            boundary_child_name(&engine),
+            "FlakyWidget",
+            "should have built the child again once given a new widget"
+        );
+
+        assert_eq!(caught.borrow().len(), 1, "should not have panicked again");
+    }
+
+    #[test]
+    pub fn builds_the_fallback_when_a_child_panics_during_layout() {
+        let child = Rc::new(RefCell::new(
+            FlakyLayout { should_panic: true }.into_widget(),
+        ));
+        let caught = Rc::default();
+
+        let mut engine = Engine::builder()
+            .with_root(boundary_root(&child, &caught))
+            .build();
+
+        engine.update().unwrap();
+
+        {
+            let caught = caught.borrow();
+
+            assert_eq!(caught.len(), 1, "should have built the fallback once");
+            assert_eq!(caught[0].message, "failed to lay out");
+            assert_eq!(
+                caught[0].path,
+                vec!["RootWidget", "ErrorBoundary", "FlakyLayout"]
+            );
+        }
+
+        assert_eq!(boundary_child_name(&engine), "DummyWidget");
+
+        *child.borrow_mut() = FlakyLayout {
+            should_panic: false,
+        }
+        .into_widget();
+
+        engine.mark_dirty(engine.root());
+        engine.update().unwrap();
+
+        assert_eq!(
+            boundary_child_name(&engine),
+            "FlakyLayout",
+            "should have built the child again once given a new widget"
+        );
+
+        assert_eq!(caught.borrow().len(), 1, "should not have panicked again");
+
+        assert!(
+            engine
+                .render_objects()
+                .iter()
+                .all(|(_, node)| !node.value().needs_layout()),
+            "every render object should have been laid out"
+        );
+    }
+}
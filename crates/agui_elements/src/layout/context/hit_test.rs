@@ -1,7 +1,10 @@
 use std::ops::{Deref, DerefMut};
 
 use agui_core::{
-    element::{ContextElement, Element, ElementId, RenderObjectHitTestContext},
+    render::{
+        ContextRenderObject, ContextRenderObjects, RenderObject, RenderObjectHitTestContext,
+        RenderObjectId,
+    },
     util::tree::Tree,
 };
 
@@ -9,13 +12,15 @@ pub struct HitTestContext<'ctx> {
     pub(crate) inner: &'ctx mut RenderObjectHitTestContext<'ctx>,
 }
 
-impl ContextElement for HitTestContext<'_> {
-    fn elements(&self) -> &Tree<ElementId, Element> {
-        self.inner.elements()
+impl ContextRenderObjects for HitTestContext<'_> {
+    fn render_objects(&self) -> &Tree<RenderObjectId, RenderObject> {
+        self.inner.render_objects()
     }
+}
 
-    fn element_id(&self) -> ElementId {
-        self.inner.element_id()
+impl ContextRenderObject for HitTestContext<'_> {
+    fn render_object_id(&self) -> RenderObjectId {
+        self.inner.render_object_id()
     }
 }
 
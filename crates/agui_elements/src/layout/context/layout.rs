@@ -1,7 +1,10 @@
 use std::ops::{Deref, DerefMut};
 
 use agui_core::{
-    element::{ContextElement, Element, ElementId, RenderObjectLayoutContext},
+    render::{
+        ContextRenderObject, ContextRenderObjects, RenderObject, RenderObjectId,
+        RenderObjectLayoutContext,
+    },
     util::tree::Tree,
 };
 
@@ -9,13 +12,15 @@ pub struct LayoutContext<'ctx> {
     pub(crate) inner: RenderObjectLayoutContext<'ctx>,
 }
 
-impl ContextElement for LayoutContext<'_> {
-    fn elements(&self) -> &Tree<ElementId, Element> {
-        self.inner.elements()
+impl ContextRenderObjects for LayoutContext<'_> {
+    fn render_objects(&self) -> &Tree<RenderObjectId, RenderObject> {
+        self.inner.render_objects()
     }
+}
 
-    fn element_id(&self) -> ElementId {
-        self.inner.element_id()
+impl ContextRenderObject for LayoutContext<'_> {
+    fn render_object_id(&self) -> RenderObjectId {
+        self.inner.render_object_id()
     }
 }
 
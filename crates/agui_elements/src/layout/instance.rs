@@ -1,17 +1,12 @@
 use std::rc::Rc;
 
 use agui_core::{
-    element::{
-        render::ElementRender, widget::ElementWidget, ElementUpdate, RenderObjectHitTestContext,
-        RenderObjectIntrinsicSizeContext, RenderObjectLayoutContext,
-    },
-    unit::{Constraints, HitTest, IntrinsicDimension, Offset, Size},
+    element::{render::ElementRender, widget::ElementWidget, ElementUpdate},
+    render::RenderObject,
     widget::{AnyWidget, Widget},
 };
 
-use super::{HitTestContext, IntrinsicSizeContext, LayoutContext};
-
-use super::WidgetLayout;
+use super::{RenderLayout, WidgetLayout};
 
 pub struct LayoutElement<W>
 where
@@ -56,31 +51,14 @@ where
         self.widget.children()
     }
 
-    fn intrinsic_size(
-        &self,
-        ctx: RenderObjectIntrinsicSizeContext,
-        dimension: IntrinsicDimension,
-        cross_extent: f32,
-    ) -> f32 {
-        self.widget.intrinsic_size(
-            &mut IntrinsicSizeContext { inner: ctx },
-            dimension,
-            cross_extent,
-        )
-    }
-
-    fn layout(&mut self, ctx: RenderObjectLayoutContext, constraints: Constraints) -> Size {
-        self.widget
-            .layout(&mut LayoutContext { inner: ctx }, constraints)
+    fn create_render_object(&self) -> RenderObject {
+        RenderObject::new(RenderLayout::new(Rc::clone(&self.widget)))
     }
 
-    fn hit_test<'ctx>(
-        &self,
-        ctx: &'ctx mut RenderObjectHitTestContext<'ctx>,
-        position: Offset,
-    ) -> HitTest {
-        self.widget
-            .hit_test(&mut HitTestContext { inner: ctx }, position)
+    fn update_render_object(&self, render_object: &mut RenderObject) {
+        if let Some(render_object) = render_object.downcast_mut::<RenderLayout<W>>() {
+            render_object.widget = Rc::clone(&self.widget);
+        }
     }
 }
 
@@ -5,9 +5,11 @@ use agui_core::{
 
 mod context;
 mod instance;
+mod render;
 
 pub use context::*;
 pub use instance::*;
+pub use render::*;
 
 pub trait WidgetLayout: 'static {
     fn children(&self) -> Vec<Widget>;
@@ -28,8 +30,10 @@ pub trait WidgetLayout: 'static {
     /// coordinate space.
     fn hit_test(&self, ctx: &mut HitTestContext, position: Offset) -> HitTest {
         if ctx.size().contains(position) {
-            while let Some(mut child) = ctx.iter_children().next_back() {
-                let offset = position - child.offset();
+            let mut children = ctx.iter_children();
+
+            while let Some(mut child) = children.next_back() {
+                let offset = child.offset();
 
                 if child.hit_test_with_offset(offset, position) == HitTest::Absorb {
                     return HitTest::Absorb;
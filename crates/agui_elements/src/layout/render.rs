@@ -0,0 +1,66 @@
+use std::rc::Rc;
+
+use agui_core::{
+    render::{
+        RenderObjectHitTestContext, RenderObjectImpl, RenderObjectIntrinsicSizeContext,
+        RenderObjectLayoutContext,
+    },
+    unit::{Constraints, HitTest, IntrinsicDimension, Offset, Size},
+    widget::AnyWidget,
+};
+
+use super::{HitTestContext, IntrinsicSizeContext, LayoutContext, WidgetLayout};
+
+/// The render object created by a [`LayoutElement`](super::LayoutElement), which defers its
+/// layout to the widget that it was most recently updated with.
+pub struct RenderLayout<W>
+where
+    W: AnyWidget + WidgetLayout,
+{
+    pub(crate) widget: Rc<W>,
+}
+
+impl<W> RenderLayout<W>
+where
+    W: AnyWidget + WidgetLayout,
+{
+    pub fn new(widget: Rc<W>) -> Self {
+        Self { widget }
+    }
+}
+
+impl<W> RenderObjectImpl for RenderLayout<W>
+where
+    W: AnyWidget + WidgetLayout,
+{
+    fn render_object_name(&self) -> &'static str {
+        self.widget.widget_name()
+    }
+
+    fn intrinsic_size(
+        &self,
+        ctx: RenderObjectIntrinsicSizeContext,
+        dimension: IntrinsicDimension,
+        cross_extent: f32,
+    ) -> f32 {
+        self.widget.intrinsic_size(
+            &mut IntrinsicSizeContext { inner: ctx },
+            dimension,
+            cross_extent,
+        )
+    }
+
+    fn layout(&mut self, ctx: RenderObjectLayoutContext, constraints: Constraints) -> Size {
+        self.widget
+            .layout(&mut LayoutContext { inner: ctx }, constraints)
+    }
+
+    fn hit_test<'ctx>(
+        &self,
+        ctx: &'ctx mut RenderObjectHitTestContext<'ctx>,
+        position: Offset,
+    ) -> HitTest {
+        self.widget
+            .hit_test(&mut HitTestContext { inner: ctx }, position)
+    }
+}
@@ -1,3 +1,5 @@
+pub mod async_builder;
+pub mod error_boundary;
 pub mod layout;
 pub mod paint;
 pub mod stateful;
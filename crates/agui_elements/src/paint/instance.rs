@@ -1,19 +1,12 @@
 use std::rc::Rc;
 
 use agui_core::{
-    element::{
-        render::ElementRender, widget::ElementWidget, ElementUpdate,
-        RenderObjectIntrinsicSizeContext,
-    },
-    render::canvas::{
-        painter::{CanvasPainter, Head},
-        Canvas,
-    },
-    unit::{IntrinsicDimension, Size},
+    element::{render::ElementRender, widget::ElementWidget, ElementUpdate},
+    render::RenderObject,
     widget::Widget,
 };
 
-use super::WidgetPaint;
+use super::{RenderPaint, WidgetPaint};
 
 pub struct PaintElement<W>
 where
@@ -58,35 +51,13 @@ where
         Vec::from_iter(self.widget.child())
     }
 
-    fn intrinsic_size(
-        &self,
-        ctx: RenderObjectIntrinsicSizeContext,
-        dimension: IntrinsicDimension,
-        cross_extent: f32,
-    ) -> f32 {
-        ctx.iter_children().next().map_or(0.0, |child| {
-            child.compute_intrinsic_size(dimension, cross_extent)
-        })
+    fn create_render_object(&self) -> RenderObject {
+        RenderObject::new(RenderPaint::new(Rc::clone(&self.widget)))
     }
 
-    fn paint(&self, size: Size) -> Option<Canvas> {
-        let mut canvas = Canvas {
-            size,
-
-            paints: Vec::default(),
-
-            head: Vec::default(),
-            children: Vec::default(),
-            tail: None,
-        };
-
-        self.widget
-            .paint(CanvasPainter::<Head<()>>::begin(&mut canvas));
-
-        if !canvas.head.is_empty() || !canvas.children.is_empty() || canvas.tail.is_some() {
-            Some(canvas)
-        } else {
-            None
+    fn update_render_object(&self, render_object: &mut RenderObject) {
+        if let Some(render_object) = render_object.downcast_mut::<RenderPaint<W>>() {
+            render_object.widget = Rc::clone(&self.widget);
         }
     }
 }
@@ -1,10 +1,12 @@
 mod instance;
+mod render;
 
 use agui_core::{
     render::CanvasPainter,
     widget::{AnyWidget, Widget},
 };
 pub use instance::*;
+pub use render::*;
 
 pub trait WidgetPaint: AnyWidget {
     fn child(&self) -> Option<Widget> {
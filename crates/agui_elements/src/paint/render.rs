@@ -0,0 +1,81 @@
+use std::rc::Rc;
+
+use agui_core::{
+    render::{
+        canvas::{
+            painter::{CanvasPainter, Head},
+            Canvas,
+        },
+        RenderObjectImpl, RenderObjectIntrinsicSizeContext, RenderObjectLayoutContext,
+    },
+    unit::{Constraints, IntrinsicDimension, Size},
+};
+
+use super::WidgetPaint;
+
+/// The render object created by a [`PaintElement`](super::PaintElement). It takes on the size of
+/// its child, if it has one, and paints using the widget that it was most recently updated with.
+pub struct RenderPaint<W>
+where
+    W: WidgetPaint,
+{
+    pub(crate) widget: Rc<W>,
+}
+
+impl<W> RenderPaint<W>
+where
+    W: WidgetPaint,
+{
+    pub fn new(widget: Rc<W>) -> Self {
+        Self { widget }
+    }
+}
+
+impl<W> RenderObjectImpl for RenderPaint<W>
+where
+    W: WidgetPaint,
+{
+    fn render_object_name(&self) -> &'static str {
+        self.widget.widget_name()
+    }
+
+    fn intrinsic_size(
+        &self,
+        ctx: RenderObjectIntrinsicSizeContext,
+        dimension: IntrinsicDimension,
+        cross_extent: f32,
+    ) -> f32 {
+        ctx.iter_children().next().map_or(0.0, |child| {
+            child.compute_intrinsic_size(dimension, cross_extent)
+        })
+    }
+
+    fn layout(&mut self, mut ctx: RenderObjectLayoutContext, constraints: Constraints) -> Size {
+        ctx.iter_children_mut()
+            .next()
+            .map_or(constraints.smallest(), |mut child| {
+                child.compute_layout(constraints)
+            })
+    }
+
+    fn paint(&self, size: Size) -> Option<Canvas> {
+        let mut canvas = Canvas {
+            size,
+
+            paints: Vec::default(),
+
+            head: Vec::default(),
+            children: Vec::default(),
+            tail: None,
+        };
+
+        self.widget
+            .paint(CanvasPainter::<Head<()>>::begin(&mut canvas));
+
+        if !canvas.head.is_empty() || !canvas.children.is_empty() || canvas.tail.is_some() {
+            Some(canvas)
+        } else {
+            None
+        }
+    }
+}
@@ -1,21 +1,33 @@
-use std::ops::{Deref, DerefMut};
+use std::{
+    future::Future,
+    hash::Hash,
+    ops::{Deref, DerefMut},
+};
 
 use agui_core::{
-    callback::{Callback, CallbackId, CallbackQueue, ContextCallbackQueue, WidgetCallback},
-    element::{ContextElement, ContextMarkDirty, Element, ElementBuildContext, ElementId},
+    callback::{
+        Callback, CallbackId, CallbackQueue, CallbackRequest, ContextCallbackQueue,
+        RequestCallback, WidgetCallback,
+    },
+    element::{
+        ContextElement, ContextElements, ContextMarkDirty, Element, ElementBuildContext, ElementId,
+    },
     plugin::{
         context::{ContextPlugins, ContextPluginsMut},
         Plugins,
     },
+    task::{ContextTaskExecutor, TaskExecutor, TaskId},
     unit::AsAny,
     util::tree::Tree,
 };
+use futures::{Stream, StreamExt};
 use rustc_hash::FxHashMap;
 
 use crate::stateful::WidgetState;
 
 use super::{
     func::{StatefulCallbackFn, StatefulCallbackFunc},
+    task::{StatefulTasks, TaskMessage},
     StatefulCallbackContext,
 };
 
@@ -26,18 +38,24 @@ where
     pub(crate) inner: ElementBuildContext<'ctx>,
 
     pub(crate) callbacks: &'ctx mut FxHashMap<CallbackId, Box<dyn StatefulCallbackFunc<S>>>,
+    pub(crate) tasks: &'ctx mut StatefulTasks<S>,
 
     pub widget: &'ctx S::Widget,
 }
 
-impl<S> ContextElement for StatefulBuildContext<'_, S>
+impl<S> ContextElements for StatefulBuildContext<'_, S>
 where
     S: WidgetState,
 {
     fn elements(&self) -> &Tree<ElementId, Element> {
         self.inner.elements()
     }
+}
 
+impl<S> ContextElement for StatefulBuildContext<'_, S>
+where
+    S: WidgetState,
+{
     fn element_id(&self) -> ElementId {
         self.inner.element_id()
     }
@@ -79,6 +97,15 @@ where
     }
 }
 
+impl<S> ContextTaskExecutor for StatefulBuildContext<'_, S>
+where
+    S: WidgetState,
+{
+    fn task_executor(&self) -> &TaskExecutor {
+        self.inner.task_executor()
+    }
+}
+
 impl<'ctx, S: 'static> Deref for StatefulBuildContext<'ctx, S>
 where
     S: WidgetState,
@@ -119,4 +146,115 @@ where
 
         Callback::Widget(callback)
     }
+
+    /// Creates a callback that is distinct from any other created by this widget with the same
+    /// closure but a different `key`, such as when creating one callback per item in a list.
+    pub fn callback_keyed<A, F>(&mut self, key: impl Hash, func: F) -> Callback<A>
+    where
+        A: AsAny,
+        F: Fn(&mut StatefulCallbackContext<S>, A) + 'static,
+    {
+        let callback =
+            WidgetCallback::new_keyed::<F>(self.element_id(), key, self.callback_queue().clone());
+
+        self.callbacks
+            .insert(callback.id(), Box::new(StatefulCallbackFn::new(func)));
+
+        Callback::Widget(callback)
+    }
+
+    /// Creates a callback whose caller receives the value returned by `func`, either by awaiting
+    /// the response or by blocking on it from another thread.
+    pub fn request_callback<A, R, F>(&mut self, func: F) -> RequestCallback<A, R>
+    where
+        A: Send + 'static,
+        R: Send + 'static,
+        F: Fn(&mut StatefulCallbackContext<S>, A) -> R + 'static,
+    {
+        let callback = RequestCallback::new::<F>(self.element_id(), self.callback_queue().clone());
+
+        self.callbacks.insert(
+            callback.id(),
+            Box::new(StatefulCallbackFn::new(
+                move |ctx: &mut StatefulCallbackContext<S>, request: CallbackRequest<A, R>| {
+                    let (arg, responder) = request.into_parts();
+
+                    responder.respond(func(ctx, arg));
+                },
+            )),
+        );
+
+        callback
+    }
+
+    /// Spawns a future on the engine's executor. Once it completes, `func` is called with its
+    /// output to update the widget's state, after which the widget is rebuilt.
+    ///
+    /// The task is cancelled if the widget is removed from the tree before it completes.
+    pub fn spawn<Fut, F>(&mut self, future: Fut, func: F) -> TaskId
+    where
+        Fut: Future + 'static,
+        Fut::Output: 'static,
+        F: Fn(&mut S, Fut::Output) + 'static,
+    {
+        let callback = self
+            .tasks
+            .callback(self.element_id(), self.callback_queue().clone());
+
+        let callback_id = callback.id();
+
+        let task_id = self.task_executor().spawn(self.element_id(), async move {
+            callback.call(TaskMessage::Next(future.await));
+            callback.call(TaskMessage::Complete);
+        });
+
+        self.tasks
+            .insert(callback_id, task_id, Box::new(func), None);
+
+        task_id
+    }
+
+    /// Spawns a task that drives the stream on the engine's executor, calling `on_next` to update
+    /// the widget's state with each item it yields and `on_complete` once it has been exhausted.
+    ///
+    /// The task is cancelled if the widget is removed from the tree before the stream ends.
+    pub fn spawn_stream<St, F, C>(&mut self, mut stream: St, on_next: F, on_complete: C) -> TaskId
+    where
+        St: Stream + Unpin + 'static,
+        St::Item: 'static,
+        F: Fn(&mut S, St::Item) + 'static,
+        C: Fn(&mut S) + 'static,
+    {
+        let callback = self
+            .tasks
+            .callback(self.element_id(), self.callback_queue().clone());
+
+        let callback_id = callback.id();
+
+        let task_id = self.task_executor().spawn(self.element_id(), async move {
+            while let Some(item) = stream.next().await {
+                callback.call(TaskMessage::Next(item));
+            }
+
+            callback.call(TaskMessage::Complete);
+        });
+
+        self.tasks.insert(
+            callback_id,
+            task_id,
+            Box::new(on_next),
+            Some(Box::new(on_complete)),
+        );
+
+        task_id
+    }
+
+    /// Cancels a task spawned by this widget, discarding any results it has not yet delivered.
+    pub fn cancel_task(&mut self, task_id: TaskId) {
+        self.task_executor().cancel(task_id);
+
+        if let Some(callback_id) = self.tasks.remove(task_id) {
+            self.callback_queue().cancel(callback_id);
+        }
+    }
 }
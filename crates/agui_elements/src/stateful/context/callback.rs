@@ -1,7 +1,11 @@
 use agui_core::element::ElementCallbackContext;
 
 use agui_core::{
-    element::{ContextElement, ContextMarkDirty, Element, ElementId},
+    element::{ContextElement, ContextElements, ContextMarkDirty, Element, ElementId},
+    plugin::{
+        context::{ContextPlugins, ContextPluginsMut},
+        Plugins,
+    },
     util::tree::Tree,
 };
 
@@ -14,11 +18,25 @@ pub struct StatefulCallbackContext<'ctx, S> {
     pub(crate) is_changed: bool,
 }
 
-impl<S> ContextElement for StatefulCallbackContext<'_, S> {
+impl<'ctx, S> ContextPlugins<'ctx> for StatefulCallbackContext<'ctx, S> {
+    fn plugins(&self) -> &Plugins {
+        self.inner.plugins()
+    }
+}
+
+impl<'ctx, S> ContextPluginsMut<'ctx> for StatefulCallbackContext<'ctx, S> {
+    fn plugins_mut(&mut self) -> &mut Plugins {
+        self.inner.plugins_mut()
+    }
+}
+
+impl<S> ContextElements for StatefulCallbackContext<'_, S> {
     fn elements(&self) -> &Tree<ElementId, Element> {
         self.inner.elements()
     }
+}
 
+impl<S> ContextElement for StatefulCallbackContext<'_, S> {
     fn element_id(&self) -> ElementId {
         self.inner.element_id()
     }
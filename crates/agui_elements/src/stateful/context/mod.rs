@@ -1,6 +1,7 @@
 mod build;
 mod callback;
 pub(crate) mod func;
+pub(crate) mod task;
 
 pub use build::*;
 pub use callback::*;
@@ -0,0 +1,155 @@
+use std::any::{Any, TypeId};
+
+use agui_core::{
+    callback::{CallbackId, CallbackQueue, WidgetCallback},
+    element::ElementId,
+    task::TaskId,
+};
+use rustc_hash::FxHashMap;
+
+use super::StatefulCallbackContext;
+
+/// Sent from a spawned task back to the element that owns it.
+pub(crate) enum TaskMessage<T> {
+    Next(T),
+    Complete,
+}
+
+pub(crate) trait StatefulTaskFunc<S> {
+    /// Handles a message from the task, returning `true` once the task has completed.
+    fn call(&self, ctx: &mut StatefulCallbackContext<S>, arg: Box<dyn Any>) -> bool;
+}
+
+pub(crate) struct StatefulTaskFn<S, T> {
+    #[allow(clippy::type_complexity)]
+    on_next: Box<dyn Fn(&mut S, T)>,
+    #[allow(clippy::type_complexity)]
+    on_complete: Option<Box<dyn Fn(&mut S)>>,
+}
+
+impl<S, T> StatefulTaskFunc<S> for StatefulTaskFn<S, T>
+where
+    T: 'static,
+{
+    fn call(&self, ctx: &mut StatefulCallbackContext<S>, arg: Box<dyn Any>) -> bool {
+        let message = arg
+            .downcast::<TaskMessage<T>>()
+            .expect("failed to downcast task message");
+
+        match *message {
+            TaskMessage::Next(value) => {
+                (self.on_next)(ctx.state, value);
+
+                ctx.is_changed = true;
+
+                false
+            }
+
+            TaskMessage::Complete => {
+                if let Some(on_complete) = &self.on_complete {
+                    on_complete(ctx.state);
+
+                    ctx.is_changed = true;
+                }
+
+                true
+            }
+        }
+    }
+}
+
+/// Tracks the handlers for tasks spawned by a stateful element. Unlike callbacks, these outlive
+/// the build that created them and are only removed once their task completes or is cancelled.
+pub(crate) struct StatefulTasks<S: ?Sized> {
+    next_key: u64,
+
+    handlers: FxHashMap<CallbackId, (TaskId, Box<dyn StatefulTaskFunc<S>>)>,
+}
+
+impl<S> Default for StatefulTasks<S> {
+    fn default() -> Self {
+        Self {
+            next_key: 0,
+
+            handlers: FxHashMap::default(),
+        }
+    }
+}
+
+impl<S> StatefulTasks<S>
+where
+    S: 'static,
+{
+    /// Creates the callback a task uses to deliver its messages to the element.
+    pub(crate) fn callback<T>(
+        &mut self,
+        element_id: ElementId,
+        callback_queue: CallbackQueue,
+    ) -> WidgetCallback<TaskMessage<T>>
+    where
+        T: 'static,
+    {
+        self.next_key += 1;
+
+        WidgetCallback::new_keyed_unchecked(
+            element_id,
+            TypeId::of::<TaskMessage<T>>(),
+            Some(self.next_key),
+            callback_queue,
+        )
+    }
+
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn insert<T>(
+        &mut self,
+        callback_id: CallbackId,
+        task_id: TaskId,
+        on_next: Box<dyn Fn(&mut S, T)>,
+        on_complete: Option<Box<dyn Fn(&mut S)>>,
+    ) where
+        T: 'static,
+    {
+        self.handlers.insert(
+            callback_id,
+            (
+                task_id,
+                Box::new(StatefulTaskFn {
+                    on_next,
+                    on_complete,
+                }),
+            ),
+        );
+    }
+
+    /// Removes the handler for the task, returning the ID of the callback it was listening on.
+    pub(crate) fn remove(&mut self, task_id: TaskId) -> Option<CallbackId> {
+        let callback_id = self
+            .handlers
+            .iter()
+            .find(|(_, (id, _))| *id == task_id)
+            .map(|(callback_id, _)| *callback_id)?;
+
+        self.handlers.remove(&callback_id);
+
+        Some(callback_id)
+    }
+
+    /// Delivers a message to the task's handler, returning `false` if no task is listening on
+    /// the callback.
+    pub(crate) fn call(
+        &mut self,
+        ctx: &mut StatefulCallbackContext<S>,
+        callback_id: CallbackId,
+        arg: Box<dyn Any>,
+    ) -> bool {
+        let Some((_, handler)) = self.handlers.get(&callback_id) else {
+            return false;
+        };
+
+        if handler.call(ctx, arg) {
+            self.handlers.remove(&callback_id);
+        }
+
+        true
+    }
+}
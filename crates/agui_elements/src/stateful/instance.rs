@@ -11,8 +11,8 @@ use agui_core::{
 use rustc_hash::FxHashMap;
 
 use super::{
-    func::StatefulCallbackFunc, StatefulBuildContext, StatefulCallbackContext, StatefulWidget,
-    WidgetState,
+    func::StatefulCallbackFunc, task::StatefulTasks, StatefulBuildContext, StatefulCallbackContext,
+    StatefulWidget, WidgetState,
 };
 
 pub struct StatefulElement<W>
@@ -27,6 +27,8 @@ where
     init_callbacks: FxHashMap<CallbackId, Box<dyn StatefulCallbackFunc<W::State>>>,
     build_callbacks: FxHashMap<CallbackId, Box<dyn StatefulCallbackFunc<W::State>>>,
 
+    tasks: StatefulTasks<W::State>,
+
     initialized: bool,
 }
 
@@ -46,6 +48,8 @@ where
             init_callbacks: FxHashMap::default(),
             build_callbacks: FxHashMap::default(),
 
+            tasks: StatefulTasks::default(),
+
             initialized: false,
         }
     }
@@ -62,7 +66,7 @@ where
     fn update(&mut self, new_widget: &Widget) -> ElementUpdate {
         if let Some(new_widget) = new_widget.downcast::<W>() {
             if !Rc::ptr_eq(&self.widget, &new_widget) {
-                self.old_widget = Some(new_widget.clone());
+                self.old_widget = Some(Rc::clone(&self.widget));
             }
 
             self.widget = new_widget;
@@ -73,6 +77,14 @@ where
             ElementUpdate::Invalid
         }
     }
+
+    fn deactivate(&mut self) {
+        self.state.deactivate();
+    }
+
+    fn activate(&mut self) {
+        self.state.activate();
+    }
 }
 
 impl<W> ElementBuild for StatefulElement<W>
@@ -86,6 +98,7 @@ where
             inner: ctx,
 
             callbacks: &mut self.build_callbacks,
+            tasks: &mut self.tasks,
 
             widget: &self.widget,
         };
@@ -114,29 +127,27 @@ where
         callback_id: CallbackId,
         arg: Box<dyn Any>,
     ) -> bool {
+        let mut ctx = StatefulCallbackContext {
+            inner: ctx,
+
+            state: &mut self.state,
+            is_changed: false,
+        };
+
         if let Some(callback) = self
             .build_callbacks
             .get(&callback_id)
             .or_else(|| self.init_callbacks.get(&callback_id))
         {
-            let mut ctx = StatefulCallbackContext {
-                inner: ctx,
-
-                state: &mut self.state,
-                is_changed: false,
-            };
-
             callback.call(&mut ctx, arg);
-
-            ctx.is_changed
-        } else {
+        } else if !self.tasks.call(&mut ctx, callback_id, arg) {
             tracing::warn!(
                 callback_id = format!("{:?}", callback_id).as_str(),
                 "callback not found"
             );
-
-            false
         }
+
+        ctx.is_changed
     }
 }
 
@@ -22,8 +22,169 @@ pub trait WidgetState: 'static {
     #[allow(unused_variables)]
     fn updated(&mut self, ctx: &mut StatefulBuildContext<Self>, old_widget: &Self::Widget) {}
 
+    /// Called when the widget is removed from the tree. If it's reinserted before the end of the
+    /// update, [`WidgetState::activate`] will be called and its state will be retained.
+    fn deactivate(&mut self) {}
+
+    /// Called when the widget is reinserted into the tree after being deactivated.
+    fn activate(&mut self) {}
+
     /// Called whenever this widget is rebuilt.
     ///
     /// This method may be called when any parent is rebuilt or when its internal state changes.
     fn build(&mut self, ctx: &mut StatefulBuildContext<Self>) -> Widget;
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use agui_core::{
+        element::{
+            mock::{build::MockBuildWidget, DummyWidget},
+            ElementBuilder, ElementType,
+        },
+        engine::Engine,
+        widget::{IntoWidget, Widget},
+    };
+    use futures::channel::{mpsc, oneshot};
+
+    use super::{StatefulBuildContext, StatefulElement, StatefulWidget, WidgetState};
+
+    /// Spawns a task when it's first built, and records its state each time it's built.
+    struct SpawningWidget {
+        rx: RefCell<Option<oneshot::Receiver<usize>>>,
+        stream_rx: RefCell<Option<mpsc::UnboundedReceiver<usize>>>,
+
+        built: Rc<RefCell<Vec<(usize, bool)>>>,
+    }
+
+    impl StatefulWidget for SpawningWidget {
+        type State = SpawningState;
+
+        fn create_state(&self) -> Self::State {
+            SpawningState::default()
+        }
+    }
+
+    impl IntoWidget for SpawningWidget {
+        fn into_widget(self) -> Widget {
+            Widget::new(self)
+        }
+    }
+
+    impl ElementBuilder for SpawningWidget {
+        fn create_element(self: Rc<Self>) -> ElementType {
+            ElementType::Widget(Box::new(StatefulElement::new(self)))
+        }
+    }
+
+    #[derive(Default)]
+    struct SpawningState {
+        sum: usize,
+        is_done: bool,
+    }
+
+    impl WidgetState for SpawningState {
+        type Widget = SpawningWidget;
+
+        fn init_state(&mut self, ctx: &mut StatefulBuildContext<Self>) {
+            if let Some(rx) = ctx.widget.rx.borrow_mut().take() {
+                ctx.spawn(rx, |state: &mut Self, value| {
+                    state.sum += value.unwrap_or_default();
+                });
+            }
+
+            if let Some(rx) = ctx.widget.stream_rx.borrow_mut().take() {
+                ctx.spawn_stream(
+                    rx,
+                    |state: &mut Self, value| state.sum += value,
+                    |state: &mut Self| state.is_done = true,
+                );
+            }
+        }
+
+        fn build(&mut self, ctx: &mut StatefulBuildContext<Self>) -> Widget {
+            ctx.widget.built.borrow_mut().push((self.sum, self.is_done));
+
+            DummyWidget.into_widget()
+        }
+    }
+
+    #[test]
+    pub fn spawned_tasks_update_state() {
+        let (tx, rx) = oneshot::channel();
+        let (stream_tx, stream_rx) = mpsc::unbounded();
+
+        let built = Rc::default();
+
+        let mut engine = Engine::builder()
+            .with_root(SpawningWidget {
+                rx: RefCell::new(Some(rx)),
+                stream_rx: RefCell::new(Some(stream_rx)),
+
+                built: Rc::clone(&built),
+            })
+            .build();
+
+        engine.update().unwrap();
+
+        tx.send(1).unwrap();
+
+        engine.update().unwrap();
+
+        stream_tx.unbounded_send(10).unwrap();
+        stream_tx.unbounded_send(100).unwrap();
+
+        engine.update().unwrap();
+
+        drop(stream_tx);
+
+        engine.update().unwrap();
+
+        assert_eq!(
+            built.borrow().last(),
+            Some(&(111, true)),
+            "should have delivered every value to the state"
+        );
+    }
+
+    #[test]
+    pub fn cancels_tasks_when_removed() {
+        let (tx, rx) = oneshot::channel();
+        let (stream_tx, stream_rx) = mpsc::unbounded();
+
+        let built = Rc::default();
+
+        let child = Rc::new(RefCell::new(
+            SpawningWidget {
+                rx: RefCell::new(Some(rx)),
+                stream_rx: RefCell::new(Some(stream_rx)),
+
+                built: Rc::clone(&built),
+            }
+            .into_widget(),
+        ));
+
+        let root_widget = MockBuildWidget::default();
+        {
+            root_widget.mock.borrow_mut().expect_build().returning_st({
+                let child = Rc::clone(&child);
+
+                move |_| child.borrow().clone()
+            });
+        }
+
+        let mut engine = Engine::builder().with_root(root_widget).build();
+
+        engine.update().unwrap();
+
+        *child.borrow_mut() = DummyWidget.into_widget();
+
+        engine.mark_dirty(engine.root());
+        engine.update().unwrap();
+
+        assert!(tx.is_canceled(), "future should have been dropped");
+        assert!(stream_tx.is_closed(), "stream should have been dropped");
+    }
+}
@@ -1,4 +1,7 @@
-use std::ops::{Deref, DerefMut};
+use std::{
+    hash::Hash,
+    ops::{Deref, DerefMut},
+};
 
 use agui_core::{
     callback::{Callback, CallbackId, CallbackQueue, ContextCallbackQueue, WidgetCallback},
@@ -88,4 +91,20 @@ impl<W: 'static> StatelessBuildContext<'_, W> {
 
         Callback::Widget(callback)
     }
+
+    /// Creates a callback that is distinct from any other created by this widget with the same
+    /// closure but a different `key`, such as when creating one callback per item in a list.
+    pub fn callback_keyed<A, F>(&mut self, key: impl Hash, func: F) -> Callback<A>
+    where
+        A: AsAny,
+        F: Fn(&mut StatelessCallbackContext, A) + 'static,
+    {
+        let callback =
+            WidgetCallback::new_keyed::<F>(self.element_id(), key, self.callback_queue().clone());
+
+        self.callbacks
+            .insert(callback.id(), Box::new(StatelessCallbackFn::new(func)));
+
+        Callback::Widget(callback)
+    }
 }
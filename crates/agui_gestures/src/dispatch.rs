@@ -0,0 +1,43 @@
+use agui_core::unit::HitTestResult;
+
+use crate::{
+    event::{KeyEvent, PointerEvent},
+    listener::ListenerHandlers,
+};
+
+/// Sends a pointer event to every [`Listener`](crate::listener::Listener) in the hit test
+/// result, starting with the deepest.
+///
+/// The event's local position is converted into the coordinate space of each listener.
+pub fn dispatch_pointer_event(result: &HitTestResult, event: PointerEvent) {
+    for entry in result.path() {
+        let Some(handlers) = entry
+            .data
+            .as_ref()
+            .and_then(|data| data.downcast_ref::<ListenerHandlers>())
+        else {
+            continue;
+        };
+
+        handlers.on_pointer.call(PointerEvent {
+            local_position: entry.to_local(event.position),
+            ..event
+        });
+    }
+}
+
+/// Sends a key event to every [`Listener`](crate::listener::Listener) in the hit test result,
+/// starting with the deepest.
+pub fn dispatch_key_event(result: &HitTestResult, event: KeyEvent) {
+    for entry in result.path() {
+        let Some(handlers) = entry
+            .data
+            .as_ref()
+            .and_then(|data| data.downcast_ref::<ListenerHandlers>())
+        else {
+            continue;
+        };
+
+        handlers.on_key.call(event);
+    }
+}
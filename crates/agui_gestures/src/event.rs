@@ -0,0 +1,98 @@
+use agui_core::unit::Offset;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PointerEvent {
+    pub kind: PointerEventKind,
+
+    /// The position of the pointer in the global coordinate space.
+    pub position: Offset,
+
+    /// The position of the pointer in the local coordinate space of the widget receiving the
+    /// event.
+    pub local_position: Offset,
+
+    /// How far the pointer has moved since the previous event.
+    pub delta: Offset,
+}
+
+impl PointerEvent {
+    pub fn new(kind: PointerEventKind, position: Offset) -> Self {
+        Self {
+            kind,
+
+            position,
+            local_position: position,
+
+            delta: Offset::ZERO,
+        }
+    }
+
+    pub fn with_delta(mut self, delta: Offset) -> Self {
+        self.delta = delta;
+        self
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PointerEventKind {
+    /// The pointer made contact, such as a mouse button being pressed or a finger touching
+    /// the screen.
+    Down,
+
+    /// The pointer moved while in contact.
+    Move,
+
+    /// The pointer stopped making contact.
+    Up,
+
+    /// The pointer interaction was interrupted and will not complete.
+    Cancel,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyEvent {
+    pub key: Key,
+    pub state: KeyState,
+}
+
+impl KeyEvent {
+    pub const fn pressed(key: Key) -> Self {
+        Self {
+            key,
+            state: KeyState::Pressed,
+        }
+    }
+
+    pub const fn released(key: Key) -> Self {
+        Self {
+            key,
+            state: KeyState::Released,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyState {
+    Pressed,
+    Released,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Key {
+    /// A key that produces a character when typed.
+    Character(char),
+
+    Enter,
+    Tab,
+    Backspace,
+    Delete,
+    Escape,
+
+    ArrowLeft,
+    ArrowRight,
+    ArrowUp,
+    ArrowDown,
+
+    Home,
+    End,
+}
@@ -1 +1,3 @@
-
+pub mod dispatch;
+pub mod event;
+pub mod listener;
@@ -0,0 +1,93 @@
+use agui_core::{
+    callback::Callback,
+    render::ContextRenderObject,
+    unit::{Constraints, HitTest, IntrinsicDimension, Offset, Size},
+    widget::Widget,
+};
+use agui_elements::layout::{HitTestContext, IntrinsicSizeContext, LayoutContext, WidgetLayout};
+use agui_macros::LayoutWidget;
+
+use crate::event::{KeyEvent, PointerEvent};
+
+/// Calls its callbacks whenever an input event is dispatched to a position within its bounds.
+///
+/// A listener absorbs any hit within its bounds, so widgets beneath it will not receive the
+/// event.
+#[derive(LayoutWidget, Debug)]
+pub struct Listener {
+    #[prop(into, default)]
+    pub on_pointer: Callback<PointerEvent>,
+
+    #[prop(into, default)]
+    pub on_key: Callback<KeyEvent>,
+
+    #[prop(into, default)]
+    pub child: Option<Widget>,
+}
+
+/// The data attached to a listener's hit test entry, used to dispatch events to it.
+pub(crate) struct ListenerHandlers {
+    pub on_pointer: Callback<PointerEvent>,
+    pub on_key: Callback<KeyEvent>,
+}
+
+impl WidgetLayout for Listener {
+    fn children(&self) -> Vec<Widget> {
+        Vec::from_iter(self.child.clone())
+    }
+
+    fn intrinsic_size(
+        &self,
+        ctx: &mut IntrinsicSizeContext,
+        dimension: IntrinsicDimension,
+        cross_extent: f32,
+    ) -> f32 {
+        ctx.iter_children()
+            .next()
+            .map(|child| child.compute_intrinsic_size(dimension, cross_extent))
+            .unwrap_or(0.0)
+    }
+
+    fn layout(&self, ctx: &mut LayoutContext, constraints: Constraints) -> Size {
+        let mut size = constraints.smallest();
+
+        let mut children = ctx.iter_children_mut();
+
+        while let Some(mut child) = children.next() {
+            size = child.compute_layout(constraints);
+            child.set_offset(Offset::ZERO);
+        }
+
+        size
+    }
+
+    fn hit_test(&self, ctx: &mut HitTestContext, position: Offset) -> HitTest {
+        if !ctx.size().contains(position) {
+            return HitTest::Pass;
+        }
+
+        let mut children = ctx.iter_children();
+
+        // Descendants are added to the result before the listener, so that events reach the
+        // deepest listener first.
+        while let Some(mut child) = children.next_back() {
+            let offset = child.offset();
+
+            if child.hit_test_with_offset(offset, position) == HitTest::Absorb {
+                break;
+            }
+        }
+
+        let render_object_id = ctx.render_object_id();
+
+        ctx.add_with_data(
+            render_object_id,
+            ListenerHandlers {
+                on_pointer: self.on_pointer.clone(),
+                on_key: self.on_key.clone(),
+            },
+        );
+
+        HitTest::Absorb
+    }
+}
@@ -1,7 +1,11 @@
 use std::rc::Rc;
 
 use agui_core::plugin::context::ContextPlugins;
-use agui_core::{element::ContextElement, plugin::context::ContextPluginsMut, widget::AnyWidget};
+use agui_core::{
+    element::{ContextElement, ContextElements},
+    plugin::context::ContextPluginsMut,
+    widget::AnyWidget,
+};
 
 use crate::{
     element::{InheritedElement, InheritedWidget},
@@ -22,7 +26,7 @@ pub trait ContextInheritedMut {
 
 impl<'ctx, C> ContextInherited for C
 where
-    C: ContextElement + ContextPlugins<'ctx>,
+    C: ContextElement + ContextElements + ContextPlugins<'ctx>,
 {
     fn find_inherited_widget<I>(&self) -> Option<Rc<I>>
     where
@@ -53,7 +57,7 @@ where
 
 impl<'ctx, C> ContextInheritedMut for C
 where
-    C: ContextElement + ContextPluginsMut<'ctx>,
+    C: ContextElement + ContextElements + ContextPluginsMut<'ctx>,
 {
     fn depend_on_inherited_widget<I>(&mut self) -> Option<Rc<I>>
     where
@@ -18,9 +18,11 @@ mod tests {
     use std::{cell::RefCell, rc::Rc};
 
     use agui_core::{
-        element::mock::{build::MockBuildWidget, render::MockRenderWidget, DummyWidget},
+        element::mock::{
+            build::MockBuildWidget, render::MockRenderWidget, DummyRenderObject, DummyWidget,
+        },
         engine::Engine,
-        unit::Size,
+        unit::Key,
         widget::{IntoWidget, Widget},
     };
     use agui_macros::InheritedWidget;
@@ -67,7 +69,7 @@ mod tests {
     pub fn updates_scoped_children() {
         let (root_widget, root_children) = create_widget("RootWidget");
 
-        let (depending_widget, inherited_data) = create_depending_widget("DependingWidget");
+        let (depending_widget, inherited_data) = create_depending_widget("DependingWidget", None);
 
         let mut engine = Engine::builder()
             .add_plugin(InheritancePlugin::default())
@@ -80,7 +82,7 @@ mod tests {
         }
         .into_widget()];
 
-        engine.update();
+        engine.update().unwrap();
 
         assert_eq!(
             *inherited_data.borrow(),
@@ -96,7 +98,7 @@ mod tests {
 
         engine.mark_dirty(engine.root());
 
-        engine.update();
+        engine.update().unwrap();
 
         assert_eq!(
             *inherited_data.borrow(),
@@ -109,7 +111,7 @@ mod tests {
     pub fn updates_nested_scope_children() {
         let (root_widget, root_children) = create_widget("RootWidget");
 
-        let (depending_widget, inherited_data) = create_depending_widget("DependingWidget");
+        let (depending_widget, inherited_data) = create_depending_widget("DependingWidget", None);
 
         let nested_scope = TestOtherInheritedWidget {
             child: depending_widget,
@@ -129,7 +131,7 @@ mod tests {
         }
         .into_widget()];
 
-        engine.update();
+        engine.update().unwrap();
 
         assert_eq!(
             *inherited_data.borrow(),
@@ -145,7 +147,7 @@ mod tests {
 
         engine.mark_dirty(engine.root());
 
-        engine.update();
+        engine.update().unwrap();
 
         assert_eq!(
             *inherited_data.borrow(),
@@ -158,7 +160,7 @@ mod tests {
     pub fn child_updates_when_dependency_unavailable() {
         let (root_widget, root_children) = create_widget("RootWidget");
 
-        let (depending_widget, inherited_data) = create_depending_widget("DependingWidget");
+        let (depending_widget, inherited_data) = create_depending_widget("DependingWidget", None);
 
         let mut engine = Engine::builder()
             .add_plugin(InheritancePlugin::default())
@@ -171,7 +173,7 @@ mod tests {
         }
         .into_widget()];
 
-        engine.update();
+        engine.update().unwrap();
 
         assert_eq!(
             *inherited_data.borrow(),
@@ -183,7 +185,7 @@ mod tests {
 
         engine.mark_dirty(engine.root());
 
-        engine.update();
+        engine.update().unwrap();
 
         assert_eq!(
             *inherited_data.borrow(),
@@ -192,29 +194,145 @@ mod tests {
         );
     }
 
+    #[test]
+    pub fn does_not_update_removed_children() {
+        let (root_widget, root_children) = create_widget("RootWidget");
+
+        let (depending_widget, inherited_data) = create_depending_widget("DependingWidget", None);
+
+        let mut engine = Engine::builder()
+            .add_plugin(InheritancePlugin::default())
+            .with_root(root_widget)
+            .build();
+
+        *root_children.borrow_mut() = vec![TestInheritedWidget {
+            data: 7,
+            child: depending_widget.clone(),
+        }
+        .into_widget()];
+
+        engine.update().unwrap();
+
+        assert_eq!(*inherited_data.borrow(), Some(7));
+
+        // Replacing the child at the same time as the data removes the depending widget, which
+        // must not be rebuilt with the new data on its way out.
+        *root_children.borrow_mut() = vec![TestInheritedWidget {
+            data: 9,
+            child: Widget::new_with_key(Some(Key::local("other")), DummyWidget),
+        }
+        .into_widget()];
+
+        engine.mark_dirty(engine.root());
+
+        engine.update().unwrap();
+
+        assert_eq!(
+            *inherited_data.borrow(),
+            Some(7),
+            "should not have rebuilt the removed child"
+        );
+    }
+
+    #[test]
+    pub fn updates_reparented_children() {
+        let (root_widget, root_children) = create_widget("RootWidget");
+
+        let (first_widget, first_children) = create_widget("FirstWidget");
+        let (second_widget, second_children) = create_widget("SecondWidget");
+
+        let (depending_widget, inherited_data) =
+            create_depending_widget("DependingWidget", Some(Key::global("dependent")));
+
+        *root_children.borrow_mut() = vec![
+            TestInheritedWidget {
+                data: 7,
+                child: first_widget,
+            }
+            .into_widget(),
+            TestInheritedWidget {
+                data: 11,
+                child: TestOtherInheritedWidget {
+                    child: second_widget,
+                }
+                .into_widget(),
+            }
+            .into_widget(),
+        ];
+
+        *first_children.borrow_mut() = vec![depending_widget.clone()];
+
+        let mut engine = Engine::builder()
+            .add_plugin(InheritancePlugin::default())
+            .with_root(root_widget)
+            .build();
+
+        engine.update().unwrap();
+
+        assert_eq!(*inherited_data.borrow(), Some(7));
+
+        let first_child = |element_id| {
+            engine
+                .elements()
+                .get_children(element_id)
+                .and_then(|children| children.first().copied())
+                .expect("no children")
+        };
+
+        let root_children = engine
+            .elements()
+            .get_children(engine.root())
+            .cloned()
+            .expect("no children");
+
+        let first_id = first_child(root_children[0]);
+        let second_id = first_child(first_child(root_children[1]));
+
+        // The first widget is rebuilt before the second, since it's shallower, so the depending
+        // widget is deactivated before it's reclaimed under a different inherited widget.
+        *first_children.borrow_mut() = vec![];
+        *second_children.borrow_mut() = vec![depending_widget];
+
+        engine.mark_dirty(first_id);
+        engine.mark_dirty(second_id);
+
+        engine.update().unwrap();
+
+        assert_eq!(
+            *inherited_data.borrow(),
+            Some(11),
+            "should have rebuilt the child with its new inherited widget"
+        );
+    }
+
     fn create_widget(name: &'static str) -> (Widget, Rc<RefCell<Vec<Widget>>>) {
         let children = Rc::new(RefCell::new(Vec::new()));
 
         let widget = MockRenderWidget::new(name);
         {
-            widget.mock.borrow_mut().children().returning_st({
+            let mut widget_mock = widget.mock.borrow_mut();
+
+            widget_mock.expect_children().returning_st({
                 let children = children.clone();
 
                 move || children.borrow().clone()
             });
 
-            widget
-                .mock
-                .borrow_mut()
-                .expect_layout()
-                .returning(|_, _| Size::ZERO);
+            widget_mock
+                .expect_create_render_object()
+                .returning(|| DummyRenderObject.into());
+
+            widget_mock.expect_update_render_object().returning(|_| {});
         }
         let widget = widget.into_widget();
 
         (widget, children)
     }
 
-    fn create_depending_widget(name: &'static str) -> (Widget, Rc<RefCell<Option<usize>>>) {
+    fn create_depending_widget(
+        name: &'static str,
+        key: Option<Key>,
+    ) -> (Widget, Rc<RefCell<Option<usize>>>) {
         let inherited_data = Rc::new(RefCell::new(None));
 
         let depending_widget = MockBuildWidget::new(name);
@@ -235,7 +353,7 @@ mod tests {
                     }
                 });
         }
-        let depending_widget = depending_widget.into_widget();
+        let depending_widget = Widget::new_with_key(key, depending_widget);
 
         (depending_widget, inherited_data)
     }
@@ -1,7 +1,7 @@
 use std::any::TypeId;
 
 use agui_core::{
-    element::{ContextElement, ContextMarkDirty, ElementId},
+    element::{ContextElements, ContextMarkDirty, ElementId},
     plugin::context::PluginElementRemountContext,
     util::map::{ElementMap, TypeMap, TypeSet},
 };
@@ -389,6 +389,47 @@ impl InheritanceManager {
         );
     }
 
+    /// Stops the node from depending on any inherited elements, so that it's no longer notified
+    /// when they change. Returns `true` if the node had any dependencies.
+    pub(crate) fn remove_dependencies(&mut self, element_id: ElementId) -> bool {
+        let Some(Inheritance::Node(node)) = self.map.get_mut(&element_id) else {
+            return false;
+        };
+
+        let dependencies = node.iter_dependencies().collect::<Vec<_>>();
+
+        if dependencies.is_empty() {
+            return false;
+        }
+
+        for type_id in &dependencies {
+            node.remove_dependency(type_id);
+        }
+
+        let Some(scope) = node
+            .scope()
+            .and_then(|scope_id| self.get_as_scope_mut(scope_id))
+        else {
+            return true;
+        };
+
+        let mut listening_to = Vec::new();
+
+        for type_id in &dependencies {
+            listening_to.extend(scope.available_scopes().get(type_id).copied());
+
+            scope.remove_dependent(type_id, element_id);
+        }
+
+        for dependency_id in listening_to {
+            if let Some(scope) = self.get_as_scope_mut(dependency_id) {
+                scope.remove_listener(element_id);
+            }
+        }
+
+        true
+    }
+
     pub(crate) fn remove(&mut self, element_id: ElementId) {
         if let Some(inheritance) = self.map.remove(&element_id) {
             tracing::trace!(
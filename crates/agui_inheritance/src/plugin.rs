@@ -1,13 +1,15 @@
 use std::any::TypeId;
 
 use agui_core::{
-    element::{ContextElement, ElementId},
+    element::{ContextElement, ContextElements, ContextMarkDirty, ElementId},
     plugin::{
         context::{
-            PluginElementMountContext, PluginElementRemountContext, PluginElementUnmountContext,
+            PluginElementDeactivateContext, PluginElementMountContext, PluginElementRemountContext,
+            PluginElementUnmountContext,
         },
         Plugin,
     },
+    util::map::ElementSet,
 };
 
 use crate::{element::InheritedWidget, manager::InheritanceManager};
@@ -15,6 +17,10 @@ use crate::{element::InheritedWidget, manager::InheritanceManager};
 #[derive(Default)]
 pub struct InheritancePlugin {
     manager: InheritanceManager,
+
+    /// Inactive elements whose dependencies were dropped, which must be rebuilt to depend on
+    /// their inherited elements again if they're remounted.
+    inactive_dependents: ElementSet,
 }
 
 impl Plugin for InheritancePlugin {
@@ -35,9 +41,33 @@ impl Plugin for InheritancePlugin {
 
         self.manager
             .update_inheritance_scope(ctx, element_id, parent_scope_id);
+
+        if self.inactive_dependents.is_empty() {
+            return;
+        }
+
+        let subtree = ctx
+            .elements()
+            .iter_down_from(element_id)
+            .collect::<Vec<_>>();
+
+        for subtree_element_id in subtree {
+            if self.inactive_dependents.remove(&subtree_element_id) {
+                ctx.mark_dirty(subtree_element_id);
+            }
+        }
+    }
+
+    fn on_element_deactivate(&mut self, ctx: &mut PluginElementDeactivateContext) {
+        // Inactive elements must not be rebuilt when their inherited elements change.
+        if self.manager.remove_dependencies(ctx.element_id()) {
+            self.inactive_dependents.insert(ctx.element_id());
+        }
     }
 
     fn on_element_unmount(&mut self, ctx: &mut PluginElementUnmountContext) {
+        self.inactive_dependents.remove(&ctx.element_id());
+
         self.manager.remove(ctx.element_id());
     }
 }
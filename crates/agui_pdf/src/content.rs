@@ -0,0 +1,823 @@
+use std::{collections::BTreeMap, fmt::Write};
+
+use ab_glyph::{Font as _, FontArc, GlyphId};
+use agui_core::{
+    engine::EngineError,
+    render::{
+        canvas::{Canvas, CanvasCommand, CanvasLayer},
+        Paint, RenderObject, RenderObjectId,
+    },
+    unit::{BlendMode, Bounds, Color, Font, Offset, Rect, TextureId},
+    util::tree::Tree,
+};
+use agui_primitives::text::{
+    fonts::FontRegistry,
+    glyph_layout::{layout_text, FontMetrics, PositionedGlyph},
+};
+use lyon::path::{Event, Path};
+
+use crate::textures::PdfTextures;
+
+/// A combination of opacity and blend mode that content is drawn with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GraphicsState {
+    pub alpha: f32,
+    pub blend_mode: &'static str,
+}
+
+/// The fonts, images and graphics states referenced by the content of a document, which are
+/// shared by each of its pages.
+#[derive(Default)]
+pub struct Resources {
+    pub fonts: Vec<FontResource>,
+    pub images: Vec<TextureId>,
+    pub graphics_states: Vec<GraphicsState>,
+}
+
+impl Resources {
+    /// Returns the name of the font, recording the glyphs that are shown with it.
+    fn font_name(&mut self, font: Font, glyphs: &[PositionedGlyph<GlyphId>]) -> String {
+        let idx = self
+            .fonts
+            .iter()
+            .position(|resource| resource.font == font)
+            .unwrap_or_else(|| {
+                self.fonts.push(FontResource {
+                    font,
+                    glyphs: BTreeMap::new(),
+                });
+
+                self.fonts.len() - 1
+            });
+
+        for glyph in glyphs {
+            self.fonts[idx].glyphs.entry(glyph.id.0).or_insert(glyph.ch);
+        }
+
+        format!("/F{idx}")
+    }
+
+    fn image_name(&mut self, texture_id: TextureId) -> String {
+        format!("/Im{}", index_of(&mut self.images, texture_id))
+    }
+
+    fn graphics_state_name(&mut self, graphics_state: GraphicsState) -> String {
+        format!("/GS{}", index_of(&mut self.graphics_states, graphics_state))
+    }
+}
+
+/// A font referenced by the content of a document, along with the glyphs that are shown with it
+/// and the characters that each of them represents.
+pub struct FontResource {
+    pub font: Font,
+    pub glyphs: BTreeMap<u16, char>,
+}
+
+fn index_of<T: PartialEq>(items: &mut Vec<T>, item: T) -> usize {
+    items
+        .iter()
+        .position(|existing| *existing == item)
+        .unwrap_or_else(|| {
+            items.push(item);
+
+            items.len() - 1
+        })
+}
+
+/// Converts canvases into the content stream of a page, in a coordinate space that points
+/// downwards like the canvas does.
+pub struct ContentWriter<'c> {
+    fonts: &'c FontRegistry,
+    textures: &'c PdfTextures,
+
+    resources: &'c mut Resources,
+
+    /// The area of the document that is shown on the page. Anything outside of it is skipped,
+    /// so that it doesn't end up in the page's text.
+    visible: Rect,
+
+    content: String,
+
+    /// The opacity and blend mode of the layers currently being drawn into. Since layers aren't
+    /// composited separately, these are applied to each command within them instead.
+    opacity: f32,
+    blend_mode: BlendMode,
+}
+
+impl<'c> ContentWriter<'c> {
+    pub fn new(
+        fonts: &'c FontRegistry,
+        textures: &'c PdfTextures,
+        resources: &'c mut Resources,
+        visible: Rect,
+    ) -> Self {
+        Self {
+            fonts,
+            textures,
+
+            resources,
+
+            visible,
+
+            content: String::new(),
+
+            opacity: 1.0,
+            blend_mode: BlendMode::SourceOver,
+        }
+    }
+
+    pub fn finish(self) -> String {
+        self.content
+    }
+
+    /// Writes a render object and all of its descendants.
+    ///
+    /// Descendants are written into the tail layer of their ancestors' canvases, so that they're
+    /// clipped along with it.
+    pub fn write_render_object(
+        &mut self,
+        render_objects: &Tree<RenderObjectId, RenderObject>,
+        render_object_id: RenderObjectId,
+        origin: Offset,
+    ) -> Result<(), EngineError> {
+        let render_object = render_objects
+            .get(render_object_id)
+            .ok_or(EngineError::MissingRenderObject { render_object_id })?;
+
+        let offset = render_object.offset();
+
+        let origin = origin + offset;
+
+        self.push_translation(offset);
+
+        let mut write_children = |writer: &mut Self| {
+            for child_id in render_objects
+                .get_children(render_object_id)
+                .into_iter()
+                .flatten()
+            {
+                writer.write_render_object(render_objects, *child_id, origin)?;
+            }
+
+            Ok(())
+        };
+
+        match render_object.paint()? {
+            Some(canvas) => self.write_canvas(&canvas, origin, &mut write_children)?,
+            None => write_children(self)?,
+        }
+
+        self.pop_translation(offset);
+
+        Ok(())
+    }
+
+    /// Writes a canvas, followed by the `contents` within its tail layer.
+    ///
+    /// The origin is the position of the canvas within the document.
+    pub fn write_canvas<E>(
+        &mut self,
+        canvas: &Canvas,
+        origin: Offset,
+        contents: &mut dyn FnMut(&mut Self) -> Result<(), E>,
+    ) -> Result<(), E> {
+        for command in &canvas.head {
+            self.write_command(&canvas.paints, command, origin);
+        }
+
+        for layer in &canvas.children {
+            self.write_layer(&canvas.paints, layer, origin, &mut |_| Ok(()))?;
+        }
+
+        match &canvas.tail {
+            Some(tail) => self.write_layer(&canvas.paints, tail, origin, contents),
+            None => contents(self),
+        }
+    }
+
+    /// Writes a layer clipped to the shape of its style.
+    fn write_layer<E>(
+        &mut self,
+        paints: &[Paint],
+        layer: &CanvasLayer,
+        origin: Offset,
+        contents: &mut dyn FnMut(&mut Self) -> Result<(), E>,
+    ) -> Result<(), E> {
+        let paint = paints
+            .get(layer.style.paint_idx)
+            .cloned()
+            .unwrap_or_else(|| {
+                tracing::warn!(
+                    paint_idx = layer.style.paint_idx,
+                    "layer references a paint that does not exist"
+                );
+
+                Paint::default()
+            });
+
+        let origin = origin + layer.offset;
+
+        self.content.push_str("q\n");
+
+        self.translate(layer.offset);
+
+        let clip_rect = self.bound_rect(Rect::from(layer.canvas.size), origin);
+
+        write_path(&mut self.content, &layer.style.shape.build_path(clip_rect));
+
+        self.content.push_str("W n\n");
+
+        let (opacity, blend_mode) = (self.opacity, self.blend_mode);
+
+        self.opacity *= paint.color.alpha.clamp(0.0, 1.0);
+
+        if paint.blend_mode != BlendMode::SourceOver {
+            self.blend_mode = paint.blend_mode;
+        }
+
+        let result = self.write_canvas(&layer.canvas, origin, contents);
+
+        self.opacity = opacity;
+        self.blend_mode = blend_mode;
+
+        self.content.push_str("Q\n");
+
+        result
+    }
+
+    fn write_command(&mut self, paints: &[Paint], command: &CanvasCommand, origin: Offset) {
+        if command.is_noop() {
+            return;
+        }
+
+        match command {
+            CanvasCommand::Shape {
+                paint_idx,
+                rect,
+                shape,
+            } => {
+                let Some(paint) = get_paint(paints, *paint_idx) else {
+                    return;
+                };
+
+                let rect = self.bound_rect(*rect, origin);
+
+                if !self.is_visible(rect, origin) {
+                    return;
+                }
+
+                self.content.push_str("q\n");
+
+                self.set_fill(paint);
+
+                write_path(&mut self.content, &shape.build_path(rect));
+
+                self.content.push_str("f\nQ\n");
+            }
+
+            CanvasCommand::Texture {
+                rect,
+                shape,
+                texture_id,
+                tex_bounds,
+            } => {
+                if self.textures.get(*texture_id).is_none() {
+                    tracing::warn!(?texture_id, "texture does not exist");
+
+                    return;
+                }
+
+                if !self.is_visible(*rect, origin) {
+                    return;
+                }
+
+                let image_name = self.resources.image_name(*texture_id);
+
+                self.content.push_str("q\n");
+
+                self.set_graphics_state(1.0, BlendMode::SourceOver);
+
+                write_path(&mut self.content, &shape.build_path(*rect));
+
+                self.content.push_str("W n\n");
+
+                // Images fill the unit square with their first row at the top, so they're
+                // flipped to match the canvas.
+                let image_rect = texture_rect(*rect, *tex_bounds);
+
+                writeln!(
+                    self.content,
+                    "{} 0 0 {} {} {} cm\n{image_name} Do\nQ",
+                    format_number(image_rect.width),
+                    format_number(-image_rect.height),
+                    format_number(image_rect.left),
+                    format_number(image_rect.top + image_rect.height)
+                )
+                .expect("failed to write to string");
+            }
+
+            CanvasCommand::Text {
+                paint_idx,
+                rect,
+                text_style,
+                text,
+            } => {
+                let Some(paint) = get_paint(paints, *paint_idx) else {
+                    return;
+                };
+
+                if !self.is_visible(*rect, origin) {
+                    return;
+                }
+
+                let Some(font_id) = self.fonts.resolve(text_style.font) else {
+                    tracing::warn!("no font is available to draw text with");
+
+                    return;
+                };
+
+                let Some(font) = self.fonts.get(font_id) else {
+                    return;
+                };
+
+                let Some(metrics) = FontMetrics::new(font, text_style.size) else {
+                    return;
+                };
+
+                let mut glyphs = Vec::new();
+
+                layout_text(&metrics, rect.width, text, |glyph| {
+                    glyphs.push(glyph);
+                });
+
+                let font_name = self.resources.font_name(font_id, &glyphs);
+
+                // The distance the text cursor moves after showing a glyph, which matches the
+                // widths the font is embedded with.
+                let advance_of =
+                    |glyph_id: GlyphId| glyph_width(font, glyph_id) * text_style.size / 1000.0;
+
+                self.content.push_str("q\n");
+
+                self.set_fill(paint);
+
+                for line in glyphs.chunk_by(|a, b| a.baseline == b.baseline) {
+                    let Some(first) = line.first() else {
+                        continue;
+                    };
+
+                    // The text matrix flips the glyphs back upright within the flipped page.
+                    writeln!(
+                        self.content,
+                        "BT\n{font_name} {} Tf\n1 0 0 -1 {} {} Tm",
+                        format_number(text_style.size),
+                        format_number(rect.left + first.x),
+                        format_number(rect.top + first.baseline)
+                    )
+                    .expect("failed to write to string");
+
+                    write_glyph_run(&mut self.content, line, text_style.size, advance_of);
+
+                    self.content.push_str("ET\n");
+                }
+
+                self.content.push_str("Q\n");
+            }
+
+            command => {
+                tracing::error!("unknown command: {:?}", command);
+            }
+        }
+    }
+
+    fn set_fill(&mut self, paint: &Paint) {
+        self.set_graphics_state(paint.color.alpha, paint.blend_mode);
+
+        let Color {
+            red, green, blue, ..
+        } = paint.color;
+
+        writeln!(
+            self.content,
+            "{} {} {} rg",
+            format_number(red.clamp(0.0, 1.0)),
+            format_number(green.clamp(0.0, 1.0)),
+            format_number(blue.clamp(0.0, 1.0))
+        )
+        .expect("failed to write to string");
+    }
+
+    fn set_graphics_state(&mut self, alpha: f32, blend_mode: BlendMode) {
+        let alpha = (alpha.clamp(0.0, 1.0) * self.opacity * 1000.0).round() / 1000.0;
+
+        let blend_mode = if blend_mode == BlendMode::SourceOver {
+            self.blend_mode
+        } else {
+            blend_mode
+        };
+
+        let blend_mode = to_blend_mode(blend_mode).unwrap_or_else(|| {
+            tracing::warn!(
+                ?blend_mode,
+                "blend mode is not supported in pdf, falling back to normal blending"
+            );
+
+            "Normal"
+        });
+
+        if alpha >= 1.0 && blend_mode == "Normal" {
+            return;
+        }
+
+        let name = self
+            .resources
+            .graphics_state_name(GraphicsState { alpha, blend_mode });
+
+        writeln!(self.content, "{name} gs").expect("failed to write to string");
+    }
+
+    fn push_translation(&mut self, offset: Offset) {
+        if offset != Offset::ZERO {
+            self.content.push_str("q\n");
+
+            self.translate(offset);
+        }
+    }
+
+    fn translate(&mut self, offset: Offset) {
+        if offset != Offset::ZERO {
+            writeln!(
+                self.content,
+                "1 0 0 1 {} {} cm",
+                format_number(offset.x),
+                format_number(offset.y)
+            )
+            .expect("failed to write to string");
+        }
+    }
+
+    fn pop_translation(&mut self, offset: Offset) {
+        if offset != Offset::ZERO {
+            self.content.push_str("Q\n");
+        }
+    }
+
+    /// Limits unbounded rects, such as those of the root render object, to the visible area of
+    /// the page. Bounded rects are returned unchanged.
+    fn bound_rect(&self, rect: Rect, origin: Offset) -> Rect {
+        if rect.width.is_finite() && rect.height.is_finite() {
+            return rect;
+        }
+
+        let left = rect.left.max(self.visible.left - origin.x);
+        let top = rect.top.max(self.visible.top - origin.y);
+        let right =
+            (rect.left + rect.width).min((self.visible.left + self.visible.width) - origin.x);
+        let bottom =
+            (rect.top + rect.height).min((self.visible.top + self.visible.height) - origin.y);
+
+        Rect::new(left, top, (right - left).max(0.0), (bottom - top).max(0.0))
+    }
+
+    fn is_visible(&self, rect: Rect, origin: Offset) -> bool {
+        let (left, top) = (rect.left + origin.x, rect.top + origin.y);
+
+        left < (self.visible.left + self.visible.width)
+            && left + rect.width > self.visible.left
+            && top < (self.visible.top + self.visible.height)
+            && top + rect.height > self.visible.top
+    }
+}
+
+/// The width of a glyph in thousandths of the font size, as it's embedded in the document.
+pub fn glyph_width(font: &FontArc, glyph_id: GlyphId) -> f32 {
+    let units_per_em = font.units_per_em().unwrap_or(1000.0);
+
+    (font.h_advance_unscaled(glyph_id) * 1000.0 / units_per_em).round()
+}
+
+/// Writes a line of glyphs as a single text-showing operation, adjusting the position of any
+/// glyph that doesn't immediately follow the previous one, such as after kerning.
+///
+/// Fonts are embedded with the `Identity-H` encoding, so glyphs are shown by their ID.
+fn write_glyph_run(
+    content: &mut String,
+    glyphs: &[PositionedGlyph<GlyphId>],
+    font_size: f32,
+    advance_of: impl Fn(GlyphId) -> f32,
+) {
+    content.push('[');
+
+    let mut run = Vec::new();
+
+    let mut pen_x: Option<f32> = None;
+
+    for glyph in glyphs {
+        // Adjustments are in thousandths of the font size, and move the cursor backwards.
+        if let Some(pen_x) = pen_x {
+            let adjustment = (pen_x - glyph.x) * 1000.0 / font_size;
+
+            if adjustment.abs() >= 0.001 {
+                write_glyph_ids(content, &run);
+                run.clear();
+
+                write!(content, " {} ", format_number(adjustment))
+                    .expect("failed to write to string");
+            }
+        }
+
+        run.push(glyph.id);
+
+        pen_x = Some(glyph.x + advance_of(glyph.id));
+    }
+
+    write_glyph_ids(content, &run);
+
+    content.push_str("] TJ\n");
+}
+
+/// Writes the glyph IDs as a hex string, with two bytes for each of them.
+fn write_glyph_ids(content: &mut String, glyph_ids: &[GlyphId]) {
+    if glyph_ids.is_empty() {
+        return;
+    }
+
+    content.push('<');
+
+    for glyph_id in glyph_ids {
+        write!(content, "{:04X}", glyph_id.0).expect("failed to write to string");
+    }
+
+    content.push('>');
+}
+
+/// Writes the path using path construction operators. Quadratic curves are converted to cubic
+/// ones, since PDF doesn't support them.
+fn write_path(content: &mut String, path: &Path) {
+    for event in path.iter() {
+        match event {
+            Event::Begin { at } => {
+                writeln!(content, "{} {} m", format_number(at.x), format_number(at.y))
+            }
+
+            Event::Line { to, .. } => {
+                writeln!(content, "{} {} l", format_number(to.x), format_number(to.y))
+            }
+
+            Event::Quadratic { from, ctrl, to } => {
+                let ctrl1 = from + (ctrl - from) * (2.0 / 3.0);
+                let ctrl2 = to + (ctrl - to) * (2.0 / 3.0);
+
+                writeln!(
+                    content,
+                    "{} {} {} {} {} {} c",
+                    format_number(ctrl1.x),
+                    format_number(ctrl1.y),
+                    format_number(ctrl2.x),
+                    format_number(ctrl2.y),
+                    format_number(to.x),
+                    format_number(to.y)
+                )
+            }
+
+            Event::Cubic {
+                ctrl1, ctrl2, to, ..
+            } => writeln!(
+                content,
+                "{} {} {} {} {} {} c",
+                format_number(ctrl1.x),
+                format_number(ctrl1.y),
+                format_number(ctrl2.x),
+                format_number(ctrl2.y),
+                format_number(to.x),
+                format_number(to.y)
+            ),
+
+            Event::End { close: true, .. } => writeln!(content, "h"),
+
+            Event::End { close: false, .. } => Ok(()),
+        }
+        .expect("failed to write to string");
+    }
+}
+
+fn get_paint(paints: &[Paint], paint_idx: usize) -> Option<&Paint> {
+    let paint = paints.get(paint_idx);
+
+    if paint.is_none() {
+        tracing::warn!(paint_idx, "command references a paint that does not exist");
+    }
+
+    paint
+}
+
+/// Places the texture so that the region selected by the bounds fills the rect. The bounds are
+/// normalized to the size of the texture, and bounds with no area select the entire texture.
+fn texture_rect(rect: Rect, tex_bounds: Bounds) -> Rect {
+    if tex_bounds.right <= tex_bounds.left || tex_bounds.bottom <= tex_bounds.top {
+        return rect;
+    }
+
+    let width = rect.width / (tex_bounds.right - tex_bounds.left);
+    let height = rect.height / (tex_bounds.bottom - tex_bounds.top);
+
+    Rect::new(
+        rect.left - tex_bounds.left * width,
+        rect.top - tex_bounds.top * height,
+        width,
+        height,
+    )
+}
+
+/// Maps a blend mode onto the name PDF uses for it. Most Porter-Duff compositing operators have
+/// no equivalent, and return `None`.
+pub fn to_blend_mode(blend_mode: BlendMode) -> Option<&'static str> {
+    match blend_mode {
+        BlendMode::SourceOver => Some("Normal"),
+        BlendMode::Modulate | BlendMode::Multiply => Some("Multiply"),
+        BlendMode::Screen => Some("Screen"),
+        BlendMode::Overlay => Some("Overlay"),
+        BlendMode::Darken => Some("Darken"),
+        BlendMode::Lighten => Some("Lighten"),
+        BlendMode::ColorDodge => Some("ColorDodge"),
+        BlendMode::ColorBurn => Some("ColorBurn"),
+        BlendMode::HardLight => Some("HardLight"),
+        BlendMode::SoftLight => Some("SoftLight"),
+        BlendMode::Difference => Some("Difference"),
+        BlendMode::Exclusion => Some("Exclusion"),
+        BlendMode::Hue => Some("Hue"),
+        BlendMode::Saturation => Some("Saturation"),
+        BlendMode::Color => Some("Color"),
+        BlendMode::Luminosity => Some("Luminosity"),
+
+        BlendMode::Clear
+        | BlendMode::Source
+        | BlendMode::Destination
+        | BlendMode::DestinationOver
+        | BlendMode::SourceIn
+        | BlendMode::DestinationIn
+        | BlendMode::SourceOut
+        | BlendMode::DestinationOut
+        | BlendMode::SourceAlphaTop
+        | BlendMode::DestinationAlphaTop
+        | BlendMode::XOr
+        | BlendMode::Plus => None,
+    }
+}
+
+/// Formats a value with at most three decimal places, since PDF doesn't support exponents.
+pub fn format_number(value: f32) -> String {
+    let formatted = format!("{value:.3}");
+    let formatted = formatted.trim_end_matches('0').trim_end_matches('.');
+
+    // Avoid a distinction between zero and negative zero.
+    if formatted == "-0" {
+        String::from("0")
+    } else {
+        String::from(formatted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ab_glyph::GlyphId;
+    use agui_core::{
+        render::{canvas::Canvas, CanvasPainter, Paint},
+        unit::{BlendMode, Color, Offset, Rect, Shape, Size},
+    };
+    use agui_primitives::text::{fonts::FontRegistry, glyph_layout::PositionedGlyph};
+
+    use crate::textures::PdfTextures;
+
+    use super::{write_glyph_run, ContentWriter, GraphicsState, Resources};
+
+    const RED: Color = Color::from_rgb((1.0, 0.0, 0.0));
+
+    fn paint_canvas(size: Size, func: impl FnOnce(CanvasPainter)) -> Canvas {
+        let mut canvas = Canvas {
+            size,
+
+            paints: Vec::default(),
+
+            head: Vec::default(),
+            children: Vec::default(),
+            tail: None,
+        };
+
+        func(CanvasPainter::begin(&mut canvas));
+
+        canvas
+    }
+
+    fn write(canvas: &Canvas, resources: &mut Resources) -> String {
+        let (fonts, textures) = (FontRegistry::default(), PdfTextures::default());
+
+        let mut writer = ContentWriter::new(&fonts, &textures, resources, Rect::from(canvas.size));
+
+        writer
+            .write_canvas::<()>(canvas, Offset::ZERO, &mut |_| Ok(()))
+            .unwrap();
+
+        writer.finish()
+    }
+
+    #[test]
+    pub fn writes_shapes_as_filled_paths() {
+        let canvas = paint_canvas(Size::new(100.0, 100.0), |mut canvas| {
+            let brush = canvas.add_paint(Paint {
+                color: RED,
+                ..Paint::default()
+            });
+
+            canvas.draw_rect_at(Rect::new(10.0, 10.0, 30.0, 30.0), &brush);
+
+            let translucent = canvas.add_paint(Paint {
+                color: Color::from_rgba((0.0, 0.0, 1.0, 0.5)),
+                anti_alias: true,
+                blend_mode: BlendMode::Multiply,
+            });
+
+            canvas.draw_rect(&translucent);
+        });
+
+        let mut resources = Resources::default();
+
+        assert_eq!(
+            write(&canvas, &mut resources),
+            concat!(
+                "q\n1 0 0 rg\n10 10 m\n40 10 l\n40 40 l\n10 40 l\nh\nf\nQ\n",
+                "q\n/GS0 gs\n0 0 1 rg\n0 0 m\n100 0 l\n100 100 l\n0 100 l\nh\nf\nQ\n",
+            )
+        );
+
+        assert_eq!(
+            resources.graphics_states,
+            [GraphicsState {
+                alpha: 0.5,
+                blend_mode: "Multiply",
+            }]
+        );
+    }
+
+    #[test]
+    pub fn applies_layer_opacity_to_its_contents() {
+        let canvas = paint_canvas(Size::new(100.0, 100.0), |mut canvas| {
+            let layer_brush = canvas.add_paint(Paint {
+                color: Color::from_rgba((1.0, 1.0, 1.0, 0.25)),
+                anti_alias: true,
+                ..Paint::default()
+            });
+
+            canvas.layer_at(
+                Rect::new(10.0, 20.0, 50.0, 50.0),
+                &layer_brush,
+                Shape::Rect,
+                |mut canvas| {
+                    let brush = canvas.add_paint(Paint {
+                        color: RED,
+                        ..Paint::default()
+                    });
+
+                    canvas.draw_rect(&brush);
+                },
+            );
+        });
+
+        let mut resources = Resources::default();
+
+        assert_eq!(
+            write(&canvas, &mut resources),
+            concat!(
+                "q\n1 0 0 1 10 20 cm\n0 0 m\n50 0 l\n50 50 l\n0 50 l\nh\nW n\n",
+                "q\n/GS0 gs\n1 0 0 rg\n0 0 m\n50 0 l\n50 50 l\n0 50 l\nh\nf\nQ\n",
+                "Q\n",
+            )
+        );
+
+        assert_eq!(
+            resources.graphics_states,
+            [GraphicsState {
+                alpha: 0.25,
+                blend_mode: "Normal",
+            }]
+        );
+    }
+
+    #[test]
+    pub fn shows_glyphs_by_their_id() {
+        let glyphs =
+            [('A', 36, 0.0), ('V', 57, 9.0), ('é', 136, 19.0)].map(|(ch, id, x)| PositionedGlyph {
+                id: GlyphId(id),
+                ch,
+                x,
+                baseline: 0.0,
+            });
+
+        let mut content = String::new();
+
+        // Every glyph is half of the 20pt font size wide, so the second glyph is kerned by 1pt.
+        write_glyph_run(&mut content, &glyphs, 20.0, |_| 10.0);
+
+        assert_eq!(content, "[<0024> 50 <00390088>] TJ\n");
+    }
+}
@@ -0,0 +1,576 @@
+use std::{collections::BTreeMap, fmt::Write, sync::Arc};
+
+use ab_glyph::{Font as _, FontArc, GlyphId, InvalidFont};
+use agui_core::{
+    engine::{Engine, EngineError},
+    unit::{EdgeInsets, Font, Offset, Rect, Size, TextureId},
+};
+use agui_primitives::text::{fonts::FontRegistry, glyph_layout::GlyphLayoutDelegate};
+use parking_lot::Mutex;
+
+use crate::{
+    content::{format_number, glyph_width, ContentWriter, Resources},
+    file::{ObjectId, PdfFile},
+    pages::{collect_leaf_extents, paginate},
+    textures::PdfTextures,
+};
+
+/// The size and margins of the pages in an exported document, in points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PageFormat {
+    pub size: Size,
+    pub margin: EdgeInsets,
+}
+
+impl PageFormat {
+    pub const A4: Size = Size::new(595.0, 842.0);
+    pub const LETTER: Size = Size::new(612.0, 792.0);
+
+    /// Creates a page format of the given size, with half-inch margins.
+    pub fn new(size: Size) -> Self {
+        Self {
+            size,
+            margin: EdgeInsets::all(36.0),
+        }
+    }
+
+    pub fn with_margin(mut self, margin: EdgeInsets) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// The size of the area within the margins that content is drawn into.
+    pub fn content_size(&self) -> Size {
+        Size::new(
+            (self.size.width - self.margin.horizontal()).max(0.0),
+            (self.size.height - self.margin.vertical()).max(0.0),
+        )
+    }
+}
+
+impl Default for PageFormat {
+    fn default() -> Self {
+        Self::new(Self::A4)
+    }
+}
+
+/// Exports the render tree of an engine as a paginated PDF document.
+///
+/// Text is laid out with the fonts added to the exporter, which are embedded in the document
+/// so that it prints exactly as it was measured. Use [`PdfExporter::text_layout_delegate`] to
+/// lay out the widget tree with the same fonts, or [`PdfExporter::with_fonts`] to print with the
+/// fonts already registered with another backend.
+#[derive(Default)]
+pub struct PdfExporter {
+    fonts: Arc<Mutex<FontRegistry>>,
+    textures: Arc<Mutex<PdfTextures>>,
+}
+
+impl PdfExporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an exporter that draws text with the given fonts, such as those of the renderer
+    /// the widget tree was laid out with, so that any [`Font`] it references resolves to the
+    /// same typeface. Every backend exposes its registry through a `fonts` method, including
+    /// the Vello plugin, which registers each font it loads with its registry as well.
+    pub fn with_fonts(fonts: Arc<Mutex<FontRegistry>>) -> Self {
+        Self {
+            fonts,
+            textures: Arc::default(),
+        }
+    }
+
+    /// Loads a TrueType font from its raw data. The first font that is added is used to draw
+    /// any text which doesn't specify one.
+    pub fn add_font(&self, font_data: Vec<u8>) -> Result<Font, InvalidFont> {
+        let font = FontArc::try_from_vec(font_data)?;
+
+        Ok(self.fonts.lock().add_font(font))
+    }
+
+    /// Adds a texture from straight (non-premultiplied) RGBA pixel data, row by row.
+    ///
+    /// Returns `None` if the texture is empty or if the data does not match its size.
+    pub fn add_texture(&self, width: u32, height: u32, rgba: &[u8]) -> Option<TextureId> {
+        self.textures.lock().add_texture(width, height, rgba)
+    }
+
+    /// Creates a delegate that measures text with the exporter's fonts, to be given to a
+    /// [`TextLayoutController`](agui_primitives::text::layout_controller::TextLayoutController).
+    pub fn text_layout_delegate(&self) -> GlyphLayoutDelegate {
+        GlyphLayoutDelegate::new(Arc::clone(&self.fonts))
+    }
+
+    /// Exports the render tree of the engine, returning the bytes of the PDF file.
+    ///
+    /// The tree is drawn from the top of the first page, within the margins of the page format.
+    /// Content that doesn't fit on a page is continued on the next one, with breaks placed
+    /// between render objects that have no children (such as text) wherever possible, so that
+    /// they aren't cut in half. Content outside of the margins horizontally is clipped.
+    pub fn export(&self, engine: &Engine, format: &PageFormat) -> Result<Vec<u8>, EngineError> {
+        let fonts = self.fonts.lock();
+        let textures = self.textures.lock();
+
+        let render_objects = engine.render_objects();
+
+        let content_size = format.content_size();
+
+        let mut file = PdfFile::default();
+
+        let catalog_id = file.reserve();
+        let pages_id = file.reserve();
+        let resources_id = file.reserve();
+
+        let mut resources = Resources::default();
+
+        let page_starts = paginate(&collect_leaf_extents(render_objects), content_size.height);
+
+        let mut page_ids = Vec::with_capacity(page_starts.len());
+
+        for (idx, page_start) in page_starts.iter().copied().enumerate() {
+            // Each page shows the document up until the next page starts, so that anything moved
+            // onto the next page isn't also shown at the bottom of this one.
+            let page_end = page_starts
+                .get(idx + 1)
+                .copied()
+                .unwrap_or(page_start + content_size.height);
+
+            let visible = Rect::new(0.0, page_start, content_size.width, page_end - page_start);
+
+            let mut writer = ContentWriter::new(&fonts, &textures, &mut resources, visible);
+
+            if let Some(root_id) = render_objects.root() {
+                writer.write_render_object(render_objects, root_id, Offset::ZERO)?;
+            }
+
+            // Flip the page so that it points downwards like the canvas, then clip it to the
+            // margins and move the slice of the document being shown into them.
+            let content = format!(
+                "1 0 0 -1 0 {page_height} cm\n{left} {top} {width} {height} re W n\n1 0 0 1 {left} {y} cm\n{body}",
+                page_height = format_number(format.size.height),
+                left = format_number(format.margin.left),
+                top = format_number(format.margin.top),
+                width = format_number(content_size.width),
+                height = format_number(visible.height),
+                y = format_number(format.margin.top - page_start),
+                body = writer.finish(),
+            );
+
+            let content_id = file.add_stream("", content.as_bytes());
+
+            page_ids.push(file.add(format!(
+                "<< /Type /Page /Parent {pages_id} /MediaBox [0 0 {} {}] /Resources {resources_id} /Contents {content_id} >>",
+                format_number(format.size.width),
+                format_number(format.size.height),
+            )));
+        }
+
+        let mut resource_dict = String::from("<<");
+
+        if !resources.fonts.is_empty() {
+            resource_dict.push_str(" /Font <<");
+
+            for (idx, resource) in resources.fonts.iter().enumerate() {
+                let Some(font) = fonts.get(resource.font) else {
+                    continue;
+                };
+
+                let font_object_id = write_font(&mut file, font, &resource.glyphs, idx);
+
+                write!(resource_dict, " /F{idx} {font_object_id}")
+                    .expect("failed to write to string");
+            }
+
+            resource_dict.push_str(" >>");
+        }
+
+        if !resources.images.is_empty() {
+            resource_dict.push_str(" /XObject <<");
+
+            for (idx, texture_id) in resources.images.iter().enumerate() {
+                let Some(texture) = textures.get(*texture_id) else {
+                    continue;
+                };
+
+                let (rgb, alpha): (Vec<_>, Vec<_>) = texture
+                    .rgba
+                    .chunks_exact(4)
+                    .map(|pixel| ([pixel[0], pixel[1], pixel[2]], pixel[3]))
+                    .unzip();
+
+                let mask_id = file.add_stream(
+                    &format!(
+                        "/Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace /DeviceGray /BitsPerComponent 8",
+                        texture.width, texture.height
+                    ),
+                    &alpha,
+                );
+
+                let image_id = file.add_stream(
+                    &format!(
+                        "/Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace /DeviceRGB /BitsPerComponent 8 /SMask {mask_id}",
+                        texture.width, texture.height
+                    ),
+                    &rgb.concat(),
+                );
+
+                write!(resource_dict, " /Im{idx} {image_id}").expect("failed to write to string");
+            }
+
+            resource_dict.push_str(" >>");
+        }
+
+        if !resources.graphics_states.is_empty() {
+            resource_dict.push_str(" /ExtGState <<");
+
+            for (idx, graphics_state) in resources.graphics_states.iter().enumerate() {
+                let alpha = format_number(graphics_state.alpha);
+
+                write!(
+                    resource_dict,
+                    " /GS{idx} << /Type /ExtGState /ca {alpha} /CA {alpha} /BM /{} >>",
+                    graphics_state.blend_mode
+                )
+                .expect("failed to write to string");
+            }
+
+            resource_dict.push_str(" >>");
+        }
+
+        resource_dict.push_str(" >>");
+
+        file.set(resources_id, resource_dict);
+
+        let kids = page_ids
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        file.set(
+            pages_id,
+            format!(
+                "<< /Type /Pages /Kids [{kids}] /Count {} >>",
+                page_ids.len()
+            ),
+        );
+
+        file.set(
+            catalog_id,
+            format!("<< /Type /Catalog /Pages {pages_id} >>"),
+        );
+
+        Ok(file.finish(catalog_id))
+    }
+}
+
+/// Embeds a TrueType font as a composite font, so that any of its glyphs can be shown. Glyphs
+/// are encoded by their ID with `Identity-H`, and a `ToUnicode` map lets readers extract the
+/// characters they were shown for. Metrics are scaled to a 1000 unit em square.
+fn write_font(
+    file: &mut PdfFile,
+    font: &FontArc,
+    glyphs: &BTreeMap<u16, char>,
+    idx: usize,
+) -> ObjectId {
+    let data = font.font_data();
+
+    let font_file_id = file.add_stream(&format!("/Length1 {}", data.len()), data);
+
+    let scale = 1000.0 / font.units_per_em().unwrap_or(1000.0);
+
+    let ascent = format_number((font.ascent_unscaled() * scale).round());
+    let descent = format_number((font.descent_unscaled() * scale).round());
+
+    let font_name = format!("/AguiFont{idx}");
+
+    // Flags mark the font as symbolic, since its glyphs aren't shown through a standard encoding.
+    let descriptor_id = file.add(format!(
+        "<< /Type /FontDescriptor /FontName {font_name} /Flags 4 /FontBBox [0 {descent} 1000 {ascent}] /ItalicAngle 0 /Ascent {ascent} /Descent {descent} /CapHeight {ascent} /StemV 80 /FontFile2 {font_file_id} >>"
+    ));
+
+    let widths = glyphs
+        .keys()
+        .map(|glyph_id| {
+            format!(
+                "{glyph_id} [{}]",
+                format_number(glyph_width(font, GlyphId(*glyph_id)))
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let cid_font_id = file.add(format!(
+        "<< /Type /Font /Subtype /CIDFontType2 /BaseFont {font_name} /CIDSystemInfo << /Registry (Adobe) /Ordering (Identity) /Supplement 0 >> /FontDescriptor {descriptor_id} /W [{widths}] /CIDToGIDMap /Identity >>"
+    ));
+
+    let to_unicode_id = file.add_stream("", to_unicode_cmap(glyphs).as_bytes());
+
+    file.add(format!(
+        "<< /Type /Font /Subtype /Type0 /BaseFont {font_name} /Encoding /Identity-H /DescendantFonts [{cid_font_id}] /ToUnicode {to_unicode_id} >>"
+    ))
+}
+
+/// Writes a CMap that maps each glyph ID to the character it was shown for, in UTF-16.
+fn to_unicode_cmap(glyphs: &BTreeMap<u16, char>) -> String {
+    let mut cmap = String::from(concat!(
+        "/CIDInit /ProcSet findresource begin\n",
+        "12 dict begin\n",
+        "begincmap\n",
+        "/CIDSystemInfo << /Registry (Adobe) /Ordering (UCS) /Supplement 0 >> def\n",
+        "/CMapName /Adobe-Identity-UCS def\n",
+        "/CMapType 2 def\n",
+        "1 begincodespacerange\n",
+        "<0000> <FFFF>\n",
+        "endcodespacerange\n",
+    ));
+
+    let glyphs = glyphs.iter().collect::<Vec<_>>();
+
+    // Each block may map at most 100 glyphs.
+    for block in glyphs.chunks(100) {
+        writeln!(cmap, "{} beginbfchar", block.len()).expect("failed to write to string");
+
+        for (glyph_id, ch) in block {
+            write!(cmap, "<{glyph_id:04X}> <").expect("failed to write to string");
+
+            for unit in ch.encode_utf16(&mut [0; 2]) {
+                write!(cmap, "{unit:04X}").expect("failed to write to string");
+            }
+
+            cmap.push_str(">\n");
+        }
+
+        cmap.push_str("endbfchar\n");
+    }
+
+    cmap.push_str(concat!(
+        "endcmap\n",
+        "CMapName currentdict /CMapResource defineresource pop\n",
+        "end\n",
+        "end\n",
+    ));
+
+    cmap
+}
+
+#[cfg(test)]
+mod tests {
+    use agui_core::unit::{Size, TextStyle};
+    use agui_macros::build;
+    use agui_primitives::{
+        flex::{Column, MainAxisAlignment},
+        text::Text,
+    };
+    use agui_test::WidgetTester;
+    use rustc_hash::FxHashMap;
+
+    use super::{PageFormat, PdfExporter};
+
+    const FONT: &[u8] = include_bytes!("../../../examples/fonts/DejaVuSans.ttf");
+
+    /// Inflates every stream in the file that holds text, skipping binary ones such as fonts.
+    fn streams(pdf: &[u8]) -> Vec<String> {
+        let mut streams = Vec::new();
+
+        let mut rest = pdf;
+
+        while let Some(start) = find(rest, b">>\nstream\n") {
+            let data = &rest[start + b">>\nstream\n".len()..];
+
+            let end = find(data, b"\nendstream").expect("stream should have ended");
+
+            if let Ok(data) = miniz_oxide::inflate::decompress_to_vec_zlib(&data[..end]) {
+                if let Ok(content) = String::from_utf8(data) {
+                    streams.push(content);
+                }
+            }
+
+            rest = &data[end..];
+        }
+
+        streams
+    }
+
+    /// The content streams of the pages that show text.
+    fn content_streams(pdf: &[u8]) -> Vec<String> {
+        streams(pdf)
+            .into_iter()
+            .filter(|content| content.contains("BT\n"))
+            .collect()
+    }
+
+    fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+        haystack
+            .windows(needle.len())
+            .position(|window| window == needle)
+    }
+
+    /// Splits a hex string into the two byte values it contains.
+    fn hex_units(hex: &str) -> Vec<u16> {
+        hex.as_bytes()
+            .chunks(4)
+            .map(|unit| {
+                u16::from_str_radix(std::str::from_utf8(unit).unwrap(), 16)
+                    .expect("should have been a hex string")
+            })
+            .collect()
+    }
+
+    /// Reads the characters that each glyph ID was shown for from the `ToUnicode` maps of the
+    /// embedded fonts, as a reader extracting text would.
+    fn to_unicode(pdf: &[u8]) -> FxHashMap<u16, String> {
+        let mut to_unicode = FxHashMap::default();
+
+        for cmap in streams(pdf) {
+            let mut in_block = false;
+
+            for line in cmap.lines() {
+                if line.ends_with("beginbfchar") {
+                    in_block = true;
+                } else if line == "endbfchar" {
+                    in_block = false;
+                } else if let Some((glyph_id, unicode)) =
+                    line.split_once("> <").filter(|_| in_block)
+                {
+                    to_unicode.insert(
+                        hex_units(glyph_id.trim_start_matches('<'))[0],
+                        String::from_utf16(&hex_units(unicode.trim_end_matches('>'))).unwrap(),
+                    );
+                }
+            }
+        }
+
+        to_unicode
+    }
+
+    /// Reads the text shown by each text-showing operation, ignoring any adjustments.
+    fn shown_text(content: &str, to_unicode: &FxHashMap<u16, String>) -> Vec<String> {
+        content
+            .lines()
+            .filter(|line| line.ends_with("TJ"))
+            .map(|line| {
+                line.split('<')
+                    .filter_map(|part| part.split_once('>'))
+                    .flat_map(|(hex, _)| hex_units(hex))
+                    .map(|glyph_id| {
+                        to_unicode
+                            .get(&glyph_id)
+                            .expect("glyph should have been mapped to a character")
+                            .as_str()
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    pub fn paginates_text_without_cutting_lines() {
+        let format = PageFormat::default();
+
+        let content_size = format.content_size();
+
+        let mut tester = WidgetTester::with_size(
+            build! {
+                <Column> {
+                    main_axis_alignment: MainAxisAlignment::Start,
+
+                    children: (0..100).map(|idx| build! {
+                        <Text> {
+                            style: TextStyle::new().size(18.0),
+                            text: format!("Line {idx:03}").into(),
+                        }
+                    }),
+                }
+            },
+            Size::new(content_size.width, 4000.0),
+        );
+
+        tester
+            .add_font(FONT.to_vec())
+            .expect("failed to load the font");
+
+        let pdf = PdfExporter::with_fonts(tester.renderer().fonts())
+            .export(tester.engine(), &format)
+            .expect("failed to export");
+
+        assert!(
+            pdf.starts_with(b"%PDF-"),
+            "should have written a pdf header"
+        );
+        assert!(
+            find(&pdf, b"/FontFile2").is_some(),
+            "should have embedded the font"
+        );
+        assert!(
+            find(&pdf, b"/Encoding /Identity-H").is_some(),
+            "should have encoded the text by glyph"
+        );
+
+        assert!(
+            find(&pdf, b"/Count 3 ").is_some(),
+            "should have split the text across three pages"
+        );
+
+        let pages = content_streams(&pdf);
+
+        assert_eq!(pages.len(), 3, "every page should have shown text");
+
+        let to_unicode = to_unicode(&pdf);
+
+        let lines = pages
+            .iter()
+            .flat_map(|content| shown_text(content, &to_unicode))
+            .collect::<Vec<_>>();
+
+        for idx in 0..100 {
+            let line = format!("Line {idx:03}");
+
+            assert_eq!(
+                lines.iter().filter(|shown| **shown == line).count(),
+                1,
+                "should have shown {line:?} on exactly one page"
+            );
+        }
+
+        assert_eq!(lines.len(), 100, "should not have shown any other text");
+    }
+
+    #[test]
+    pub fn keeps_text_outside_of_latin_selectable() {
+        let text = "Привет, κόσμε";
+
+        let mut tester = WidgetTester::new(build! {
+            <Text> {
+                style: TextStyle::new().size(18.0),
+                text: text.into(),
+            }
+        });
+
+        tester
+            .add_font(FONT.to_vec())
+            .expect("failed to load the font");
+
+        let pdf = PdfExporter::with_fonts(tester.renderer().fonts())
+            .export(tester.engine(), &PageFormat::default())
+            .expect("failed to export");
+
+        let to_unicode = to_unicode(&pdf);
+
+        assert!(
+            !to_unicode.contains_key(&0),
+            "should not have shown any missing glyphs"
+        );
+
+        assert_eq!(
+            content_streams(&pdf)
+                .iter()
+                .flat_map(|content| shown_text(content, &to_unicode))
+                .collect::<String>(),
+            text,
+            "should have mapped every glyph back to its character"
+        );
+    }
+}
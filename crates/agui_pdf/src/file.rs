@@ -0,0 +1,109 @@
+use std::{fmt, io::Write};
+
+/// A reference to an indirect object in a [`PdfFile`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ObjectId(usize);
+
+impl fmt::Display for ObjectId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} 0 R", self.0)
+    }
+}
+
+/// Serializes indirect objects into a PDF file, along with the cross-reference table that
+/// readers use to locate them.
+#[derive(Default)]
+pub struct PdfFile {
+    objects: Vec<Option<Vec<u8>>>,
+}
+
+impl PdfFile {
+    /// Reserves an object, so that it can be referenced before its contents are known.
+    pub fn reserve(&mut self) -> ObjectId {
+        self.objects.push(None);
+
+        ObjectId(self.objects.len())
+    }
+
+    /// Sets the contents of a reserved object.
+    pub fn set(&mut self, object_id: ObjectId, contents: impl Into<Vec<u8>>) {
+        self.objects[object_id.0 - 1] = Some(contents.into());
+    }
+
+    /// Sets the contents of a reserved object to a compressed stream. The dictionary must not
+    /// include the `/Length` or `/Filter` of the stream, as they're added automatically.
+    pub fn set_stream(&mut self, object_id: ObjectId, dictionary: &str, data: &[u8]) {
+        let data = miniz_oxide::deflate::compress_to_vec_zlib(data, 6);
+
+        let mut contents = format!(
+            "<< {dictionary} /Length {} /Filter /FlateDecode >>\nstream\n",
+            data.len()
+        )
+        .into_bytes();
+
+        contents.extend_from_slice(&data);
+        contents.extend_from_slice(b"\nendstream");
+
+        self.set(object_id, contents);
+    }
+
+    pub fn add(&mut self, contents: impl Into<Vec<u8>>) -> ObjectId {
+        let object_id = self.reserve();
+
+        self.set(object_id, contents);
+
+        object_id
+    }
+
+    pub fn add_stream(&mut self, dictionary: &str, data: &[u8]) -> ObjectId {
+        let object_id = self.reserve();
+
+        self.set_stream(object_id, dictionary, data);
+
+        object_id
+    }
+
+    /// Writes the file, with the given object as its document catalog.
+    ///
+    /// # Panics
+    ///
+    /// Panics if an object was reserved but never set.
+    pub fn finish(self, catalog_id: ObjectId) -> Vec<u8> {
+        let mut output = Vec::new();
+
+        // The comment with high bytes marks the file as binary for transfer tools.
+        output.extend_from_slice(b"%PDF-1.7\n%\xE2\xE3\xCF\xD3\n");
+
+        let mut offsets = Vec::with_capacity(self.objects.len());
+
+        for (idx, contents) in self.objects.into_iter().enumerate() {
+            let contents = contents.expect("object was reserved but never set");
+
+            offsets.push(output.len());
+
+            writeln!(output, "{} 0 obj", idx + 1).expect("failed to write to buffer");
+            output.extend_from_slice(&contents);
+            output.extend_from_slice(b"\nendobj\n");
+        }
+
+        let xref_offset = output.len();
+
+        write!(output, "xref\n0 {}\n", offsets.len() + 1).expect("failed to write to buffer");
+
+        // Each entry must be exactly 20 bytes long, including the line ending.
+        output.extend_from_slice(b"0000000000 65535 f\r\n");
+
+        for offset in offsets.iter() {
+            write!(output, "{offset:010} 00000 n\r\n").expect("failed to write to buffer");
+        }
+
+        write!(
+            output,
+            "trailer\n<< /Size {} /Root {catalog_id} >>\nstartxref\n{xref_offset}\n%%EOF\n",
+            offsets.len() + 1
+        )
+        .expect("failed to write to buffer");
+
+        output
+    }
+}
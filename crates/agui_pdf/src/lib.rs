@@ -0,0 +1,9 @@
+mod content;
+mod exporter;
+mod file;
+mod pages;
+mod textures;
+
+pub use ab_glyph::InvalidFont;
+
+pub use exporter::{PageFormat, PdfExporter};
@@ -0,0 +1,189 @@
+use agui_core::{
+    render::{RenderObject, RenderObjectId},
+    unit::Offset,
+    util::tree::Tree,
+};
+use rustc_hash::FxHashMap;
+
+/// The vertical extent of a leaf render object within the document.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Extent {
+    pub top: f32,
+    pub bottom: f32,
+}
+
+/// Collects the vertical extents of every leaf render object with a finite size, such as text
+/// and boxes without children. These are treated as indivisible when breaking pages.
+pub fn collect_leaf_extents(render_objects: &Tree<RenderObjectId, RenderObject>) -> Vec<Extent> {
+    let mut offsets = FxHashMap::<RenderObjectId, Offset>::default();
+
+    let mut extents = Vec::new();
+
+    for render_object_id in render_objects.iter_down() {
+        let Some(render_object) = render_objects.get(render_object_id) else {
+            continue;
+        };
+
+        // Parents are always visited before their children, so their offset is already known.
+        let offset = render_objects
+            .get_parent(render_object_id)
+            .and_then(|parent_id| offsets.get(&parent_id))
+            .copied()
+            .unwrap_or_default()
+            + render_object.offset();
+
+        offsets.insert(render_object_id, offset);
+
+        let has_children = render_objects
+            .get_children(render_object_id)
+            .is_some_and(|children| !children.is_empty());
+
+        if has_children {
+            continue;
+        }
+
+        if let Some(size) = render_object.size() {
+            if size.height.is_finite() && size.height > 0.0 {
+                extents.push(Extent {
+                    top: offset.y,
+                    bottom: offset.y + size.height,
+                });
+            }
+        }
+    }
+
+    extents
+}
+
+/// Splits the document into pages of the given height, returning the offset each page starts
+/// at. The document ends at the bottom of its lowest leaf.
+///
+/// Each page ends at the lowest point that doesn't cut through a leaf. Leaves taller than a page
+/// can't be kept whole, so they're cut wherever the page ends.
+pub fn paginate(leaves: &[Extent], page_height: f32) -> Vec<f32> {
+    let mut page_starts = Vec::from([0.0]);
+
+    if page_height <= 0.0 {
+        return page_starts;
+    }
+
+    let document_bottom = leaves
+        .iter()
+        .map(|leaf| leaf.bottom)
+        .fold(0.0_f32, f32::max);
+
+    // Leaves taller than a page are cut regardless, so they never prevent a break.
+    let mut whole_leaves = leaves
+        .iter()
+        .filter(|leaf| leaf.bottom - leaf.top <= page_height)
+        .copied()
+        .collect::<Vec<_>>();
+
+    whole_leaves.sort_by(|a, b| a.top.total_cmp(&b.top));
+
+    // The lowest bottom of the leaves up to and including each one, so that a break can be
+    // checked against every leaf that starts above it at once.
+    let lowest_bottoms = whole_leaves
+        .iter()
+        .scan(f32::NEG_INFINITY, |lowest_bottom, leaf| {
+            *lowest_bottom = lowest_bottom.max(leaf.bottom);
+
+            Some(*lowest_bottom)
+        })
+        .collect::<Vec<_>>();
+
+    let is_clean_break = |at: f32| match whole_leaves.partition_point(|leaf| leaf.top < at) {
+        0 => true,
+        idx => lowest_bottoms[idx - 1] <= at,
+    };
+
+    let mut clean_breaks = leaves
+        .iter()
+        .map(|leaf| leaf.top)
+        .filter(|at| is_clean_break(*at))
+        .collect::<Vec<_>>();
+
+    clean_breaks.sort_by(f32::total_cmp);
+
+    let mut page_start = 0.0;
+
+    while page_start + page_height < document_bottom {
+        let page_end = page_start + page_height;
+
+        let page_break = if is_clean_break(page_end) {
+            page_end
+        } else {
+            clean_breaks[..clean_breaks.partition_point(|at| *at <= page_end)]
+                .last()
+                .copied()
+                .filter(|at| *at > page_start)
+                .unwrap_or(page_end)
+        };
+
+        page_starts.push(page_break);
+
+        page_start = page_break;
+    }
+
+    page_starts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{paginate, Extent};
+
+    fn extent(top: f32, bottom: f32) -> Extent {
+        Extent { top, bottom }
+    }
+
+    #[test]
+    pub fn breaks_pages_between_leaves() {
+        assert_eq!(paginate(&[extent(0.0, 50.0)], 100.0), [0.0]);
+
+        assert_eq!(
+            paginate(
+                &[
+                    extent(0.0, 40.0),
+                    extent(40.0, 80.0),
+                    extent(80.0, 120.0),
+                    extent(120.0, 160.0),
+                ],
+                100.0
+            ),
+            [0.0, 80.0],
+            "should have moved the leaf that crossed the end of the page to the next one"
+        );
+
+        assert_eq!(
+            paginate(&[extent(0.0, 40.0), extent(40.0, 100.0)], 100.0),
+            [0.0],
+            "should have kept a leaf that ends exactly at the end of the page"
+        );
+
+        assert_eq!(
+            paginate(&[extent(0.0, 250.0)], 100.0),
+            [0.0, 100.0, 200.0],
+            "should have cut a leaf that's taller than a page"
+        );
+
+        assert_eq!(
+            paginate(
+                &[
+                    extent(0.0, 90.0),
+                    extent(10.0, 30.0),
+                    extent(90.0, 150.0),
+                    extent(60.0, 80.0),
+                ],
+                100.0
+            ),
+            [0.0, 90.0],
+            "should have found the break regardless of the order of the leaves"
+        );
+
+        assert_eq!(
+            paginate(&[extent(0.0, 60.0), extent(50.0, 110.0)], 100.0),
+            [0.0, 100.0],
+            "should have cut at the end of the page when no clean break exists"
+        );
+    }
+}
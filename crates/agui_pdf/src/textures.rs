@@ -0,0 +1,38 @@
+use agui_core::unit::TextureId;
+
+pub struct PdfTexture {
+    pub width: u32,
+    pub height: u32,
+
+    /// Straight (non-premultiplied) RGBA pixel data, row by row.
+    pub rgba: Vec<u8>,
+}
+
+#[derive(Default)]
+pub struct PdfTextures {
+    textures: Vec<PdfTexture>,
+}
+
+impl PdfTextures {
+    /// Adds a texture from straight (non-premultiplied) RGBA pixel data.
+    ///
+    /// Returns `None` if the texture is empty or if the data does not match its size.
+    pub fn add_texture(&mut self, width: u32, height: u32, rgba: &[u8]) -> Option<TextureId> {
+        if width == 0 || height == 0 || rgba.len() != width as usize * height as usize * 4 {
+            return None;
+        }
+
+        self.textures.push(PdfTexture {
+            width,
+            height,
+
+            rgba: rgba.to_vec(),
+        });
+
+        Some(TextureId::new(self.textures.len() - 1))
+    }
+
+    pub fn get(&self, texture_id: TextureId) -> Option<&PdfTexture> {
+        texture_id.idx().and_then(|idx| self.textures.get(idx))
+    }
+}
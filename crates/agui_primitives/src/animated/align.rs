@@ -0,0 +1,89 @@
+use std::time::Duration;
+
+use agui_core::{animation::Curve, unit::Alignment, widget::Widget};
+use agui_elements::stateful::{StatefulBuildContext, StatefulWidget, WidgetState};
+use agui_macros::StatefulWidget;
+
+use crate::align::Align;
+
+use super::{AnimationCurve, ImplicitAnimation, ImplicitTween};
+
+/// An [`Align`] that animates to its new `alignment` and size factors whenever they change.
+#[derive(StatefulWidget, Debug)]
+pub struct AnimatedAlign {
+    pub alignment: Alignment,
+
+    #[prop(default)]
+    pub width_factor: Option<f32>,
+    #[prop(default)]
+    pub height_factor: Option<f32>,
+
+    pub duration: Duration,
+    #[prop(default, transform = |curve: impl Curve + 'static| AnimationCurve::new(curve))]
+    pub curve: AnimationCurve,
+
+    #[prop(into, default)]
+    pub child: Option<Widget>,
+}
+
+impl StatefulWidget for AnimatedAlign {
+    type State = AnimatedAlignState;
+
+    fn create_state(&self) -> Self::State {
+        AnimatedAlignState {
+            animation: ImplicitAnimation::default(),
+
+            alignment: ImplicitTween::new(self.alignment),
+
+            width_factor: ImplicitTween::new(self.width_factor),
+            height_factor: ImplicitTween::new(self.height_factor),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AnimatedAlignState {
+    animation: ImplicitAnimation,
+
+    alignment: ImplicitTween<Alignment>,
+
+    width_factor: ImplicitTween<Option<f32>>,
+    height_factor: ImplicitTween<Option<f32>>,
+}
+
+impl WidgetState for AnimatedAlignState {
+    type Widget = AnimatedAlign;
+
+    fn init_state(&mut self, ctx: &mut StatefulBuildContext<Self>) {
+        let duration = ctx.widget.duration;
+
+        self.animation.init(ctx, duration);
+    }
+
+    fn updated(&mut self, ctx: &mut StatefulBuildContext<Self>, _: &Self::Widget) {
+        let t = self.animation.progress(&ctx.widget.curve);
+
+        // Every value must be retargeted, so this can't short-circuit
+        let is_changed = self.alignment.retarget(&ctx.widget.alignment, t)
+            | self.width_factor.retarget(&ctx.widget.width_factor, t)
+            | self.height_factor.retarget(&ctx.widget.height_factor, t);
+
+        if is_changed {
+            self.animation.restart(ctx.widget.duration);
+        }
+    }
+
+    fn build(&mut self, ctx: &mut StatefulBuildContext<Self>) -> Widget {
+        let t = self.animation.progress(&ctx.widget.curve);
+
+        Align {
+            alignment: self.alignment.value(t),
+
+            width_factor: self.width_factor.value(t),
+            height_factor: self.height_factor.value(t),
+
+            child: ctx.widget.child.clone(),
+        }
+        .into()
+    }
+}
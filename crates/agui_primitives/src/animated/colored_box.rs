@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+use agui_core::{animation::Curve, unit::Color, widget::Widget};
+use agui_elements::stateful::{StatefulBuildContext, StatefulWidget, WidgetState};
+use agui_macros::StatefulWidget;
+
+use crate::colored_box::ColoredBox;
+
+use super::{AnimationCurve, ImplicitAnimation, ImplicitTween};
+
+/// A [`ColoredBox`] that animates to its new `color` whenever it changes.
+#[derive(StatefulWidget, Debug)]
+pub struct AnimatedColoredBox {
+    pub color: Color,
+
+    pub duration: Duration,
+    #[prop(default, transform = |curve: impl Curve + 'static| AnimationCurve::new(curve))]
+    pub curve: AnimationCurve,
+
+    #[prop(into, default)]
+    pub child: Option<Widget>,
+}
+
+impl StatefulWidget for AnimatedColoredBox {
+    type State = AnimatedColoredBoxState;
+
+    fn create_state(&self) -> Self::State {
+        AnimatedColoredBoxState {
+            animation: ImplicitAnimation::default(),
+
+            color: ImplicitTween::new(self.color),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AnimatedColoredBoxState {
+    animation: ImplicitAnimation,
+
+    color: ImplicitTween<Color>,
+}
+
+impl WidgetState for AnimatedColoredBoxState {
+    type Widget = AnimatedColoredBox;
+
+    fn init_state(&mut self, ctx: &mut StatefulBuildContext<Self>) {
+        let duration = ctx.widget.duration;
+
+        self.animation.init(ctx, duration);
+    }
+
+    fn updated(&mut self, ctx: &mut StatefulBuildContext<Self>, _: &Self::Widget) {
+        let t = self.animation.progress(&ctx.widget.curve);
+
+        if self.color.retarget(&ctx.widget.color, t) {
+            self.animation.restart(ctx.widget.duration);
+        }
+    }
+
+    fn build(&mut self, ctx: &mut StatefulBuildContext<Self>) -> Widget {
+        let t = self.animation.progress(&ctx.widget.curve);
+
+        ColoredBox {
+            color: self.color.value(t),
+
+            child: ctx.widget.child.clone(),
+        }
+        .into()
+    }
+}
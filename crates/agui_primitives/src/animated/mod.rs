@@ -0,0 +1,408 @@
+use std::{rc::Rc, time::Duration};
+
+use agui_core::{
+    animation::{AnimationController, Curve, Lerp, Linear, Tween},
+    listenable::{Listenable, NotifierHandle},
+    plugin::context::ContextPlugins,
+    scheduler::FrameSchedulerPlugin,
+};
+use agui_elements::stateful::{ContextWidgetStateMut, StatefulBuildContext, WidgetState};
+
+mod align;
+mod colored_box;
+mod opacity;
+mod padding;
+mod sized_box;
+
+pub use align::*;
+pub use colored_box::*;
+pub use opacity::*;
+pub use padding::*;
+pub use sized_box::*;
+
+/// The curve used by an implicitly animated widget.
+#[derive(Clone)]
+pub struct AnimationCurve(Rc<dyn Curve>);
+
+impl AnimationCurve {
+    pub fn new(curve: impl Curve + 'static) -> Self {
+        Self(Rc::new(curve))
+    }
+}
+
+impl Default for AnimationCurve {
+    fn default() -> Self {
+        Self::new(Linear)
+    }
+}
+
+impl Curve for AnimationCurve {
+    fn transform(&self, t: f32) -> f32 {
+        self.0.transform(t)
+    }
+}
+
+impl std::fmt::Debug for AnimationCurve {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("AnimationCurve").finish()
+    }
+}
+
+/// Drives the transition of an implicitly animated widget from its previous props to its
+/// current ones, rebuilding the widget each frame while it runs.
+///
+/// If the engine has no [`FrameSchedulerPlugin`], changes are applied immediately.
+#[derive(Default)]
+pub struct ImplicitAnimation {
+    controller: Option<AnimationController>,
+
+    handle: Option<NotifierHandle>,
+}
+
+impl ImplicitAnimation {
+    /// Creates the underlying controller. This should be called from
+    /// [`WidgetState::init_state`].
+    pub fn init<S>(&mut self, ctx: &mut StatefulBuildContext<S>, duration: Duration)
+    where
+        S: WidgetState,
+    {
+        let Some(scheduler) = ctx.plugins().get::<FrameSchedulerPlugin>() else {
+            return;
+        };
+
+        let controller = AnimationController::new(scheduler, duration);
+
+        let rebuild = ctx.callback(|ctx, ()| ctx.set_state(|_| {}));
+
+        self.handle = Some(controller.add_listener(move || rebuild.call(())));
+        self.controller = Some(controller);
+    }
+
+    /// Returns the eased progress of the current transition, which is `1.0` once it has
+    /// finished.
+    pub fn progress(&self, curve: &impl Curve) -> f32 {
+        self.controller
+            .as_ref()
+            .map_or(1.0, |controller| curve.transform(controller.value()))
+    }
+
+    /// Starts a new transition from the beginning.
+    pub fn restart(&self, duration: Duration) {
+        if let Some(controller) = &self.controller {
+            controller.set_duration(duration);
+
+            controller.reset();
+            controller.forward();
+        }
+    }
+}
+
+impl std::fmt::Debug for ImplicitAnimation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ImplicitAnimation")
+            .field("controller", &self.controller)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A tween that is retargeted whenever the value that it animates towards changes, starting
+/// from wherever the previous transition had reached.
+#[derive(Debug, Clone)]
+pub struct ImplicitTween<T> {
+    tween: Tween<T>,
+}
+
+impl<T> ImplicitTween<T>
+where
+    T: Lerp + Clone + PartialEq,
+{
+    pub fn new(value: T) -> Self {
+        Self {
+            tween: Tween::new(value.clone(), value),
+        }
+    }
+
+    /// Animates from the value at `t` towards the target. Returns `false` if the target has not
+    /// changed.
+    pub fn retarget(&mut self, target: &T, t: f32) -> bool {
+        if self.tween.end == *target {
+            return false;
+        }
+
+        self.tween = Tween::new(self.tween.transform(t), target.clone());
+
+        true
+    }
+
+    pub fn value(&self, t: f32) -> T {
+        self.tween.transform(t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc, time::Duration};
+
+    use agui_core::{
+        element::{ElementBuilder, ElementType},
+        unit::{Alignment, Color, EdgeInsets},
+        widget::{AnyWidget, IntoWidget, Widget},
+    };
+    use agui_elements::stateless::{StatelessBuildContext, StatelessElement, StatelessWidget};
+    use agui_macros::build;
+    use agui_test::{WidgetTester, FRAME_INTERVAL};
+
+    use crate::{
+        align::Align, colored_box::ColoredBox, opacity::Opacity, padding::Padding,
+        sized_box::SizedBox,
+    };
+
+    use super::{
+        AnimatedAlign, AnimatedColoredBox, AnimatedOpacity, AnimatedPadding, AnimatedSizedBox,
+        ImplicitTween,
+    };
+
+    const DURATION: Duration = Duration::from_millis(320);
+
+    /// The first frame after a change starts the transition, so reaching the half way point
+    /// takes one frame longer than half of the duration.
+    const HALF_WAY: Duration = Duration::from_millis(160).saturating_add(FRAME_INTERVAL);
+
+    /// Builds whichever widget `child` holds, so that tests can replace it.
+    struct Swappable {
+        child: Rc<RefCell<Widget>>,
+    }
+
+    impl StatelessWidget for Swappable {
+        fn build(&self, _: &mut StatelessBuildContext<Self>) -> Widget {
+            self.child.borrow().clone()
+        }
+    }
+
+    impl IntoWidget for Swappable {
+        fn into_widget(self) -> Widget {
+            Widget::new(self)
+        }
+    }
+
+    impl ElementBuilder for Swappable {
+        fn create_element(self: Rc<Self>) -> ElementType {
+            ElementType::Widget(Box::new(StatelessElement::new(self)))
+        }
+    }
+
+    /// Creates a tester around `widget`, returning the slot that can be used to replace it.
+    fn tester_with(widget: Widget) -> (WidgetTester, Rc<RefCell<Widget>>) {
+        let child = Rc::new(RefCell::new(widget));
+
+        let tester = WidgetTester::new(Swappable {
+            child: Rc::clone(&child),
+        });
+
+        (tester, child)
+    }
+
+    /// Replaces the widget in `child` and pumps the rebuild, without advancing time.
+    fn replace(tester: &mut WidgetTester, child: &Rc<RefCell<Widget>>, widget: Widget) {
+        *child.borrow_mut() = widget;
+
+        let element_id = tester
+            .engine()
+            .elements()
+            .iter()
+            .find(|(_, node)| node.value().widget().downcast::<Swappable>().is_some())
+            .map(|(element_id, _)| element_id)
+            .expect("should have found the swappable widget");
+
+        tester.engine_mut().mark_dirty(element_id);
+        tester.pump();
+    }
+
+    /// Returns the widget of the given type that the animated widget built.
+    fn built<W>(tester: &WidgetTester) -> Rc<W>
+    where
+        W: AnyWidget,
+    {
+        tester
+            .find_by_type::<W>()
+            .and_then(|element| element.widget().downcast::<W>())
+            .expect("should have found the built widget")
+    }
+
+    #[test]
+    pub fn animated_sized_box_transitions_to_its_new_size() {
+        let (mut tester, child) = tester_with(build! {
+            <AnimatedSizedBox> {
+                width: Some(0.0),
+                height: Some(100.0),
+                duration: DURATION,
+            }
+        });
+
+        replace(
+            &mut tester,
+            &child,
+            build! {
+                <AnimatedSizedBox> {
+                    width: Some(100.0),
+                    height: Some(0.0),
+                    duration: DURATION,
+                }
+            },
+        );
+
+        tester.pump_for(HALF_WAY);
+
+        let sized_box = built::<SizedBox>(&tester);
+
+        assert_eq!(sized_box.width, Some(50.0));
+        assert_eq!(sized_box.height, Some(50.0));
+
+        tester.pump_for(DURATION);
+
+        let sized_box = built::<SizedBox>(&tester);
+
+        assert_eq!(sized_box.width, Some(100.0));
+        assert_eq!(sized_box.height, Some(0.0));
+    }
+
+    #[test]
+    pub fn animated_padding_transitions_to_its_new_padding() {
+        let (mut tester, child) = tester_with(build! {
+            <AnimatedPadding> {
+                padding: EdgeInsets::all(0.0),
+                duration: DURATION,
+            }
+        });
+
+        replace(
+            &mut tester,
+            &child,
+            build! {
+                <AnimatedPadding> {
+                    padding: EdgeInsets::all(20.0),
+                    duration: DURATION,
+                }
+            },
+        );
+
+        tester.pump_for(HALF_WAY);
+
+        assert_eq!(built::<Padding>(&tester).padding, EdgeInsets::all(10.0));
+
+        tester.pump_for(DURATION);
+
+        assert_eq!(built::<Padding>(&tester).padding, EdgeInsets::all(20.0));
+    }
+
+    #[test]
+    pub fn animated_align_transitions_to_its_new_alignment() {
+        let (mut tester, child) = tester_with(build! {
+            <AnimatedAlign> {
+                alignment: Alignment::TOP_LEFT,
+                duration: DURATION,
+            }
+        });
+
+        replace(
+            &mut tester,
+            &child,
+            build! {
+                <AnimatedAlign> {
+                    alignment: Alignment::BOTTOM_RIGHT,
+                    duration: DURATION,
+                }
+            },
+        );
+
+        tester.pump_for(HALF_WAY);
+
+        assert_eq!(built::<Align>(&tester).alignment, Alignment::CENTER);
+
+        tester.pump_for(DURATION);
+
+        assert_eq!(built::<Align>(&tester).alignment, Alignment::BOTTOM_RIGHT);
+    }
+
+    #[test]
+    pub fn animated_colored_box_transitions_to_its_new_color() {
+        let (mut tester, child) = tester_with(build! {
+            <AnimatedColoredBox> {
+                color: Color::from_rgb((0.0, 0.0, 0.0)),
+                duration: DURATION,
+            }
+        });
+
+        replace(
+            &mut tester,
+            &child,
+            build! {
+                <AnimatedColoredBox> {
+                    color: Color::from_rgb((1.0, 0.5, 0.0)),
+                    duration: DURATION,
+                }
+            },
+        );
+
+        tester.pump_for(HALF_WAY);
+
+        assert_eq!(
+            built::<ColoredBox>(&tester).color,
+            Color::from_rgb((0.5, 0.25, 0.0))
+        );
+
+        tester.pump_for(DURATION);
+
+        assert_eq!(
+            built::<ColoredBox>(&tester).color,
+            Color::from_rgb((1.0, 0.5, 0.0))
+        );
+    }
+
+    #[test]
+    pub fn animated_opacity_transitions_to_its_new_opacity() {
+        let (mut tester, child) = tester_with(build! {
+            <AnimatedOpacity> {
+                opacity: 1.0,
+                duration: DURATION,
+            }
+        });
+
+        replace(
+            &mut tester,
+            &child,
+            build! {
+                <AnimatedOpacity> {
+                    opacity: 0.0,
+                    duration: DURATION,
+                }
+            },
+        );
+
+        tester.pump_for(HALF_WAY);
+
+        assert_eq!(built::<Opacity>(&tester).opacity, 0.5);
+
+        tester.pump_for(DURATION);
+
+        assert_eq!(built::<Opacity>(&tester).opacity, 0.0);
+    }
+
+    #[test]
+    pub fn retargets_from_the_current_value() {
+        let mut tween = ImplicitTween::new(0.0);
+
+        assert!(
+            !tween.retarget(&0.0, 1.0),
+            "should not retarget to the same value"
+        );
+
+        assert!(tween.retarget(&10.0, 1.0));
+        assert_eq!(tween.value(0.5), 5.0);
+
+        // Interrupt the transition half way through
+        assert!(tween.retarget(&0.0, 0.5));
+        assert_eq!(tween.value(0.0), 5.0);
+        assert_eq!(tween.value(1.0), 0.0);
+    }
+}
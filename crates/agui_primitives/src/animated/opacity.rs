@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+use agui_core::{animation::Curve, widget::Widget};
+use agui_elements::stateful::{StatefulBuildContext, StatefulWidget, WidgetState};
+use agui_macros::StatefulWidget;
+
+use crate::opacity::Opacity;
+
+use super::{AnimationCurve, ImplicitAnimation, ImplicitTween};
+
+/// An [`Opacity`] that fades to its new `opacity` whenever it changes.
+#[derive(StatefulWidget, Debug)]
+pub struct AnimatedOpacity {
+    pub opacity: f32,
+
+    pub duration: Duration,
+    #[prop(default, transform = |curve: impl Curve + 'static| AnimationCurve::new(curve))]
+    pub curve: AnimationCurve,
+
+    #[prop(into, default)]
+    pub child: Option<Widget>,
+}
+
+impl StatefulWidget for AnimatedOpacity {
+    type State = AnimatedOpacityState;
+
+    fn create_state(&self) -> Self::State {
+        AnimatedOpacityState {
+            animation: ImplicitAnimation::default(),
+
+            opacity: ImplicitTween::new(self.opacity),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AnimatedOpacityState {
+    animation: ImplicitAnimation,
+
+    opacity: ImplicitTween<f32>,
+}
+
+impl WidgetState for AnimatedOpacityState {
+    type Widget = AnimatedOpacity;
+
+    fn init_state(&mut self, ctx: &mut StatefulBuildContext<Self>) {
+        let duration = ctx.widget.duration;
+
+        self.animation.init(ctx, duration);
+    }
+
+    fn updated(&mut self, ctx: &mut StatefulBuildContext<Self>, _: &Self::Widget) {
+        let t = self.animation.progress(&ctx.widget.curve);
+
+        if self.opacity.retarget(&ctx.widget.opacity, t) {
+            self.animation.restart(ctx.widget.duration);
+        }
+    }
+
+    fn build(&mut self, ctx: &mut StatefulBuildContext<Self>) -> Widget {
+        let t = self.animation.progress(&ctx.widget.curve);
+
+        Opacity {
+            opacity: self.opacity.value(t),
+
+            child: ctx.widget.child.clone(),
+        }
+        .into()
+    }
+}
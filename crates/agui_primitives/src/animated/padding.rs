@@ -0,0 +1,70 @@
+use std::time::Duration;
+
+use agui_core::{animation::Curve, unit::EdgeInsets, widget::Widget};
+use agui_elements::stateful::{StatefulBuildContext, StatefulWidget, WidgetState};
+use agui_macros::StatefulWidget;
+
+use crate::padding::Padding;
+
+use super::{AnimationCurve, ImplicitAnimation, ImplicitTween};
+
+/// A [`Padding`] that animates to its new `padding` whenever it changes.
+#[derive(StatefulWidget, Debug)]
+pub struct AnimatedPadding {
+    pub padding: EdgeInsets,
+
+    pub duration: Duration,
+    #[prop(default, transform = |curve: impl Curve + 'static| AnimationCurve::new(curve))]
+    pub curve: AnimationCurve,
+
+    #[prop(into, default)]
+    pub child: Option<Widget>,
+}
+
+impl StatefulWidget for AnimatedPadding {
+    type State = AnimatedPaddingState;
+
+    fn create_state(&self) -> Self::State {
+        AnimatedPaddingState {
+            animation: ImplicitAnimation::default(),
+
+            padding: ImplicitTween::new(self.padding),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AnimatedPaddingState {
+    animation: ImplicitAnimation,
+
+    padding: ImplicitTween<EdgeInsets>,
+}
+
+impl WidgetState for AnimatedPaddingState {
+    type Widget = AnimatedPadding;
+
+    fn init_state(&mut self, ctx: &mut StatefulBuildContext<Self>) {
+        let duration = ctx.widget.duration;
+
+        self.animation.init(ctx, duration);
+    }
+
+    fn updated(&mut self, ctx: &mut StatefulBuildContext<Self>, _: &Self::Widget) {
+        let t = self.animation.progress(&ctx.widget.curve);
+
+        if self.padding.retarget(&ctx.widget.padding, t) {
+            self.animation.restart(ctx.widget.duration);
+        }
+    }
+
+    fn build(&mut self, ctx: &mut StatefulBuildContext<Self>) -> Widget {
+        let t = self.animation.progress(&ctx.widget.curve);
+
+        Padding {
+            padding: self.padding.value(t),
+
+            child: ctx.widget.child.clone(),
+        }
+        .into()
+    }
+}
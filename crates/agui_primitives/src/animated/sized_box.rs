@@ -0,0 +1,81 @@
+use std::time::Duration;
+
+use agui_core::{animation::Curve, widget::Widget};
+use agui_elements::stateful::{StatefulBuildContext, StatefulWidget, WidgetState};
+use agui_macros::StatefulWidget;
+
+use crate::sized_box::SizedBox;
+
+use super::{AnimationCurve, ImplicitAnimation, ImplicitTween};
+
+/// A [`SizedBox`] that animates to its new `width` and `height` whenever they change. A
+/// dimension that is added or removed is applied immediately.
+#[derive(StatefulWidget, Debug)]
+pub struct AnimatedSizedBox {
+    #[prop(default)]
+    pub width: Option<f32>,
+    #[prop(default)]
+    pub height: Option<f32>,
+
+    pub duration: Duration,
+    #[prop(default, transform = |curve: impl Curve + 'static| AnimationCurve::new(curve))]
+    pub curve: AnimationCurve,
+
+    #[prop(into, default)]
+    pub child: Option<Widget>,
+}
+
+impl StatefulWidget for AnimatedSizedBox {
+    type State = AnimatedSizedBoxState;
+
+    fn create_state(&self) -> Self::State {
+        AnimatedSizedBoxState {
+            animation: ImplicitAnimation::default(),
+
+            width: ImplicitTween::new(self.width),
+            height: ImplicitTween::new(self.height),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AnimatedSizedBoxState {
+    animation: ImplicitAnimation,
+
+    width: ImplicitTween<Option<f32>>,
+    height: ImplicitTween<Option<f32>>,
+}
+
+impl WidgetState for AnimatedSizedBoxState {
+    type Widget = AnimatedSizedBox;
+
+    fn init_state(&mut self, ctx: &mut StatefulBuildContext<Self>) {
+        let duration = ctx.widget.duration;
+
+        self.animation.init(ctx, duration);
+    }
+
+    fn updated(&mut self, ctx: &mut StatefulBuildContext<Self>, _: &Self::Widget) {
+        let t = self.animation.progress(&ctx.widget.curve);
+
+        // Both must be retargeted, so this can't short-circuit
+        let is_changed =
+            self.width.retarget(&ctx.widget.width, t) | self.height.retarget(&ctx.widget.height, t);
+
+        if is_changed {
+            self.animation.restart(ctx.widget.duration);
+        }
+    }
+
+    fn build(&mut self, ctx: &mut StatefulBuildContext<Self>) -> Widget {
+        let t = self.animation.progress(&ctx.widget.curve);
+
+        SizedBox {
+            width: self.width.value(t),
+            height: self.height.value(t),
+
+            child: ctx.widget.child.clone(),
+        }
+        .into()
+    }
+}
@@ -66,7 +66,7 @@ mod tests {
             .with_root(Builder::new(|_| TestWidget::default().into()))
             .build();
 
-        engine.update();
+        engine.update().unwrap();
 
         assert!(
             engine.query().by_type::<TestWidget>().next().is_some(),
@@ -345,7 +345,7 @@ impl Flex {
 
             if flex > 0.0 {
                 total_flex += flex;
-                last_flexible_child = Some(child.element_id());
+                last_flexible_child = Some(child.render_object_id());
             } else {
                 let inner_constraints = if self.cross_axis_alignment == CrossAxisAlignment::Stretch
                 {
@@ -383,7 +383,7 @@ impl Flex {
 
                 if flex > 0.0 {
                     let max_child_extent = if can_flex {
-                        if Some(child.element_id()) == last_flexible_child {
+                        if Some(child.render_object_id()) == last_flexible_child {
                             free_space - allocated_flex_space
                         } else {
                             space_per_flex * flex
@@ -1,9 +1,11 @@
 pub mod align;
+pub mod animated;
 pub mod builder;
 pub mod clip;
 pub mod colored_box;
 pub mod flex;
 pub mod intrinsic;
+pub mod opacity;
 pub mod padding;
 pub mod sized_box;
 pub mod stack;
@@ -0,0 +1,37 @@
+use agui_core::{
+    render::{CanvasPainter, Paint},
+    unit::{Color, Shape},
+    widget::Widget,
+};
+use agui_elements::paint::WidgetPaint;
+use agui_macros::PaintWidget;
+
+/// Paints its child into a layer that is blended with the given opacity, where `0.0` is fully
+/// transparent and `1.0` is fully opaque.
+#[derive(PaintWidget, Debug)]
+pub struct Opacity {
+    pub opacity: f32,
+
+    #[prop(into, default)]
+    pub child: Option<Widget>,
+}
+
+impl WidgetPaint for Opacity {
+    fn child(&self) -> Option<Widget> {
+        self.child.clone()
+    }
+
+    fn paint(&self, mut canvas: CanvasPainter) {
+        let brush = canvas.add_paint(Paint {
+            color: Color {
+                alpha: self.opacity.clamp(0.0, 1.0),
+
+                ..Color::default()
+            },
+
+            ..Paint::default()
+        });
+
+        canvas.start_layer(&brush, Shape::Rect);
+    }
+}
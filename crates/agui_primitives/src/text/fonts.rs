@@ -92,6 +92,55 @@ impl AvailableFonts {
     }
 }
 
+/// The data of the fonts that have been loaded, by the [`Font`] each was registered as.
+///
+/// Backends that draw with the same registry resolve a [`Font`] to the same typeface, so that a
+/// widget tree measured by one of them is drawn identically by the others.
+#[cfg(feature = "ab_glyph")]
+#[derive(Default)]
+pub struct FontRegistry {
+    fonts: rustc_hash::FxHashMap<Font, ab_glyph::FontArc>,
+
+    default_font: Option<Font>,
+}
+
+#[cfg(feature = "ab_glyph")]
+impl FontRegistry {
+    /// Registers the font. The first font that is added is used for any text which doesn't
+    /// specify one.
+    pub fn add_font(&mut self, font: ab_glyph::FontArc) -> Font {
+        let font_id = Font::by_id(self.fonts.len());
+
+        self.fonts.insert(font_id, font);
+
+        if self.default_font.is_none() {
+            self.default_font = Some(font_id);
+        }
+
+        font_id
+    }
+
+    pub fn get(&self, font: Font) -> Option<&ab_glyph::FontArc> {
+        self.fonts.get(&font)
+    }
+
+    pub fn get_default(&self) -> Option<&ab_glyph::FontArc> {
+        self.default_font.and_then(|font| self.get(font))
+    }
+
+    /// Resolves the font that text should be drawn with, falling back to the default font.
+    pub fn resolve(&self, font: Option<Font>) -> Option<Font> {
+        match font {
+            Some(font) if self.fonts.contains_key(&font) => Some(font),
+            _ => self.default_font,
+        }
+    }
+
+    pub fn get_or_default(&self, font: Option<Font>) -> Option<&ab_glyph::FontArc> {
+        self.resolve(font).and_then(|font| self.get(font))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::cell::RefCell;
@@ -148,7 +197,7 @@ mod tests {
             })
             .build();
 
-        engine.update();
+        engine.update().unwrap();
 
         TEST_HOOK.with(|result| {
             assert_ne!(
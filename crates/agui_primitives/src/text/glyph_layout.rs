@@ -0,0 +1,352 @@
+#[cfg(feature = "ab_glyph")]
+use std::borrow::Cow;
+
+#[cfg(feature = "ab_glyph")]
+use agui_core::unit::{Constraints, TextStyle};
+use agui_core::unit::{IntrinsicDimension, Size};
+
+#[cfg(feature = "ab_glyph")]
+use crate::text::{fonts::FontRegistry, layout_controller::TextLayoutDelegate};
+
+/// The measurements of a font at a particular size, which [`layout_text`] uses to place glyphs.
+pub trait GlyphMetrics {
+    type GlyphId: Copy;
+
+    fn glyph_id(&self, ch: char) -> Self::GlyphId;
+
+    /// The distance the pen moves after placing the glyph.
+    fn h_advance(&self, glyph_id: Self::GlyphId) -> f32;
+
+    /// The adjustment to the distance between two adjacent glyphs.
+    fn kerning(&self, _first: Self::GlyphId, _second: Self::GlyphId) -> f32 {
+        0.0
+    }
+
+    /// The distance from the top of a line to its baseline.
+    fn ascent(&self) -> f32;
+
+    /// The distance from the top of one line to the top of the next.
+    fn line_height(&self) -> f32;
+}
+
+/// A glyph placed by [`layout_text`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionedGlyph<G> {
+    pub id: G,
+
+    /// The character the glyph was chosen for.
+    pub ch: char,
+
+    /// The horizontal position of the glyph's origin, relative to the left of the text.
+    pub x: f32,
+
+    /// The vertical position of the glyph's baseline, relative to the top of the text.
+    pub baseline: f32,
+}
+
+/// Places each glyph of the text, calling `on_glyph` for every one of them, and returns the size
+/// of the laid out text.
+///
+/// Lines are wrapped when they would overflow `max_width`. This is naive, and doesn't account for
+/// word boundaries.
+pub fn layout_text<M>(
+    metrics: &M,
+    max_width: f32,
+    text: &str,
+    mut on_glyph: impl FnMut(PositionedGlyph<M::GlyphId>),
+) -> Size
+where
+    M: GlyphMetrics + ?Sized,
+{
+    let ascent = metrics.ascent();
+    let line_height = metrics.line_height();
+
+    let mut width = 0.0_f32;
+
+    let mut pen_x = 0.0;
+    let mut pen_y = 0.0;
+
+    let mut last_glyph_id = None;
+
+    for ch in text.chars() {
+        if ch == '\n' {
+            width = width.max(pen_x);
+
+            pen_x = 0.0;
+            pen_y += line_height;
+            last_glyph_id = None;
+            continue;
+        }
+
+        let glyph_id = metrics.glyph_id(ch);
+
+        if let Some(last_glyph_id) = last_glyph_id {
+            pen_x += metrics.kerning(last_glyph_id, glyph_id);
+        }
+
+        let advance = metrics.h_advance(glyph_id);
+
+        if pen_x > 0.0 && pen_x + advance > max_width {
+            width = width.max(pen_x);
+
+            pen_x = 0.0;
+            pen_y += line_height;
+        }
+
+        on_glyph(PositionedGlyph {
+            id: glyph_id,
+            ch,
+            x: pen_x,
+            baseline: pen_y + ascent,
+        });
+
+        pen_x += advance;
+        last_glyph_id = Some(glyph_id);
+    }
+
+    Size::new(width.max(pen_x), pen_y + line_height)
+}
+
+/// Measures the text along the given dimension, for use in
+/// [`TextLayoutDelegate::compute_intrinsic_size`](super::layout_controller::TextLayoutDelegate::compute_intrinsic_size).
+pub fn intrinsic_size<M>(
+    metrics: &M,
+    text: &str,
+    dimension: IntrinsicDimension,
+    cross_axis: f32,
+) -> f32
+where
+    M: GlyphMetrics + ?Sized,
+{
+    match dimension {
+        // Since wrapping may happen between any two glyphs, the minimum intrinsic width is the
+        // width of the widest glyph
+        IntrinsicDimension::MinWidth => layout_text(metrics, 0.0, text, |_| {}).width,
+
+        // The maximum intrinsic width is the width of the widest line without wrapping
+        IntrinsicDimension::MaxWidth => layout_text(metrics, f32::INFINITY, text, |_| {}).width,
+
+        // The height of the text is determined entirely by the wrapping necessary to fit within
+        // the given `cross_axis` width
+        IntrinsicDimension::MinHeight | IntrinsicDimension::MaxHeight => {
+            layout_text(metrics, cross_axis, text, |_| {}).height
+        }
+    }
+}
+
+/// Measures an [`ab_glyph`] font at a particular size.
+#[cfg(feature = "ab_glyph")]
+#[derive(Debug, Clone, Copy)]
+pub struct FontMetrics<'font, F> {
+    font: &'font F,
+    scale: f32,
+}
+
+#[cfg(feature = "ab_glyph")]
+impl<'font, F: ab_glyph::Font> FontMetrics<'font, F> {
+    /// Returns `None` if the font doesn't define how many units make up its em square.
+    pub fn new(font: &'font F, size: f32) -> Option<Self> {
+        Some(Self {
+            font,
+            scale: size / font.units_per_em()?,
+        })
+    }
+
+    pub fn font(&self) -> &'font F {
+        self.font
+    }
+
+    /// The factor that converts font units into the size the font is being measured at.
+    pub fn scale(&self) -> f32 {
+        self.scale
+    }
+}
+
+#[cfg(feature = "ab_glyph")]
+impl<F: ab_glyph::Font> GlyphMetrics for FontMetrics<'_, F> {
+    type GlyphId = ab_glyph::GlyphId;
+
+    fn glyph_id(&self, ch: char) -> Self::GlyphId {
+        self.font.glyph_id(ch)
+    }
+
+    fn h_advance(&self, glyph_id: Self::GlyphId) -> f32 {
+        self.font.h_advance_unscaled(glyph_id) * self.scale
+    }
+
+    fn kerning(&self, first: Self::GlyphId, second: Self::GlyphId) -> f32 {
+        self.font.kern_unscaled(first, second) * self.scale
+    }
+
+    fn ascent(&self) -> f32 {
+        self.font.ascent_unscaled() * self.scale
+    }
+
+    fn line_height(&self) -> f32 {
+        (self.font.ascent_unscaled() - self.font.descent_unscaled() + self.font.line_gap_unscaled())
+            * self.scale
+    }
+}
+
+/// Measures text using the fonts in a [`FontRegistry`], so that it is laid out exactly as it will
+/// be drawn by the backends sharing the registry.
+#[cfg(feature = "ab_glyph")]
+pub struct GlyphLayoutDelegate {
+    fonts: std::sync::Arc<parking_lot::Mutex<FontRegistry>>,
+}
+
+#[cfg(feature = "ab_glyph")]
+impl GlyphLayoutDelegate {
+    pub fn new(fonts: std::sync::Arc<parking_lot::Mutex<FontRegistry>>) -> Self {
+        Self { fonts }
+    }
+}
+
+#[cfg(feature = "ab_glyph")]
+impl TextLayoutDelegate for GlyphLayoutDelegate {
+    fn compute_intrinsic_size(
+        &self,
+        font_style: &TextStyle,
+        text: Cow<'static, str>,
+        dimension: IntrinsicDimension,
+        cross_axis: f32,
+    ) -> f32 {
+        let fonts = self.fonts.lock();
+
+        let Some(metrics) = fonts
+            .get_or_default(font_style.font)
+            .and_then(|font| FontMetrics::new(font, font_style.size))
+        else {
+            return 0.0;
+        };
+
+        intrinsic_size(&metrics, &text, dimension, cross_axis)
+    }
+
+    fn compute_layout(
+        &self,
+        font_style: &TextStyle,
+        text: Cow<'static, str>,
+        constraints: Constraints,
+    ) -> Size {
+        let fonts = self.fonts.lock();
+
+        let Some(metrics) = fonts
+            .get_or_default(font_style.font)
+            .and_then(|font| FontMetrics::new(font, font_style.size))
+        else {
+            return Size::ZERO;
+        };
+
+        layout_text(&metrics, constraints.max_width(), &text, |_| {})
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use agui_core::unit::{IntrinsicDimension, Size};
+
+    use super::{intrinsic_size, layout_text, GlyphMetrics};
+
+    /// Every glyph is 10 wide, except that `A` and `V` are kerned together.
+    struct FixedMetrics;
+
+    impl GlyphMetrics for FixedMetrics {
+        type GlyphId = char;
+
+        fn glyph_id(&self, ch: char) -> Self::GlyphId {
+            ch
+        }
+
+        fn h_advance(&self, _: Self::GlyphId) -> f32 {
+            10.0
+        }
+
+        fn kerning(&self, first: Self::GlyphId, second: Self::GlyphId) -> f32 {
+            if (first, second) == ('A', 'V') {
+                -2.0
+            } else {
+                0.0
+            }
+        }
+
+        fn ascent(&self) -> f32 {
+            8.0
+        }
+
+        fn line_height(&self) -> f32 {
+            12.0
+        }
+    }
+
+    #[test]
+    pub fn wraps_text_to_fit_the_constraints() {
+        assert_eq!(
+            layout_text(&FixedMetrics, f32::INFINITY, "hello", |_| {}),
+            Size::new(50.0, 12.0)
+        );
+
+        assert_eq!(
+            layout_text(&FixedMetrics, f32::INFINITY, "hello\nworld!", |_| {}),
+            Size::new(60.0, 24.0),
+            "should have broken lines at newlines"
+        );
+
+        let mut glyphs = Vec::new();
+
+        assert_eq!(
+            layout_text(&FixedMetrics, 35.0, "abcdefg", |glyph| glyphs.push((
+                glyph.ch,
+                glyph.x,
+                glyph.baseline
+            ))),
+            Size::new(30.0, 36.0),
+            "should have wrapped the text to fit the width"
+        );
+
+        assert_eq!(glyphs[3], ('d', 0.0, 20.0));
+        assert_eq!(glyphs[6], ('g', 0.0, 32.0));
+
+        assert_eq!(
+            layout_text(&FixedMetrics, 0.0, "ab", |_| {}),
+            Size::new(10.0, 24.0),
+            "should always place at least one glyph on each line"
+        );
+    }
+
+    #[test]
+    pub fn applies_kerning_between_glyphs() {
+        let mut glyphs = Vec::new();
+
+        assert_eq!(
+            layout_text(&FixedMetrics, f32::INFINITY, "AVA", |glyph| glyphs
+                .push(glyph.x)),
+            Size::new(28.0, 12.0)
+        );
+
+        assert_eq!(glyphs, [0.0, 8.0, 18.0]);
+    }
+
+    #[test]
+    pub fn computes_intrinsic_sizes() {
+        let text = "abc\nde";
+
+        assert_eq!(
+            intrinsic_size(&FixedMetrics, text, IntrinsicDimension::MinWidth, 0.0),
+            10.0,
+            "min width should be the widest glyph"
+        );
+
+        assert_eq!(
+            intrinsic_size(&FixedMetrics, text, IntrinsicDimension::MaxWidth, 0.0),
+            30.0,
+            "max width should be the widest line"
+        );
+
+        assert_eq!(
+            intrinsic_size(&FixedMetrics, text, IntrinsicDimension::MinHeight, 20.0),
+            36.0,
+            "height should account for wrapping within the cross axis"
+        );
+    }
+}
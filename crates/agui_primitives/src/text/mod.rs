@@ -17,6 +17,7 @@ use crate::text::layout_controller::{TextLayoutController, TextLayoutDelegate};
 
 pub mod edit;
 pub mod fonts;
+pub mod glyph_layout;
 pub mod layout_controller;
 pub mod query;
 
@@ -82,7 +82,7 @@ mod tests {
             })
             .build();
 
-        engine.update();
+        engine.update().unwrap();
 
         assert_eq!(
             engine
@@ -1,5 +1,5 @@
 use agui_core::{
-    element::ContextElement,
+    element::{ContextElement, ContextElements},
     plugin::{
         context::{
             PluginElementBuildContext, PluginElementMountContext, PluginElementRemountContext,
@@ -0,0 +1,173 @@
+use std::sync::Arc;
+
+use agui_core::{
+    engine::{Engine, EngineError},
+    unit::Size,
+};
+use parking_lot::Mutex;
+use rustc_hash::FxHashMap;
+
+use crate::{RenderViewId, ViewRenderer};
+
+/// A view that draws its frames into a buffer, and only presents them to its target when it is
+/// rendered.
+pub trait BufferedView {
+    /// Draws the engine's render tree into the buffer, replacing the previous frame.
+    fn redraw(&mut self, engine: &Engine) -> Result<(), EngineError>;
+
+    fn resize(&mut self, size: Size);
+
+    /// Presents the most recently drawn frame to the view's target.
+    fn present(&self);
+}
+
+/// Renders a [`BufferedView`], redrawing it only when asked to so that rendering merely presents
+/// the last frame.
+pub struct BufferedViewRenderer<V> {
+    render_view_id: RenderViewId,
+
+    view: Mutex<V>,
+}
+
+impl<V: BufferedView> BufferedViewRenderer<V> {
+    pub fn new(render_view_id: RenderViewId, view: V) -> Self {
+        Self {
+            render_view_id,
+
+            view: Mutex::new(view),
+        }
+    }
+
+    pub fn render_view_id(&self) -> RenderViewId {
+        self.render_view_id
+    }
+
+    /// Draws the engine's render tree into the view's buffer. The new frame is not presented
+    /// until the view is rendered.
+    pub fn redraw(&self, engine: &Engine) -> Result<(), EngineError> {
+        self.view.lock().redraw(engine)
+    }
+}
+
+impl<V: BufferedView> ViewRenderer for BufferedViewRenderer<V> {
+    fn resize(&self, size: Size) {
+        self.view.lock().resize(size);
+    }
+
+    fn render(&self) {
+        self.view.lock().present();
+    }
+}
+
+/// The views that have been bound to a renderer, so that they may all be redrawn at once.
+pub struct BufferedViews<V> {
+    views: Mutex<FxHashMap<RenderViewId, Arc<BufferedViewRenderer<V>>>>,
+}
+
+impl<V> Default for BufferedViews<V> {
+    fn default() -> Self {
+        Self {
+            views: Mutex::default(),
+        }
+    }
+}
+
+impl<V> BufferedViews<V>
+where
+    V: BufferedView + 'static,
+{
+    /// Binds the view, replacing any view that was previously bound with the same ID.
+    pub fn bind(&self, render_view_id: RenderViewId, view: V) -> Arc<BufferedViewRenderer<V>> {
+        let view_renderer = Arc::new(BufferedViewRenderer::new(render_view_id, view));
+
+        self.views
+            .lock()
+            .insert(render_view_id, Arc::clone(&view_renderer));
+
+        view_renderer
+    }
+
+    /// Redraws every bound view from the engine's render tree. The new frames are not presented
+    /// until each view is rendered.
+    pub fn redraw(&self, engine: &Engine) -> Result<(), EngineError> {
+        for view in self.views.lock().values() {
+            view.redraw(engine)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use agui_core::{
+        element::mock::DummyWidget,
+        engine::{Engine, EngineError},
+        unit::Size,
+    };
+
+    use crate::{RenderViewId, ViewRenderer};
+
+    use super::{BufferedView, BufferedViews};
+
+    #[derive(Default)]
+    struct TestView {
+        size: Size,
+
+        frame: usize,
+        presented: Rc<RefCell<Vec<(usize, Size)>>>,
+    }
+
+    impl BufferedView for TestView {
+        fn redraw(&mut self, _: &Engine) -> Result<(), EngineError> {
+            self.frame += 1;
+
+            Ok(())
+        }
+
+        fn resize(&mut self, size: Size) {
+            self.size = size;
+        }
+
+        fn present(&self) {
+            self.presented.borrow_mut().push((self.frame, self.size));
+        }
+    }
+
+    #[test]
+    pub fn presents_frames_only_when_rendered() {
+        let engine = Engine::builder().with_root(DummyWidget).build();
+
+        let presented = Rc::default();
+
+        let views = BufferedViews::default();
+
+        let view = views.bind(
+            RenderViewId::default(),
+            TestView {
+                presented: Rc::clone(&presented),
+                ..TestView::default()
+            },
+        );
+
+        views.redraw(&engine).unwrap();
+
+        assert!(
+            presented.borrow().is_empty(),
+            "should not have presented the frame before rendering"
+        );
+
+        view.resize(Size::new(10.0, 10.0));
+        view.render();
+
+        views.redraw(&engine).unwrap();
+        view.render();
+
+        assert_eq!(
+            *presented.borrow(),
+            [(1, Size::new(10.0, 10.0)), (2, Size::new(10.0, 10.0))]
+        );
+    }
+}
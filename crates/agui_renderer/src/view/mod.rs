@@ -1,8 +1,10 @@
+mod buffered;
 mod current_view;
 mod element;
 mod id;
 mod widget;
 
+pub use buffered::*;
 pub use current_view::*;
 pub use id::*;
 pub use widget::*;
@@ -0,0 +1,44 @@
+use std::{fs, io, path::Path, sync::Arc};
+
+use parking_lot::Mutex;
+
+/// An SVG document that rendered frames are presented to.
+///
+/// Documents are cheap to clone, and every clone refers to the same markup, so one may be bound
+/// to a view while another is used to read back what it presented.
+#[derive(Clone, Default)]
+pub struct SvgDocument {
+    markup: Arc<Mutex<String>>,
+}
+
+impl SvgDocument {
+    /// Creates a document that is empty until a frame is presented to it.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the markup of the most recently presented frame.
+    pub fn markup(&self) -> String {
+        self.markup.lock().clone()
+    }
+
+    /// Writes the markup of the most recently presented frame to the given path.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        fs::write(path, self.markup.lock().as_bytes())
+    }
+
+    pub(crate) fn present(&self, frame: &str) {
+        let mut markup = self.markup.lock();
+
+        markup.clear();
+        markup.push_str(frame);
+    }
+}
+
+impl std::fmt::Debug for SvgDocument {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SvgDocument")
+            .field("len", &self.markup.lock().len())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use ab_glyph::FontArc;
+use agui_core::unit::Font;
+use agui_primitives::text::{fonts::FontRegistry, glyph_layout::GlyphLayoutDelegate};
+use parking_lot::Mutex;
+use rustc_hash::FxHashMap;
+
+/// The fonts that text is laid out with, along with the family each is referenced by in
+/// documents.
+#[derive(Default)]
+pub struct SvgFonts {
+    registry: Arc<Mutex<FontRegistry>>,
+
+    families: FxHashMap<Font, String>,
+}
+
+impl SvgFonts {
+    pub fn add_font(&mut self, family: String, font: FontArc) -> Font {
+        let font_id = self.registry.lock().add_font(font);
+
+        self.families.insert(font_id, family);
+
+        font_id
+    }
+
+    pub fn registry(&self) -> &Arc<Mutex<FontRegistry>> {
+        &self.registry
+    }
+
+    pub fn family(&self, font: Font) -> Option<&str> {
+        self.families.get(&font).map(String::as_str)
+    }
+
+    pub fn text_layout_delegate(&self) -> GlyphLayoutDelegate {
+        GlyphLayoutDelegate::new(Arc::clone(&self.registry))
+    }
+}
@@ -0,0 +1,11 @@
+mod document;
+mod fonts;
+mod path;
+mod renderer;
+mod textures;
+mod view;
+mod writer;
+
+pub use document::SvgDocument;
+pub use renderer::SvgRenderer;
+pub use view::{SvgView, SvgViewRenderer};
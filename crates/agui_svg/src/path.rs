@@ -0,0 +1,71 @@
+use agui_core::unit::{Offset, Rect, Size};
+use lyon::path::{Event, Path};
+
+/// Converts a path into the contents of an SVG `d` attribute.
+pub fn to_path_data(path: &Path) -> String {
+    let mut commands = Vec::new();
+
+    for event in path.iter() {
+        commands.push(match event {
+            Event::Begin { at } => format!("M{} {}", format_number(at.x), format_number(at.y)),
+
+            Event::Line { to, .. } => format!("L{} {}", format_number(to.x), format_number(to.y)),
+
+            Event::Quadratic { ctrl, to, .. } => format!(
+                "Q{} {} {} {}",
+                format_number(ctrl.x),
+                format_number(ctrl.y),
+                format_number(to.x),
+                format_number(to.y)
+            ),
+
+            Event::Cubic {
+                ctrl1, ctrl2, to, ..
+            } => format!(
+                "C{} {} {} {} {} {}",
+                format_number(ctrl1.x),
+                format_number(ctrl1.y),
+                format_number(ctrl2.x),
+                format_number(ctrl2.y),
+                format_number(to.x),
+                format_number(to.y)
+            ),
+
+            Event::End { close: true, .. } => String::from("Z"),
+
+            Event::End { close: false, .. } => continue,
+        });
+    }
+
+    commands.join(" ")
+}
+
+/// Formats a value with at most three decimal places, so that documents are not sensitive to
+/// floating point noise.
+pub fn format_number(value: f32) -> String {
+    let formatted = format!("{value:.3}");
+    let formatted = formatted.trim_end_matches('0').trim_end_matches('.');
+
+    // Avoid a distinction between zero and negative zero.
+    if formatted == "-0" {
+        String::from("0")
+    } else {
+        String::from(formatted)
+    }
+}
+
+/// Limits unbounded rects, such as those of the root render object, to the area of the document
+/// so that they can be written. The origin is the position of the rect's coordinate space within
+/// the document. Bounded rects are returned unchanged.
+pub fn bound_rect(rect: Rect, origin: Offset, size: Size) -> Rect {
+    if rect.width.is_finite() && rect.height.is_finite() {
+        return rect;
+    }
+
+    let left = rect.left.max(-origin.x);
+    let top = rect.top.max(-origin.y);
+    let right = (rect.left + rect.width).min(size.width - origin.x);
+    let bottom = (rect.top + rect.height).min(size.height - origin.y);
+
+    Rect::new(left, top, (right - left).max(0.0), (bottom - top).max(0.0))
+}
@@ -0,0 +1,268 @@
+use std::sync::Arc;
+
+use ab_glyph::{FontArc, InvalidFont};
+use agui_core::{
+    engine::{Engine, EngineError},
+    unit::{Font, Size, TextureId},
+};
+use agui_primitives::text::glyph_layout::GlyphLayoutDelegate;
+use agui_renderer::{BufferedViews, RenderViewId, Renderer, ViewRenderer};
+use parking_lot::Mutex;
+
+use crate::{
+    document::SvgDocument, fonts::SvgFonts, textures::SvgTextures, view::SvgView, writer::SvgWriter,
+};
+
+/// Renders views into [`SvgDocument`]s, keeping shapes and text as vectors.
+///
+/// Shapes are written as paths, layers as groups clipped to their shape, and text as `<text>`
+/// elements. Text is broken into lines using the metrics of the fonts added to the renderer,
+/// so it's placed correctly as long as whatever displays the document has the same fonts. Use
+/// [`SvgRenderer::text_layout_delegate`] to lay out the widget tree with those fonts.
+#[derive(Default)]
+pub struct SvgRenderer {
+    fonts: Arc<Mutex<SvgFonts>>,
+    textures: Arc<Mutex<SvgTextures>>,
+
+    views: BufferedViews<SvgView>,
+}
+
+impl SvgRenderer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads a TrueType font from its raw data, which documents will reference by the given
+    /// family name. The first font that is added is used for any text which doesn't specify one.
+    pub fn add_font(
+        &self,
+        family: impl Into<String>,
+        font_data: Vec<u8>,
+    ) -> Result<Font, InvalidFont> {
+        let font = FontArc::try_from_vec(font_data)?;
+
+        Ok(self.fonts.lock().add_font(family.into(), font))
+    }
+
+    /// Registers a texture, which documents will reference by the given URL. This may be a data
+    /// URL to embed the texture in the document.
+    pub fn add_texture(&self, href: impl Into<String>) -> TextureId {
+        self.textures.lock().add_texture(href.into())
+    }
+
+    /// Creates a delegate that measures text with the renderer's fonts, to be given to a
+    /// [`TextLayoutController`](agui_primitives::text::layout_controller::TextLayoutController).
+    pub fn text_layout_delegate(&self) -> GlyphLayoutDelegate {
+        self.fonts.lock().text_layout_delegate()
+    }
+
+    /// Redraws every bound view from the engine's render tree. The new frames are not presented
+    /// until each view is rendered.
+    pub fn redraw(&self, engine: &Engine) -> Result<(), EngineError> {
+        self.views.redraw(engine)
+    }
+
+    /// Writes the engine's render tree into a standalone document of the given size.
+    pub fn export(&self, engine: &Engine, size: Size) -> Result<String, EngineError> {
+        let fonts = self.fonts.lock();
+        let textures = self.textures.lock();
+
+        SvgWriter::new(&fonts, &textures, size)
+            .write_document(engine.render_objects(), engine.render_objects().root())
+    }
+}
+
+impl Renderer for SvgRenderer {
+    type Target = SvgDocument;
+
+    fn bind(
+        &self,
+        render_view_id: RenderViewId,
+        target: &Self::Target,
+        size: Size,
+    ) -> Result<Arc<dyn ViewRenderer>, Box<dyn std::error::Error>> {
+        Ok(self.views.bind(
+            render_view_id,
+            SvgView::new(
+                Arc::clone(&self.fonts),
+                Arc::clone(&self.textures),
+                target.clone(),
+                size,
+            ),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use agui_core::{
+        element::mock::render::{MockRenderObject, MockRenderWidget},
+        engine::Engine,
+        render::{canvas::Canvas, CanvasPainter, Paint},
+        unit::{Color, Offset, Rect, Shape, Size},
+        widget::IntoWidget,
+    };
+    use agui_renderer::{RenderViewId, Renderer};
+
+    use crate::SvgDocument;
+
+    use super::SvgRenderer;
+
+    fn paint_canvas(size: Size, func: impl FnOnce(CanvasPainter)) -> Canvas {
+        let mut canvas = Canvas {
+            size,
+
+            paints: Vec::default(),
+
+            head: Vec::default(),
+            children: Vec::default(),
+            tail: None,
+        };
+
+        func(CanvasPainter::begin(&mut canvas));
+
+        canvas
+    }
+
+    #[test]
+    pub fn renders_views_into_documents() {
+        let child_widget = MockRenderWidget::new("ChildWidget");
+        {
+            child_widget
+                .mock
+                .borrow_mut()
+                .expect_children()
+                .returning(Vec::new);
+
+            child_widget
+                .mock
+                .borrow_mut()
+                .expect_create_render_object()
+                .returning(|| {
+                    let render_object = MockRenderObject::new("ChildRenderObject");
+                    {
+                        let mut render_object_mock = render_object.mock.lock();
+
+                        render_object_mock
+                            .expect_layout()
+                            .returning(|_, constraints| constraints.biggest());
+
+                        render_object_mock.expect_paint().returning(|size| {
+                            Some(paint_canvas(size, |mut canvas| {
+                                let brush = canvas.add_paint(Paint {
+                                    color: Color::from_rgb((1.0, 0.0, 0.0)),
+                                    ..Paint::default()
+                                });
+
+                                canvas.draw_rect(&brush);
+                            }))
+                        });
+                    }
+                    render_object.into()
+                });
+        }
+
+        let child_widget = child_widget.into_widget();
+
+        let root_widget = MockRenderWidget::new("RootWidget");
+        {
+            root_widget
+                .mock
+                .borrow_mut()
+                .expect_children()
+                .returning_st(move || vec![child_widget.clone()]);
+
+            root_widget
+                .mock
+                .borrow_mut()
+                .expect_create_render_object()
+                .returning(|| {
+                    let render_object = MockRenderObject::new("RootRenderObject");
+                    {
+                        let mut render_object_mock = render_object.mock.lock();
+
+                        render_object_mock.expect_layout().returning(|mut ctx, _| {
+                            let mut children = ctx.iter_children_mut();
+
+                            while let Some(mut child) = children.next() {
+                                child.compute_layout(Size::new(100.0, 100.0));
+                                child.set_offset(Offset::new(10.0, 10.0));
+                            }
+
+                            Size::new(100.0, 100.0)
+                        });
+
+                        render_object_mock.expect_paint().returning(|size| {
+                            Some(paint_canvas(size, |mut canvas| {
+                                let brush = canvas.add_paint(Paint {
+                                    color: Color::from_rgb((0.0, 0.0, 1.0)),
+                                    ..Paint::default()
+                                });
+
+                                canvas.draw_rect(&brush);
+
+                                let clip = canvas.add_paint(Paint::default());
+
+                                canvas.start_layer_at(
+                                    Rect::new(0.0, 0.0, 50.0, 50.0),
+                                    &clip,
+                                    Shape::Rect,
+                                );
+                            }))
+                        });
+                    }
+                    render_object.into()
+                });
+        }
+
+        let mut engine = Engine::builder().with_root(root_widget).build();
+
+        engine.update().unwrap();
+
+        let renderer = SvgRenderer::new();
+
+        let document = SvgDocument::new();
+
+        let view = renderer
+            .bind(RenderViewId::default(), &document, Size::new(100.0, 100.0))
+            .unwrap();
+
+        renderer.redraw(&engine).unwrap();
+
+        view.render();
+
+        assert_eq!(
+            document.markup(),
+            concat!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="100" height="100" viewBox="0 0 100 100">"#,
+                "\n",
+                r##"  <path d="M0 0 L100 0 L100 100 L0 100 Z" fill="#0000ff" shape-rendering="crispEdges"/>"##,
+                "\n",
+                r#"  <clipPath id="clip-0">"#,
+                "\n",
+                r#"    <path d="M0 0 L50 0 L50 50 L0 50 Z" shape-rendering="crispEdges"/>"#,
+                "\n",
+                r#"  </clipPath>"#,
+                "\n",
+                r#"  <g clip-path="url(#clip-0)">"#,
+                "\n",
+                r#"    <g transform="translate(10 10)">"#,
+                "\n",
+                r##"      <path d="M0 0 L100 0 L100 100 L0 100 Z" fill="#ff0000" shape-rendering="crispEdges"/>"##,
+                "\n",
+                r#"    </g>"#,
+                "\n",
+                r#"  </g>"#,
+                "\n",
+                r#"</svg>"#,
+                "\n",
+            )
+        );
+
+        assert_eq!(
+            renderer.export(&engine, Size::new(100.0, 100.0)).unwrap(),
+            document.markup(),
+            "should have exported the same document"
+        );
+    }
+}
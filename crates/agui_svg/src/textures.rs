@@ -0,0 +1,21 @@
+use agui_core::unit::TextureId;
+
+#[derive(Default)]
+pub struct SvgTextures {
+    hrefs: Vec<String>,
+}
+
+impl SvgTextures {
+    pub fn add_texture(&mut self, href: String) -> TextureId {
+        self.hrefs.push(href);
+
+        TextureId::new(self.hrefs.len() - 1)
+    }
+
+    pub fn get(&self, texture_id: TextureId) -> Option<&str> {
+        texture_id
+            .idx()
+            .and_then(|idx| self.hrefs.get(idx))
+            .map(String::as_str)
+    }
+}
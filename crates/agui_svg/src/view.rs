@@ -0,0 +1,61 @@
+use std::sync::Arc;
+
+use agui_core::{engine::Engine, engine::EngineError, unit::Size};
+use agui_renderer::{BufferedView, BufferedViewRenderer};
+use parking_lot::Mutex;
+
+use crate::{document::SvgDocument, fonts::SvgFonts, textures::SvgTextures, writer::SvgWriter};
+
+/// Renders a view into an [`SvgDocument`].
+pub type SvgViewRenderer = BufferedViewRenderer<SvgView>;
+
+/// A view whose frames are written to a buffer, and are only presented to the document when the
+/// view is rendered.
+pub struct SvgView {
+    fonts: Arc<Mutex<SvgFonts>>,
+    textures: Arc<Mutex<SvgTextures>>,
+
+    document: SvgDocument,
+    size: Size,
+
+    scene: String,
+}
+
+impl SvgView {
+    pub(crate) fn new(
+        fonts: Arc<Mutex<SvgFonts>>,
+        textures: Arc<Mutex<SvgTextures>>,
+        document: SvgDocument,
+        size: Size,
+    ) -> Self {
+        Self {
+            fonts,
+            textures,
+
+            document,
+            size,
+
+            scene: String::new(),
+        }
+    }
+}
+
+impl BufferedView for SvgView {
+    fn redraw(&mut self, engine: &Engine) -> Result<(), EngineError> {
+        let fonts = self.fonts.lock();
+        let textures = self.textures.lock();
+
+        self.scene = SvgWriter::new(&fonts, &textures, self.size)
+            .write_document(engine.render_objects(), engine.render_objects().root())?;
+
+        Ok(())
+    }
+
+    fn resize(&mut self, size: Size) {
+        self.size = size;
+    }
+
+    fn present(&self) {
+        self.document.present(&self.scene);
+    }
+}
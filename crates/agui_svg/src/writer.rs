@@ -0,0 +1,773 @@
+use std::fmt::Write;
+
+use agui_core::{
+    engine::EngineError,
+    render::{
+        canvas::{Canvas, CanvasCommand, CanvasLayer},
+        Paint, RenderObject, RenderObjectId,
+    },
+    unit::{BlendMode, Bounds, Color, Offset, Rect, Shape, Size},
+    util::tree::Tree,
+};
+use agui_primitives::text::glyph_layout::{layout_text, FontMetrics};
+
+use crate::{
+    fonts::SvgFonts,
+    path::{bound_rect, format_number, to_path_data},
+    textures::SvgTextures,
+};
+
+/// Converts canvases into the elements of an SVG document, looking up any fonts and textures
+/// they reference.
+pub struct SvgWriter<'w> {
+    fonts: &'w SvgFonts,
+    textures: &'w SvgTextures,
+
+    size: Size,
+
+    markup: String,
+
+    next_clip_id: usize,
+}
+
+impl<'w> SvgWriter<'w> {
+    /// Creates a writer for a document of the given size. Anything drawn outside of it is
+    /// still written, but won't be visible.
+    pub fn new(fonts: &'w SvgFonts, textures: &'w SvgTextures, size: Size) -> Self {
+        Self {
+            fonts,
+            textures,
+
+            size,
+
+            markup: String::new(),
+
+            next_clip_id: 0,
+        }
+    }
+
+    /// Writes a standalone document containing the render object and all of its descendants.
+    pub fn write_document(
+        mut self,
+        render_objects: &Tree<RenderObjectId, RenderObject>,
+        render_object_id: Option<RenderObjectId>,
+    ) -> Result<String, EngineError> {
+        let (width, height) = (
+            format_number(self.size.width),
+            format_number(self.size.height),
+        );
+
+        self.line(
+            0,
+            format_args!(
+                r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+            ),
+        );
+
+        if let Some(render_object_id) = render_object_id {
+            self.write_render_object(render_objects, render_object_id, Offset::ZERO, 1)?;
+        }
+
+        self.line(0, format_args!("</svg>"));
+
+        Ok(self.markup)
+    }
+
+    /// Writes a render object and all of its descendants.
+    ///
+    /// Descendants are written into the tail layer of their ancestors' canvases, so that they're
+    /// clipped and composited along with it.
+    fn write_render_object(
+        &mut self,
+        render_objects: &Tree<RenderObjectId, RenderObject>,
+        render_object_id: RenderObjectId,
+        origin: Offset,
+        depth: usize,
+    ) -> Result<(), EngineError> {
+        let render_object = render_objects
+            .get(render_object_id)
+            .ok_or(EngineError::MissingRenderObject { render_object_id })?;
+
+        let offset = render_object.offset();
+
+        let origin = origin + offset;
+
+        let depth = self.open_translation(offset, depth);
+
+        let mut write_children = |writer: &mut Self, depth: usize| {
+            for child_id in render_objects
+                .get_children(render_object_id)
+                .into_iter()
+                .flatten()
+            {
+                writer.write_render_object(render_objects, *child_id, origin, depth)?;
+            }
+
+            Ok(())
+        };
+
+        match render_object.paint()? {
+            Some(canvas) => self.write_canvas(&canvas, origin, depth, &mut write_children)?,
+            None => write_children(self, depth)?,
+        }
+
+        self.close_translation(offset, depth);
+
+        Ok(())
+    }
+
+    /// Writes a canvas, followed by the `contents` within its tail layer.
+    ///
+    /// The origin is the position of the canvas within the document.
+    pub fn write_canvas<E>(
+        &mut self,
+        canvas: &Canvas,
+        origin: Offset,
+        depth: usize,
+        contents: &mut dyn FnMut(&mut Self, usize) -> Result<(), E>,
+    ) -> Result<(), E> {
+        for command in &canvas.head {
+            self.write_command(&canvas.paints, command, origin, depth);
+        }
+
+        for layer in &canvas.children {
+            self.write_layer(&canvas.paints, layer, origin, depth, &mut |_, _| Ok(()))?;
+        }
+
+        match &canvas.tail {
+            Some(tail) => self.write_layer(&canvas.paints, tail, origin, depth, contents),
+            None => contents(self, depth),
+        }
+    }
+
+    /// Writes a layer as a group, clipped to the shape of its style and composited using its
+    /// paint.
+    fn write_layer<E>(
+        &mut self,
+        paints: &[Paint],
+        layer: &CanvasLayer,
+        origin: Offset,
+        depth: usize,
+        contents: &mut dyn FnMut(&mut Self, usize) -> Result<(), E>,
+    ) -> Result<(), E> {
+        let paint = paints
+            .get(layer.style.paint_idx)
+            .cloned()
+            .unwrap_or_else(|| {
+                tracing::warn!(
+                    paint_idx = layer.style.paint_idx,
+                    "layer references a paint that does not exist"
+                );
+
+                Paint::default()
+            });
+
+        let origin = origin + layer.offset;
+
+        let depth = self.open_translation(layer.offset, depth);
+
+        let clip_id = self.write_clip_path(
+            &layer.style.shape,
+            bound_rect(Rect::from(layer.canvas.size), origin, self.size),
+            paint.anti_alias,
+            depth,
+        );
+
+        let mut attributes = format!(r#"clip-path="url(#{clip_id})""#);
+
+        if paint.color.alpha < 1.0 {
+            write!(
+                attributes,
+                r#" opacity="{}""#,
+                format_number(paint.color.alpha.max(0.0))
+            )
+            .expect("failed to write to string");
+        }
+
+        attributes.push_str(&blend_mode_attribute(paint.blend_mode));
+
+        self.line(depth, format_args!("<g {attributes}>"));
+
+        self.write_canvas(&layer.canvas, origin, depth + 1, contents)?;
+
+        self.line(depth, format_args!("</g>"));
+
+        self.close_translation(layer.offset, depth);
+
+        Ok(())
+    }
+
+    fn write_command(
+        &mut self,
+        paints: &[Paint],
+        command: &CanvasCommand,
+        origin: Offset,
+        depth: usize,
+    ) {
+        if command.is_noop() {
+            return;
+        }
+
+        match command {
+            CanvasCommand::Shape {
+                paint_idx,
+                rect,
+                shape,
+            } => {
+                let Some(paint) = get_paint(paints, *paint_idx) else {
+                    return;
+                };
+
+                let path = shape.build_path(bound_rect(*rect, origin, self.size));
+
+                self.line(
+                    depth,
+                    format_args!(
+                        r#"<path d="{}"{}{}{}/>"#,
+                        to_path_data(&path),
+                        fill_attributes(paint.color),
+                        shape_rendering_attribute(paint.anti_alias),
+                        blend_mode_attribute(paint.blend_mode)
+                    ),
+                );
+            }
+
+            CanvasCommand::Texture {
+                rect,
+                shape,
+                texture_id,
+                tex_bounds,
+            } => {
+                let Some(href) = self.textures.get(*texture_id) else {
+                    tracing::warn!(?texture_id, "texture does not exist");
+
+                    return;
+                };
+
+                let image_rect = texture_rect(*rect, *tex_bounds);
+
+                let image = format!(
+                    r#"<image x="{}" y="{}" width="{}" height="{}" href="{}" preserveAspectRatio="none"/>"#,
+                    format_number(image_rect.left),
+                    format_number(image_rect.top),
+                    format_number(image_rect.width),
+                    format_number(image_rect.height),
+                    escape(href)
+                );
+
+                let clip_id = self.write_clip_path(shape, *rect, true, depth);
+
+                self.line(depth, format_args!(r#"<g clip-path="url(#{clip_id})">"#));
+                self.line(depth + 1, format_args!("{image}"));
+                self.line(depth, format_args!("</g>"));
+            }
+
+            CanvasCommand::Text {
+                paint_idx,
+                rect,
+                text_style,
+                text,
+            } => {
+                let Some(paint) = get_paint(paints, *paint_idx) else {
+                    return;
+                };
+
+                let registry = self.fonts.registry().lock();
+
+                let Some(font_id) = registry.resolve(text_style.font) else {
+                    tracing::warn!("no font is available to lay out text with");
+
+                    return;
+                };
+
+                let Some(metrics) = registry
+                    .get(font_id)
+                    .and_then(|font| FontMetrics::new(font, text_style.size))
+                else {
+                    return;
+                };
+
+                let mut attributes = String::new();
+
+                if let Some(family) = self.fonts.family(font_id) {
+                    write!(attributes, r#" font-family="{}""#, escape(family))
+                        .expect("failed to write to string");
+                }
+
+                write!(
+                    attributes,
+                    r#" font-size="{}"{}{}"#,
+                    format_number(text_style.size),
+                    fill_attributes(paint.color),
+                    blend_mode_attribute(paint.blend_mode)
+                )
+                .expect("failed to write to string");
+
+                let mut glyphs = Vec::new();
+
+                layout_text(&metrics, rect.width, text, |glyph| glyphs.push(glyph));
+
+                drop(registry);
+
+                // The lines are written without any whitespace between them, since it would be
+                // preserved and drawn as part of the text.
+                let mut markup = format!(r#"<text{attributes} xml:space="preserve">"#);
+
+                for line in glyphs.chunk_by(|a, b| a.baseline == b.baseline) {
+                    let Some(first) = line.first() else {
+                        continue;
+                    };
+
+                    let line = line.iter().map(|glyph| glyph.ch).collect::<String>();
+
+                    write!(
+                        markup,
+                        r#"<tspan x="{}" y="{}">{}</tspan>"#,
+                        format_number(rect.left + first.x),
+                        format_number(rect.top + first.baseline),
+                        escape(&line)
+                    )
+                    .expect("failed to write to string");
+                }
+
+                markup.push_str("</text>");
+
+                self.line(depth, format_args!("{markup}"));
+            }
+
+            command => {
+                tracing::error!("unknown command: {:?}", command);
+            }
+        }
+    }
+
+    /// Writes a clip path of the shape filling the rect, returning its ID.
+    fn write_clip_path(
+        &mut self,
+        shape: &Shape,
+        rect: Rect,
+        anti_alias: bool,
+        depth: usize,
+    ) -> String {
+        let clip_id = format!("clip-{}", self.next_clip_id);
+
+        self.next_clip_id += 1;
+
+        self.line(depth, format_args!(r#"<clipPath id="{clip_id}">"#));
+        self.line(
+            depth + 1,
+            format_args!(
+                r#"<path d="{}"{}/>"#,
+                to_path_data(&shape.build_path(rect)),
+                shape_rendering_attribute(anti_alias)
+            ),
+        );
+        self.line(depth, format_args!("</clipPath>"));
+
+        clip_id
+    }
+
+    /// Opens a group translated by the offset, if it's not zero, returning the depth of its
+    /// contents.
+    fn open_translation(&mut self, offset: Offset, depth: usize) -> usize {
+        if offset == Offset::ZERO {
+            return depth;
+        }
+
+        self.line(
+            depth,
+            format_args!(
+                r#"<g transform="translate({} {})">"#,
+                format_number(offset.x),
+                format_number(offset.y)
+            ),
+        );
+
+        depth + 1
+    }
+
+    /// Closes a group opened by [`SvgWriter::open_translation`], given the depth of its contents.
+    fn close_translation(&mut self, offset: Offset, depth: usize) {
+        if offset != Offset::ZERO {
+            self.line(depth - 1, format_args!("</g>"));
+        }
+    }
+
+    fn line(&mut self, depth: usize, contents: std::fmt::Arguments) {
+        writeln!(self.markup, "{}{contents}", "  ".repeat(depth))
+            .expect("failed to write to string");
+    }
+}
+
+fn get_paint(paints: &[Paint], paint_idx: usize) -> Option<&Paint> {
+    let paint = paints.get(paint_idx);
+
+    if paint.is_none() {
+        tracing::warn!(paint_idx, "command references a paint that does not exist");
+    }
+
+    paint
+}
+
+/// Places the texture so that the region selected by the bounds fills the rect. The bounds are
+/// normalized to the size of the texture, and bounds with no area select the entire texture.
+fn texture_rect(rect: Rect, tex_bounds: Bounds) -> Rect {
+    if tex_bounds.right <= tex_bounds.left || tex_bounds.bottom <= tex_bounds.top {
+        return rect;
+    }
+
+    let width = rect.width / (tex_bounds.right - tex_bounds.left);
+    let height = rect.height / (tex_bounds.bottom - tex_bounds.top);
+
+    Rect::new(
+        rect.left - tex_bounds.left * width,
+        rect.top - tex_bounds.top * height,
+        width,
+        height,
+    )
+}
+
+fn fill_attributes(color: Color) -> String {
+    let to_channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+
+    let mut attributes = format!(
+        r##" fill="#{:02x}{:02x}{:02x}""##,
+        to_channel(color.red),
+        to_channel(color.green),
+        to_channel(color.blue)
+    );
+
+    if color.alpha < 1.0 {
+        write!(
+            attributes,
+            r#" fill-opacity="{}""#,
+            format_number(color.alpha.max(0.0))
+        )
+        .expect("failed to write to string");
+    }
+
+    attributes
+}
+
+fn shape_rendering_attribute(anti_alias: bool) -> &'static str {
+    if anti_alias {
+        ""
+    } else {
+        r#" shape-rendering="crispEdges""#
+    }
+}
+
+fn blend_mode_attribute(blend_mode: BlendMode) -> String {
+    match to_mix_blend_mode(blend_mode) {
+        Some("normal") => String::new(),
+
+        Some(mix_blend_mode) => format!(r#" style="mix-blend-mode: {mix_blend_mode}""#),
+
+        None => {
+            tracing::warn!(
+                ?blend_mode,
+                "blend mode is not supported in svg, falling back to normal blending"
+            );
+
+            String::new()
+        }
+    }
+}
+
+/// Maps a blend mode onto the CSS `mix-blend-mode` property. Most Porter-Duff compositing
+/// operators have no equivalent, and return `None`.
+pub fn to_mix_blend_mode(blend_mode: BlendMode) -> Option<&'static str> {
+    match blend_mode {
+        BlendMode::SourceOver => Some("normal"),
+        BlendMode::Plus => Some("plus-lighter"),
+        BlendMode::Modulate | BlendMode::Multiply => Some("multiply"),
+        BlendMode::Screen => Some("screen"),
+        BlendMode::Overlay => Some("overlay"),
+        BlendMode::Darken => Some("darken"),
+        BlendMode::Lighten => Some("lighten"),
+        BlendMode::ColorDodge => Some("color-dodge"),
+        BlendMode::ColorBurn => Some("color-burn"),
+        BlendMode::HardLight => Some("hard-light"),
+        BlendMode::SoftLight => Some("soft-light"),
+        BlendMode::Difference => Some("difference"),
+        BlendMode::Exclusion => Some("exclusion"),
+        BlendMode::Hue => Some("hue"),
+        BlendMode::Saturation => Some("saturation"),
+        BlendMode::Color => Some("color"),
+        BlendMode::Luminosity => Some("luminosity"),
+
+        BlendMode::Clear
+        | BlendMode::Source
+        | BlendMode::Destination
+        | BlendMode::DestinationOver
+        | BlendMode::SourceIn
+        | BlendMode::DestinationIn
+        | BlendMode::SourceOut
+        | BlendMode::DestinationOut
+        | BlendMode::SourceAlphaTop
+        | BlendMode::DestinationAlphaTop
+        | BlendMode::XOr => None,
+    }
+}
+
+/// Escapes text so that it can be used in element contents and attribute values.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for ch in text.chars() {
+        match ch {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            ch => escaped.push(ch),
+        }
+    }
+
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use ab_glyph::FontArc;
+    use agui_core::{
+        render::{
+            canvas::{Canvas, CanvasCommand},
+            CanvasPainter, Paint,
+        },
+        unit::{BlendMode, Bounds, Color, Offset, Rect, Shape, Size, TextStyle},
+    };
+    use agui_primitives::text::glyph_layout::{FontMetrics, GlyphMetrics};
+
+    use crate::{fonts::SvgFonts, path::format_number, textures::SvgTextures};
+
+    use super::SvgWriter;
+
+    const FONT: &[u8] = include_bytes!("../../../examples/fonts/DejaVuSans.ttf");
+
+    const RED: Color = Color::from_rgb((1.0, 0.0, 0.0));
+
+    fn paint_canvas(size: Size, func: impl FnOnce(CanvasPainter)) -> Canvas {
+        let mut canvas = Canvas {
+            size,
+
+            paints: Vec::default(),
+
+            head: Vec::default(),
+            children: Vec::default(),
+            tail: None,
+        };
+
+        func(CanvasPainter::begin(&mut canvas));
+
+        canvas
+    }
+
+    fn write(canvas: &Canvas, fonts: &SvgFonts, textures: &SvgTextures) -> String {
+        let mut writer = SvgWriter::new(fonts, textures, canvas.size);
+
+        writer
+            .write_canvas::<()>(canvas, Offset::ZERO, 0, &mut |_, _| Ok(()))
+            .unwrap();
+
+        writer.markup
+    }
+
+    #[test]
+    pub fn writes_shapes_as_paths() {
+        let canvas = paint_canvas(Size::new(100.0, 100.0), |mut canvas| {
+            let brush = canvas.add_paint(Paint {
+                color: RED,
+                ..Paint::default()
+            });
+
+            canvas.draw_rect_at(Rect::new(10.0, 10.0, 30.0, 30.0), &brush);
+
+            let translucent = canvas.add_paint(Paint {
+                color: Color::from_rgba((0.0, 0.0, 1.0, 0.5)),
+                anti_alias: true,
+                blend_mode: BlendMode::Multiply,
+            });
+
+            canvas.draw_rect(&translucent);
+        });
+
+        assert_eq!(
+            write(&canvas, &SvgFonts::default(), &SvgTextures::default()),
+            concat!(
+                r##"<path d="M10 10 L40 10 L40 40 L10 40 Z" fill="#ff0000" shape-rendering="crispEdges"/>"##,
+                "\n",
+                r##"<path d="M0 0 L100 0 L100 100 L0 100 Z" fill="#0000ff" fill-opacity="0.5" style="mix-blend-mode: multiply"/>"##,
+                "\n",
+            )
+        );
+    }
+
+    #[test]
+    pub fn writes_layers_as_clip_groups() {
+        let canvas = paint_canvas(Size::new(100.0, 100.0), |mut canvas| {
+            let layer_brush = canvas.add_paint(Paint {
+                color: Color::from_rgba((1.0, 1.0, 1.0, 0.25)),
+                anti_alias: true,
+                ..Paint::default()
+            });
+
+            canvas.layer_at(
+                Rect::new(10.0, 20.0, 50.0, 50.0),
+                &layer_brush,
+                Shape::Rect,
+                |mut canvas| {
+                    let brush = canvas.add_paint(Paint {
+                        color: RED,
+                        ..Paint::default()
+                    });
+
+                    canvas.draw_rect(&brush);
+                },
+            );
+        });
+
+        assert_eq!(
+            write(&canvas, &SvgFonts::default(), &SvgTextures::default()),
+            concat!(
+                r#"<g transform="translate(10 20)">"#,
+                "\n",
+                r#"  <clipPath id="clip-0">"#,
+                "\n",
+                r#"    <path d="M0 0 L50 0 L50 50 L0 50 Z"/>"#,
+                "\n",
+                r#"  </clipPath>"#,
+                "\n",
+                r#"  <g clip-path="url(#clip-0)" opacity="0.25">"#,
+                "\n",
+                r##"    <path d="M0 0 L50 0 L50 50 L0 50 Z" fill="#ff0000" shape-rendering="crispEdges"/>"##,
+                "\n",
+                r#"  </g>"#,
+                "\n",
+                r#"</g>"#,
+                "\n",
+            )
+        );
+    }
+
+    #[test]
+    pub fn writes_textures_as_images() {
+        let mut textures = SvgTextures::default();
+
+        let texture_id = textures.add_texture(String::from("atlas.png"));
+
+        let mut canvas = paint_canvas(Size::new(10.0, 10.0), |_| {});
+
+        canvas.head.push(CanvasCommand::Texture {
+            rect: Rect::new(0.0, 0.0, 10.0, 10.0),
+            shape: Shape::Rect,
+            texture_id,
+            tex_bounds: Bounds {
+                left: 0.5,
+                top: 0.0,
+                right: 1.0,
+                bottom: 0.5,
+            },
+        });
+
+        let markup = write(&canvas, &SvgFonts::default(), &textures);
+
+        assert!(
+            markup.contains(
+                r#"<image x="-10" y="0" width="20" height="20" href="atlas.png" preserveAspectRatio="none"/>"#
+            ),
+            "should have placed the selected region of the texture over the rect, got:\n{markup}"
+        );
+
+        assert!(
+            markup.contains(r#"<g clip-path="url(#clip-0)">"#),
+            "should have clipped the texture to its shape, got:\n{markup}"
+        );
+    }
+
+    #[test]
+    pub fn writes_text_elements() {
+        let font = FontArc::try_from_slice(FONT).unwrap();
+
+        let metrics = FontMetrics::new(&font, 10.0).unwrap();
+
+        let mut fonts = SvgFonts::default();
+
+        fonts.add_font(String::from("DejaVu Sans"), font.clone());
+
+        let canvas = paint_canvas(Size::new(100.0, 100.0), |mut canvas| {
+            let brush = canvas.add_paint(Paint::default());
+
+            canvas.draw_text(&brush, TextStyle::new().size(10.0), "a < b\n& c");
+        });
+
+        assert_eq!(
+            write(&canvas, &fonts, &SvgTextures::default()),
+            format!(
+                concat!(
+                    r##"<text font-family="DejaVu Sans" font-size="10" fill="#ffffff" xml:space="preserve">"##,
+                    r#"<tspan x="0" y="{}">a &lt; b</tspan>"#,
+                    r#"<tspan x="0" y="{}">&amp; c</tspan>"#,
+                    r#"</text>"#,
+                    "\n",
+                ),
+                format_number(metrics.ascent()),
+                format_number(metrics.ascent() + metrics.line_height())
+            )
+        );
+    }
+
+    #[test]
+    pub fn wraps_text_to_fit_its_rect() {
+        let mut fonts = SvgFonts::default();
+
+        fonts.add_font(
+            String::from("DejaVu Sans"),
+            FontArc::try_from_slice(FONT).unwrap(),
+        );
+
+        let canvas = paint_canvas(Size::new(20.0, 100.0), |mut canvas| {
+            let brush = canvas.add_paint(Paint::default());
+
+            canvas.draw_text(&brush, TextStyle::new().size(10.0), "abcdefgh");
+        });
+
+        let markup = write(&canvas, &fonts, &SvgTextures::default());
+
+        assert_eq!(
+            markup.lines().count(),
+            1,
+            "should have written the text on a single line, got:\n{markup}"
+        );
+
+        assert!(
+            markup.matches("<tspan").count() > 1,
+            "should have wrapped the text onto multiple lines, got:\n{markup}"
+        );
+
+        let text = markup
+            .split("<tspan")
+            .skip(1)
+            .filter_map(|tspan| tspan.split_once('>'))
+            .filter_map(|(_, rest)| rest.split_once("</tspan>"))
+            .map(|(line, _)| line)
+            .collect::<String>();
+
+        assert_eq!(text, "abcdefgh", "should have kept every glyph");
+    }
+
+    #[test]
+    pub fn skips_text_without_a_font() {
+        let canvas = paint_canvas(Size::new(100.0, 100.0), |mut canvas| {
+            let brush = canvas.add_paint(Paint::default());
+
+            canvas.draw_text(&brush, TextStyle::new().size(10.0), "hello");
+        });
+
+        assert_eq!(
+            write(&canvas, &SvgFonts::default(), &SvgTextures::default()),
+            ""
+        );
+    }
+}
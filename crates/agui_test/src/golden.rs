@@ -0,0 +1,215 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use agui_tiny_skia::Framebuffer;
+
+use crate::snapshot::BLESS_ENV_VAR;
+
+/// The largest difference allowed between any channel of a pixel and the same pixel of a golden
+/// image before it is considered to have changed, absorbing small anti-aliasing differences.
+pub const DEFAULT_GOLDEN_TOLERANCE: u8 = 2;
+
+/// The directory that failed golden comparisons write their actual and diff images to.
+pub fn golden_failure_dir() -> PathBuf {
+    std::env::temp_dir().join("agui_test").join("goldens")
+}
+
+/// Compares an image against the golden image stored at the given path, panicking if any pixel
+/// differs from it by more than the tolerance.
+///
+/// On failure, the actual image and an image highlighting the mismatched pixels in red are
+/// written to [`golden_failure_dir`].
+///
+/// If the [`BLESS_ENV_VAR`] environment variable is set to `1`, the stored golden image is
+/// replaced instead.
+///
+/// # Panics
+///
+/// Panics if the images differ, if no golden image has been stored yet, or if the golden image
+/// could not be read or written.
+pub fn assert_golden(path: impl AsRef<Path>, actual: &Framebuffer, tolerance: u8) {
+    let path = path.as_ref();
+
+    if std::env::var(BLESS_ENV_VAR).is_ok_and(|value| value == "1") {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).expect("failed to create golden directory");
+        }
+
+        actual.save_png(path).expect("failed to write golden");
+
+        return;
+    }
+
+    if let Err(message) = compare_golden(path, actual, tolerance, &golden_failure_dir()) {
+        panic!("{message}");
+    }
+}
+
+fn compare_golden(
+    path: &Path,
+    actual: &Framebuffer,
+    tolerance: u8,
+    failure_dir: &Path,
+) -> Result<(), String> {
+    let expected = match Framebuffer::load_png(path) {
+        Ok(expected) => expected,
+
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            let actual_path = write_failure_image(failure_dir, path, "actual", actual)?;
+
+            return Err(format!(
+                "no golden stored at {}, rerun with {BLESS_ENV_VAR}=1 to create it\n\n  actual: {}",
+                path.display(),
+                actual_path.display()
+            ));
+        }
+
+        Err(err) => return Err(format!("failed to read golden {}: {err}", path.display())),
+    };
+
+    if expected.width() != actual.width() || expected.height() != actual.height() {
+        let actual_path = write_failure_image(failure_dir, path, "actual", actual)?;
+
+        return Err(format!(
+            "golden {} is {}x{}, but the actual image is {}x{}, rerun with {BLESS_ENV_VAR}=1 to \
+             accept the changes\n\n  actual: {}",
+            path.display(),
+            expected.width(),
+            expected.height(),
+            actual.width(),
+            actual.height(),
+            actual_path.display()
+        ));
+    }
+
+    let expected_pixels = expected.to_rgba8();
+    let actual_pixels = actual.to_rgba8();
+
+    let mut mismatched = 0;
+
+    let diff_pixels = expected_pixels
+        .chunks_exact(4)
+        .zip(actual_pixels.chunks_exact(4))
+        .flat_map(|(expected, actual)| {
+            let difference = expected
+                .iter()
+                .zip(actual)
+                .map(|(expected, actual)| expected.abs_diff(*actual))
+                .max()
+                .unwrap_or(0);
+
+            if difference > tolerance {
+                mismatched += 1;
+
+                [255, 0, 0, 255]
+            } else {
+                // Fade the expected image, so that the mismatched pixels stand out.
+                [expected[0], expected[1], expected[2], expected[3] / 4]
+            }
+        })
+        .collect::<Vec<_>>();
+
+    if mismatched == 0 {
+        return Ok(());
+    }
+
+    let diff = Framebuffer::from_rgba8(actual.width(), actual.height(), &diff_pixels)
+        .expect("diff image should match the size of the actual image");
+
+    let actual_path = write_failure_image(failure_dir, path, "actual", actual)?;
+    let diff_path = write_failure_image(failure_dir, path, "diff", &diff)?;
+
+    Err(format!(
+        "golden {} does not match, {mismatched} of {} pixels differ by more than {tolerance}, \
+         rerun with {BLESS_ENV_VAR}=1 to accept the changes\n\n  actual: {}\n  diff: {}",
+        path.display(),
+        actual.width() * actual.height(),
+        actual_path.display(),
+        diff_path.display()
+    ))
+}
+
+fn write_failure_image(
+    dir: &Path,
+    golden_path: &Path,
+    kind: &str,
+    image: &Framebuffer,
+) -> Result<PathBuf, String> {
+    let name = golden_path
+        .file_stem()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default();
+
+    let path = dir.join(format!("{name}.{kind}.png"));
+
+    fs::create_dir_all(dir)
+        .and_then(|_| image.save_png(&path))
+        .map_err(|err| format!("failed to write {}: {err}", path.display()))?;
+
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use agui_tiny_skia::Framebuffer;
+
+    use super::compare_golden;
+
+    fn solid(width: u32, height: u32, rgba: [u8; 4]) -> Framebuffer {
+        Framebuffer::from_rgba8(width, height, &rgba.repeat((width * height) as usize)).unwrap()
+    }
+
+    #[test]
+    pub fn compares_against_stored_goldens() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let path = dir.path().join("compares_against_stored_goldens.png");
+        let failure_dir = dir.path().join("failures");
+
+        solid(4, 4, [100, 150, 200, 255]).save_png(&path).unwrap();
+
+        assert_eq!(
+            compare_golden(&path, &solid(4, 4, [101, 149, 200, 255]), 1, &failure_dir),
+            Ok(()),
+            "should have allowed differences within the tolerance"
+        );
+
+        let message = compare_golden(&path, &solid(4, 4, [100, 150, 210, 255]), 1, &failure_dir)
+            .expect_err("should not have matched a changed image");
+
+        assert!(
+            message.contains("16 of 16 pixels"),
+            "should have counted the mismatched pixels, got:\n{message}"
+        );
+
+        let diff =
+            Framebuffer::load_png(failure_dir.join("compares_against_stored_goldens.diff.png"))
+                .expect("should have written a diff image");
+
+        assert_eq!(diff.pixel(0, 0), Some([255, 0, 0, 255]));
+
+        let actual =
+            Framebuffer::load_png(failure_dir.join("compares_against_stored_goldens.actual.png"))
+                .expect("should have written the actual image");
+
+        assert_eq!(actual.pixel(0, 0), Some([100, 150, 210, 255]));
+
+        assert!(
+            compare_golden(&path, &solid(2, 4, [100, 150, 200, 255]), 1, &failure_dir).is_err(),
+            "should not have matched an image of a different size"
+        );
+
+        assert!(
+            compare_golden(
+                &path.with_extension("missing"),
+                &solid(4, 4, [0; 4]),
+                1,
+                &failure_dir
+            )
+            .is_err(),
+            "should not have matched a golden that does not exist"
+        );
+    }
+}
@@ -0,0 +1,8 @@
+mod golden;
+mod snapshot;
+mod surface;
+mod tester;
+
+pub use golden::*;
+pub use snapshot::*;
+pub use tester::*;
@@ -0,0 +1,181 @@
+use std::{fmt::Write, fs, path::Path};
+
+use agui_core::{
+    engine::Engine,
+    render::{RenderObject, RenderObjectId},
+    util::tree::Tree,
+};
+use similar::TextDiff;
+
+/// Setting this environment variable to `1` causes snapshot assertions to overwrite their
+/// stored snapshot instead of comparing against it.
+pub const BLESS_ENV_VAR: &str = "AGUI_BLESS";
+
+/// Serializes the engine's render tree into a human-readable snapshot, listing the name, size
+/// and offset of each render object indented by its depth.
+pub fn render_tree_snapshot(engine: &Engine) -> String {
+    match engine.render_objects().root() {
+        Some(root_id) => render_subtree_snapshot(engine.render_objects(), root_id),
+        None => String::new(),
+    }
+}
+
+/// Serializes a render object and its descendants into a human-readable snapshot. The render
+/// object is written without any indentation, regardless of its depth in the tree.
+pub fn render_subtree_snapshot(
+    render_objects: &Tree<RenderObjectId, RenderObject>,
+    render_object_id: RenderObjectId,
+) -> String {
+    let base_depth = render_objects.get_depth(render_object_id).unwrap_or(0);
+
+    let mut snapshot = String::new();
+
+    for render_object_id in render_objects.iter_down_from(render_object_id) {
+        let Some(render_object) = render_objects.get(render_object_id) else {
+            continue;
+        };
+
+        let depth = render_objects.get_depth(render_object_id).unwrap_or(0) - base_depth;
+
+        let size = render_object.size().map_or_else(
+            || String::from("none"),
+            |size| {
+                format!(
+                    "{} x {}",
+                    format_extent(size.width),
+                    format_extent(size.height)
+                )
+            },
+        );
+
+        let offset = render_object.offset();
+
+        writeln!(
+            snapshot,
+            "{}{} size=({size}) offset=({}, {})",
+            "  ".repeat(depth),
+            render_object.render_object_name(),
+            format_extent(offset.x),
+            format_extent(offset.y)
+        )
+        .expect("failed to write to string");
+    }
+
+    snapshot
+}
+
+/// Formats a value with at most two decimal places, so that snapshots are not sensitive to
+/// floating point noise.
+fn format_extent(value: f32) -> String {
+    if value.is_infinite() {
+        return String::from(if value > 0.0 { "inf" } else { "-inf" });
+    }
+
+    let formatted = format!("{value:.2}");
+    let formatted = formatted.trim_end_matches('0').trim_end_matches('.');
+
+    // Avoid a distinction between zero and negative zero.
+    if formatted == "-0" {
+        String::from("0")
+    } else {
+        String::from(formatted)
+    }
+}
+
+/// Compares a snapshot against the one stored at the given path, panicking with a readable diff
+/// if they differ.
+///
+/// If the [`BLESS_ENV_VAR`] environment variable is set to `1`, the stored snapshot is replaced
+/// instead.
+///
+/// # Panics
+///
+/// Panics if the snapshots differ, if no snapshot has been stored yet, or if the snapshot file
+/// could not be read or written.
+pub fn assert_snapshot(path: impl AsRef<Path>, actual: &str) {
+    let path = path.as_ref();
+
+    if std::env::var(BLESS_ENV_VAR).is_ok_and(|value| value == "1") {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).expect("failed to create snapshot directory");
+        }
+
+        fs::write(path, actual).expect("failed to write snapshot");
+
+        return;
+    }
+
+    if let Err(message) = compare_snapshot(path, actual) {
+        panic!("{message}");
+    }
+}
+
+fn compare_snapshot(path: &Path, actual: &str) -> Result<(), String> {
+    let expected = match fs::read_to_string(path) {
+        Ok(expected) => expected,
+
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+            return Err(format!(
+                "no snapshot stored at {}, rerun with {BLESS_ENV_VAR}=1 to create it:\n\n{actual}",
+                path.display()
+            ));
+        }
+
+        Err(err) => return Err(format!("failed to read snapshot {}: {err}", path.display())),
+    };
+
+    // Ignore differences in line endings, which may be changed by version control.
+    let expected = expected.replace("\r\n", "\n");
+
+    if expected == actual {
+        return Ok(());
+    }
+
+    let diff = TextDiff::from_lines(expected.as_str(), actual);
+
+    Err(format!(
+        "snapshot {} does not match, rerun with {BLESS_ENV_VAR}=1 to accept the changes:\n\n{}",
+        path.display(),
+        diff.unified_diff().header("expected", "actual")
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::{compare_snapshot, format_extent};
+
+    #[test]
+    pub fn formats_extents() {
+        assert_eq!(format_extent(10.0), "10");
+        assert_eq!(format_extent(33.333_332), "33.33");
+        assert_eq!(format_extent(0.5), "0.5");
+        assert_eq!(format_extent(-0.001), "0");
+        assert_eq!(format_extent(f32::INFINITY), "inf");
+    }
+
+    #[test]
+    pub fn compares_against_stored_snapshots() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let path = dir.path().join("compares_against_stored_snapshots.snap");
+
+        fs::write(&path, "Root\r\n  Child\r\n").unwrap();
+
+        assert_eq!(compare_snapshot(&path, "Root\n  Child\n"), Ok(()));
+
+        let message = compare_snapshot(&path, "Root\n  Other\n")
+            .expect_err("should not have matched a changed snapshot");
+
+        assert!(
+            message.contains("-  Child\n+  Other\n"),
+            "should have included a diff, got:\n{message}"
+        );
+
+        assert!(
+            compare_snapshot(&path.with_extension("missing"), "Root\n").is_err(),
+            "should not have matched a snapshot that does not exist"
+        );
+    }
+}
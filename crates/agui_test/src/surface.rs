@@ -0,0 +1,40 @@
+use agui_core::{
+    unit::{Constraints, IntrinsicDimension, Offset, Size},
+    widget::Widget,
+};
+use agui_elements::layout::{IntrinsicSizeContext, LayoutContext, WidgetLayout};
+use agui_macros::LayoutWidget;
+
+/// Stands in for a window, forcing the widget under test to the size of the surface.
+#[derive(LayoutWidget, Debug)]
+pub(crate) struct TestSurface {
+    pub size: Size,
+
+    pub child: Widget,
+}
+
+impl WidgetLayout for TestSurface {
+    fn children(&self) -> Vec<Widget> {
+        Vec::from([self.child.clone()])
+    }
+
+    fn intrinsic_size(
+        &self,
+        _: &mut IntrinsicSizeContext,
+        dimension: IntrinsicDimension,
+        _: f32,
+    ) -> f32 {
+        self.size.extent(dimension.axis())
+    }
+
+    fn layout(&self, ctx: &mut LayoutContext, _: Constraints) -> Size {
+        let mut children = ctx.iter_children_mut();
+
+        while let Some(mut child) = children.next() {
+            child.compute_layout(Constraints::tight(self.size));
+            child.set_offset(Offset::ZERO);
+        }
+
+        self.size
+    }
+}
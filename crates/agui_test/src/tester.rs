@@ -0,0 +1,851 @@
+use std::{iter, path::PathBuf, rc::Rc, time::Duration};
+
+use agui_core::{
+    element::Element,
+    engine::{Engine, EngineError},
+    plugin::context::ContextPlugins,
+    query::{WidgetQuery, WidgetQueryExt},
+    scheduler::{FrameSchedulerPlugin, ManualClock},
+    timer::TimerPlugin,
+    unit::{Font, Key, Offset, Rect, Size},
+    widget::{AnyWidget, IntoWidget},
+};
+use agui_gestures::{
+    dispatch::{dispatch_key_event, dispatch_pointer_event},
+    event::{Key as KeyboardKey, KeyEvent, PointerEvent, PointerEventKind},
+};
+use agui_inheritance::InheritancePlugin;
+use agui_primitives::text::{layout_controller::TextLayoutController, query::TextQueryExt};
+use agui_tiny_skia::{Framebuffer, InvalidFont, TinySkiaRenderer};
+
+use crate::{
+    golden::{assert_golden, DEFAULT_GOLDEN_TOLERANCE},
+    snapshot::{assert_snapshot, render_subtree_snapshot},
+    surface::TestSurface,
+};
+
+/// The size of the surface widgets are laid out in, unless otherwise specified.
+pub const DEFAULT_SURFACE_SIZE: Size = Size::new(800.0, 600.0);
+
+/// The amount of time that passes between frames.
+pub const FRAME_INTERVAL: Duration = Duration::from_millis(16);
+
+/// The number of frames [`WidgetTester::pump_and_settle`] will pump before giving up.
+pub const SETTLE_FRAME_LIMIT: usize = 10_000;
+
+/// Runs a widget in an engine with the standard plugins and no window, so that it can be tested
+/// end to end.
+///
+/// Time only passes when the tester is told to pump frames, making animations and timers fully
+/// deterministic.
+///
+/// Text is measured and drawn by a CPU renderer, which has no fonts until one is added with
+/// [`WidgetTester::add_font`].
+pub struct WidgetTester {
+    engine: Engine,
+
+    clock: ManualClock,
+
+    size: Size,
+    renderer: TinySkiaRenderer,
+}
+
+impl WidgetTester {
+    /// Creates a tester that lays out the widget in a surface of [`DEFAULT_SURFACE_SIZE`], and
+    /// pumps the initial build.
+    pub fn new(widget: impl IntoWidget) -> Self {
+        Self::with_size(widget, DEFAULT_SURFACE_SIZE)
+    }
+
+    /// Creates a tester that lays out the widget in a surface of the given size, and pumps the
+    /// initial build.
+    pub fn with_size(widget: impl IntoWidget, size: Size) -> Self {
+        let clock = ManualClock::default();
+
+        let renderer = TinySkiaRenderer::new();
+
+        let engine = Engine::builder()
+            .add_plugin(FrameSchedulerPlugin::with_clock(clock.clone()))
+            .add_plugin(TimerPlugin::with_clock(clock.clone()))
+            .add_plugin(InheritancePlugin::default())
+            .with_root(TextLayoutController {
+                delegate: Rc::new(renderer.text_layout_delegate()),
+
+                child: Some(
+                    TestSurface {
+                        size,
+
+                        child: widget.into_widget(),
+                    }
+                    .into_widget(),
+                ),
+            })
+            .build();
+
+        let mut tester = Self {
+            engine,
+            clock,
+
+            size,
+            renderer,
+        };
+
+        tester.pump();
+
+        tester
+    }
+
+    pub fn engine(&self) -> &Engine {
+        &self.engine
+    }
+
+    pub fn engine_mut(&mut self) -> &mut Engine {
+        &mut self.engine
+    }
+
+    /// The clock driving frames and timers, which only moves when frames are pumped.
+    pub fn clock(&self) -> &ManualClock {
+        &self.clock
+    }
+
+    /// The renderer used to measure text and to render the widget under test.
+    pub fn renderer(&self) -> &TinySkiaRenderer {
+        &self.renderer
+    }
+
+    /// Loads a font from its raw data, then lays out the widget under test again so that any
+    /// text is measured with it. The first font that is added is used by any text which doesn't
+    /// specify one.
+    pub fn add_font(&mut self, font_data: Vec<u8>) -> Result<Font, InvalidFont> {
+        let font = self.renderer.add_font(font_data)?;
+
+        let render_object_ids = self.engine.render_objects().iter_down().collect::<Vec<_>>();
+
+        for render_object_id in render_object_ids {
+            self.engine.mark_needs_layout(render_object_id);
+        }
+
+        self.pump();
+
+        Ok(font)
+    }
+
+    /// Updates the engine without advancing time, processing any pending rebuilds, callbacks
+    /// and layout.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the update fails. Use [`WidgetTester::try_pump`] to handle the error instead.
+    pub fn pump(&mut self) {
+        self.try_pump().expect("failed to update the engine");
+    }
+
+    pub fn try_pump(&mut self) -> Result<(), EngineError> {
+        self.engine.update()
+    }
+
+    /// Advances time by the given duration one frame at a time, updating the engine after each
+    /// frame.
+    pub fn pump_for(&mut self, duration: Duration) {
+        let mut remaining = duration;
+
+        while !remaining.is_zero() {
+            let step = remaining.min(FRAME_INTERVAL);
+
+            self.clock.advance(step);
+            remaining -= step;
+
+            self.pump();
+        }
+    }
+
+    /// Pumps frames until none are scheduled, such as once every animation has finished.
+    /// Returns the number of frames that were pumped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if frames are still being scheduled after [`SETTLE_FRAME_LIMIT`] frames.
+    pub fn pump_and_settle(&mut self) -> usize {
+        self.pump();
+
+        let mut frames = 0;
+
+        while self.has_scheduled_frame() {
+            assert!(
+                frames < SETTLE_FRAME_LIMIT,
+                "frames were still being scheduled after {SETTLE_FRAME_LIMIT} frames"
+            );
+
+            self.clock.advance(FRAME_INTERVAL);
+
+            self.pump();
+
+            frames += 1;
+        }
+
+        frames
+    }
+
+    pub fn has_scheduled_frame(&self) -> bool {
+        self.engine
+            .plugins()
+            .get::<FrameSchedulerPlugin>()
+            .is_some_and(FrameSchedulerPlugin::has_scheduled_frame)
+    }
+
+    /// Query widgets from the tree.
+    pub fn query(&self) -> WidgetQuery<'_> {
+        self.engine.query()
+    }
+
+    /// Finds the first element whose widget is of the given type.
+    pub fn find_by_type<W>(&self) -> Option<&Element>
+    where
+        W: AnyWidget,
+    {
+        self.query().by_type::<W>().next()
+    }
+
+    /// Finds the element whose widget has the given key.
+    pub fn find_by_key(&self, key: Key) -> Option<&Element> {
+        self.query().by_key(key).next()
+    }
+
+    /// Finds the first element whose widget is a text widget with the given text.
+    pub fn find_text(&self, text: &str) -> Option<&Element> {
+        self.query()
+            .find(|element| iter::once(*element).with_text(text).next().is_some())
+    }
+
+    /// The size the element was laid out with, if it has been laid out.
+    pub fn size_of(&self, element: &Element) -> Option<Size> {
+        let render_object_id = element.render_object_id()?;
+
+        self.engine.render_objects().get(render_object_id)?.size()
+    }
+
+    /// The offset of the element from the top left of the surface.
+    pub fn offset_of(&self, element: &Element) -> Option<Offset> {
+        let render_objects = self.engine.render_objects();
+
+        let mut current_id = element.render_object_id();
+
+        let mut offset = Offset::ZERO;
+
+        while let Some(render_object_id) = current_id {
+            offset += render_objects.get(render_object_id)?.offset();
+
+            current_id = render_objects.get_parent(render_object_id);
+        }
+
+        Some(offset)
+    }
+
+    /// The area the element occupies on the surface, if it has been laid out.
+    pub fn rect_of(&self, element: &Element) -> Option<Rect> {
+        let size = self.size_of(element)?;
+        let offset = self.offset_of(element)?;
+
+        Some(Rect::new(offset.x, offset.y, size.width, size.height))
+    }
+
+    /// The position of the center of the element on the surface, if it has been laid out.
+    pub fn center_of(&self, element: &Element) -> Option<Offset> {
+        let rect = self.rect_of(element)?;
+
+        Some(Offset::new(
+            rect.left + rect.width / 2.0,
+            rect.top + rect.height / 2.0,
+        ))
+    }
+
+    /// Serializes the render tree of the widget under test into a human-readable snapshot. See
+    /// [`render_tree_snapshot`](crate::render_tree_snapshot) for details.
+    pub fn layout_snapshot(&self) -> String {
+        let render_objects = self.engine.render_objects();
+
+        self.find_by_type::<TestSurface>()
+            .and_then(Element::render_object_id)
+            .and_then(|surface_id| render_objects.get_children(surface_id))
+            .into_iter()
+            .flatten()
+            .map(|child_id| render_subtree_snapshot(render_objects, *child_id))
+            .collect()
+    }
+
+    /// Compares the layout of the widget under test against the snapshot stored at
+    /// `tests/snapshots/{name}.snap` in the crate being tested, panicking with a readable diff if
+    /// they differ. See [`assert_snapshot`] for details.
+    pub fn assert_layout_snapshot(&self, name: &str) {
+        let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_default();
+
+        assert_snapshot(
+            manifest_dir
+                .join("tests")
+                .join("snapshots")
+                .join(format!("{name}.snap")),
+            &self.layout_snapshot(),
+        );
+    }
+
+    /// Renders the widget under test into an image the size of the surface.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the render tree could not be drawn.
+    pub fn render(&self) -> Framebuffer {
+        let framebuffer = Framebuffer::new(
+            self.size.width.ceil() as u32,
+            self.size.height.ceil() as u32,
+        );
+
+        self.renderer
+            .rasterize(&self.engine, &framebuffer)
+            .expect("failed to render the widget under test");
+
+        framebuffer
+    }
+
+    /// Compares a render of the widget under test against the golden image stored at
+    /// `tests/goldens/{name}.png` in the crate being tested, allowing
+    /// [`DEFAULT_GOLDEN_TOLERANCE`] of difference per channel. See [`assert_golden`] for details.
+    pub fn assert_golden(&self, name: &str) {
+        self.assert_golden_with_tolerance(name, DEFAULT_GOLDEN_TOLERANCE);
+    }
+
+    /// Compares a render of the widget under test against the golden image stored at
+    /// `tests/goldens/{name}.png` in the crate being tested, allowing the given difference per
+    /// channel. See [`assert_golden`] for details.
+    pub fn assert_golden_with_tolerance(&self, name: &str, tolerance: u8) {
+        let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR")
+            .map(PathBuf::from)
+            .unwrap_or_default();
+
+        assert_golden(
+            manifest_dir
+                .join("tests")
+                .join("goldens")
+                .join(format!("{name}.png")),
+            &self.render(),
+            tolerance,
+        );
+    }
+
+    /// Presses and releases a pointer at the given position, then pumps the engine.
+    pub fn tap_at(&mut self, position: Offset) {
+        let result = self.engine.hit_test(position);
+
+        dispatch_pointer_event(&result, PointerEvent::new(PointerEventKind::Down, position));
+        dispatch_pointer_event(&result, PointerEvent::new(PointerEventKind::Up, position));
+
+        self.pump();
+    }
+
+    /// Presses a pointer at the given position, moves it by the given offset, and releases it,
+    /// then pumps the engine.
+    ///
+    /// Every event is sent to the widgets that were under the pointer when it was pressed.
+    pub fn drag_from(&mut self, start: Offset, delta: Offset) {
+        let end = start + delta;
+
+        let result = self.engine.hit_test(start);
+
+        dispatch_pointer_event(&result, PointerEvent::new(PointerEventKind::Down, start));
+        dispatch_pointer_event(
+            &result,
+            PointerEvent::new(PointerEventKind::Move, end).with_delta(delta),
+        );
+        dispatch_pointer_event(&result, PointerEvent::new(PointerEventKind::Up, end));
+
+        self.pump();
+    }
+
+    /// Sends a key event to the widgets at the given position, then pumps the engine.
+    pub fn send_key_at(&mut self, position: Offset, event: KeyEvent) {
+        let result = self.engine.hit_test(position);
+
+        dispatch_key_event(&result, event);
+
+        self.pump();
+    }
+
+    /// Presses and releases a key for each character of the text, sending them to the widgets
+    /// at the given position, then pumps the engine.
+    pub fn enter_text_at(&mut self, position: Offset, text: &str) {
+        let result = self.engine.hit_test(position);
+
+        for character in text.chars() {
+            let key = KeyboardKey::Character(character);
+
+            dispatch_key_event(&result, KeyEvent::pressed(key));
+            dispatch_key_event(&result, KeyEvent::released(key));
+        }
+
+        self.pump();
+    }
+}
+
+impl std::fmt::Debug for WidgetTester {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WidgetTester")
+            .field("clock", &self.clock)
+            .field("size", &self.size)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use agui_core::{
+        animation::AnimationController,
+        callback::Callback,
+        plugin::context::ContextPlugins,
+        scheduler::FrameSchedulerPlugin,
+        timer::TimerPlugin,
+        unit::{Axis, Color, EdgeInsets, Offset, Shape, Size, TextStyle},
+    };
+    use agui_gestures::{
+        event::{Key, KeyEvent, PointerEvent, PointerEventKind},
+        listener::Listener,
+    };
+    use agui_macros::build;
+    use agui_primitives::{
+        align::Center,
+        clip::Clip,
+        colored_box::ColoredBox,
+        flex::{Column, Flex, Flexible},
+        intrinsic::IntrinsicWidth,
+        padding::Padding,
+        sized_box::SizedBox,
+        stack::Stack,
+        text::Text,
+    };
+    use parking_lot::Mutex;
+
+    use super::WidgetTester;
+
+    #[test]
+    pub fn reads_the_layout_of_found_widgets() {
+        let tester = WidgetTester::with_size(
+            build! {
+                <Padding> {
+                    padding: EdgeInsets::all(10.0),
+
+                    child: <Text> {
+                        text: "foo".into(),
+                    },
+                }
+            },
+            Size::new(100.0, 50.0),
+        );
+
+        let text = tester.find_text("foo").expect("should have found the text");
+
+        assert_eq!(tester.size_of(text), Some(Size::new(80.0, 30.0)));
+        assert_eq!(tester.offset_of(text), Some(Offset::new(10.0, 10.0)));
+        assert_eq!(tester.center_of(text), Some(Offset::new(50.0, 25.0)));
+
+        assert!(
+            tester.find_text("bar").is_none(),
+            "should not have found text that does not exist"
+        );
+    }
+
+    #[test]
+    pub fn snapshots_the_layout_of_the_widget_under_test() {
+        let tester = WidgetTester::with_size(
+            build! {
+                <Padding> {
+                    padding: EdgeInsets::all(10.0),
+
+                    child: <SizedBox> {
+                        width: Some(20.0),
+                    },
+                }
+            },
+            Size::new(100.0, 50.0),
+        );
+
+        assert_eq!(
+            tester.layout_snapshot(),
+            concat!(
+                "Padding size=(100 x 50) offset=(0, 0)\n",
+                "  SizedBox size=(80 x 30) offset=(10, 10)\n",
+            )
+        );
+    }
+
+    #[test]
+    pub fn matches_snapshot_flex() {
+        let tester = WidgetTester::with_size(
+            build! {
+                <Flex> {
+                    direction: Axis::Horizontal,
+
+                    children: [
+                        build! {
+                            <SizedBox> {
+                                width: Some(20.0),
+                                height: Some(10.0),
+                            }
+                        },
+                        build! {
+                            <Flexible> {
+                                flex: Some(1.0),
+
+                                child: <SizedBox> {
+                                    height: Some(10.0),
+                                },
+                            }
+                        },
+                        build! {
+                            <SizedBox> {
+                                width: Some(30.0),
+                                height: Some(20.0),
+                            }
+                        },
+                    ],
+                }
+            },
+            Size::new(100.0, 50.0),
+        );
+
+        tester.assert_layout_snapshot("flex");
+    }
+
+    #[test]
+    pub fn matches_snapshot_stack() {
+        let tester = WidgetTester::with_size(
+            build! {
+                <Stack> {
+                    children: [
+                        build! {
+                            <SizedBox> {
+                                width: Some(40.0),
+                                height: Some(20.0),
+                            }
+                        },
+                        build! {
+                            <Padding> {
+                                padding: EdgeInsets::all(5.0),
+
+                                child: <SizedBox> {
+                                    width: Some(10.0),
+                                    height: Some(10.0),
+                                },
+                            }
+                        },
+                    ],
+                }
+            },
+            Size::new(100.0, 50.0),
+        );
+
+        tester.assert_layout_snapshot("stack");
+    }
+
+    #[test]
+    pub fn matches_snapshot_intrinsic_width() {
+        let tester = WidgetTester::with_size(
+            build! {
+                <Center> {
+                    child: <IntrinsicWidth> {
+                        child: <Column> {
+                            children: [
+                                build! {
+                                    <SizedBox> {
+                                        width: Some(30.0),
+                                        height: Some(10.0),
+                                    }
+                                },
+                                build! {
+                                    <SizedBox> {
+                                        width: Some(50.0),
+                                        height: Some(10.0),
+                                    }
+                                },
+                            ],
+                        },
+                    },
+                }
+            },
+            Size::new(100.0, 50.0),
+        );
+
+        tester.assert_layout_snapshot("intrinsic_width");
+    }
+
+    #[test]
+    pub fn pumping_frames_fires_timers() {
+        let mut tester = WidgetTester::new(build! {
+            <SizedBox> {
+                width: Some(10.0),
+            }
+        });
+
+        let count = Arc::new(AtomicUsize::new(0));
+
+        tester
+            .engine()
+            .plugins()
+            .get::<TimerPlugin>()
+            .expect("no timer plugin")
+            .set_timeout(
+                tester.engine().root(),
+                Duration::from_millis(100),
+                Callback::from({
+                    let count = Arc::clone(&count);
+
+                    move |()| {
+                        count.fetch_add(1, Ordering::Relaxed);
+                    }
+                }),
+            );
+
+        tester.pump_for(Duration::from_millis(96));
+
+        assert_eq!(
+            count.load(Ordering::Relaxed),
+            0,
+            "should not fire before the delay has elapsed"
+        );
+
+        tester.pump_for(Duration::from_millis(16));
+
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+
+        assert_eq!(
+            tester.pump_and_settle(),
+            0,
+            "timers should not schedule frames"
+        );
+    }
+
+    #[test]
+    pub fn pumping_frames_runs_animations_until_they_settle() {
+        let mut tester = WidgetTester::new(build! {
+            <SizedBox> {
+                width: Some(10.0),
+            }
+        });
+
+        let controller = AnimationController::new(
+            tester
+                .engine()
+                .plugins()
+                .get::<FrameSchedulerPlugin>()
+                .expect("no frame scheduler plugin"),
+            Duration::from_millis(160),
+        );
+
+        controller.forward();
+
+        assert!(tester.has_scheduled_frame());
+
+        // The first frame starts the animation, so only 64ms of it have elapsed after five.
+        tester.pump_for(Duration::from_millis(80));
+
+        assert!((controller.value() - 0.4).abs() < 0.001);
+        assert!(controller.is_animating());
+
+        let frames = tester.pump_and_settle();
+
+        assert_eq!(frames, 6, "should have pumped the remaining 96ms of frames");
+
+        assert_eq!(controller.value(), 1.0);
+        assert!(!controller.is_animating());
+        assert!(!tester.has_scheduled_frame());
+    }
+
+    #[test]
+    pub fn sends_input_to_widgets_under_the_pointer() {
+        let pointer_events = Arc::new(Mutex::new(Vec::<PointerEvent>::new()));
+        let key_events = Arc::new(Mutex::new(Vec::<KeyEvent>::new()));
+
+        let mut tester = WidgetTester::new(build! {
+            <Padding> {
+                padding: EdgeInsets::all(10.0),
+
+                child: <Listener> {
+                    on_pointer: {
+                        let pointer_events = Arc::clone(&pointer_events);
+
+                        move |event: PointerEvent| pointer_events.lock().push(event)
+                    },
+
+                    on_key: {
+                        let key_events = Arc::clone(&key_events);
+
+                        move |event: KeyEvent| key_events.lock().push(event)
+                    },
+
+                    child: <SizedBox> {},
+                },
+            }
+        });
+
+        tester.tap_at(Offset::new(5.0, 5.0));
+
+        assert!(
+            pointer_events.lock().is_empty(),
+            "should not have received a tap outside of the listener"
+        );
+
+        tester.tap_at(Offset::new(20.0, 30.0));
+
+        {
+            let pointer_events = pointer_events.lock();
+
+            assert_eq!(
+                pointer_events
+                    .iter()
+                    .map(|event| event.kind)
+                    .collect::<Vec<_>>(),
+                [PointerEventKind::Down, PointerEventKind::Up]
+            );
+
+            assert_eq!(
+                pointer_events[0].local_position,
+                Offset::new(10.0, 20.0),
+                "should have been relative to the listener"
+            );
+        }
+
+        pointer_events.lock().clear();
+
+        tester.drag_from(Offset::new(20.0, 30.0), Offset::new(100.0, 0.0));
+
+        assert_eq!(
+            pointer_events
+                .lock()
+                .iter()
+                .map(|event| (event.kind, event.position))
+                .collect::<Vec<_>>(),
+            [
+                (PointerEventKind::Down, Offset::new(20.0, 30.0)),
+                (PointerEventKind::Move, Offset::new(120.0, 30.0)),
+                (PointerEventKind::Up, Offset::new(120.0, 30.0)),
+            ]
+        );
+
+        tester.enter_text_at(Offset::new(20.0, 30.0), "hi");
+
+        assert_eq!(
+            *key_events.lock(),
+            [
+                KeyEvent::pressed(Key::Character('h')),
+                KeyEvent::released(Key::Character('h')),
+                KeyEvent::pressed(Key::Character('i')),
+                KeyEvent::released(Key::Character('i')),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn matches_golden_colored_boxes() {
+        let tester = WidgetTester::with_size(
+            build! {
+                <ColoredBox> {
+                    color: Color::from_rgb((1.0, 0.0, 0.0)),
+
+                    child: <Padding> {
+                        padding: EdgeInsets::all(16.0),
+
+                        child: <ColoredBox> {
+                            color: Color::from_rgb((0.0, 0.0, 1.0)),
+                        },
+                    },
+                }
+            },
+            Size::new(64.0, 64.0),
+        );
+
+        let framebuffer = tester.render();
+
+        assert_eq!(framebuffer.pixel(8, 8), Some([255, 0, 0, 255]));
+        assert_eq!(framebuffer.pixel(32, 32), Some([0, 0, 255, 255]));
+
+        tester.assert_golden("colored_boxes");
+    }
+
+    #[test]
+    pub fn matches_golden_clips() {
+        for (name, shape) in [
+            (
+                "rounded_rect_clip",
+                Shape::RoundedRect {
+                    top_left: 16.0,
+                    top_right: 8.0,
+                    bottom_right: 16.0,
+                    bottom_left: 0.0,
+                },
+            ),
+            ("circle_clip", Shape::Circle),
+        ] {
+            let tester = WidgetTester::with_size(
+                build! {
+                    <Padding> {
+                        padding: EdgeInsets::all(8.0),
+
+                        child: <Clip> {
+                            shape: shape,
+                            anti_alias: true,
+
+                            child: <ColoredBox> {
+                                color: Color::from_rgb((0.0, 0.5, 0.0)),
+                            },
+                        },
+                    }
+                },
+                Size::new(64.0, 64.0),
+            );
+
+            let framebuffer = tester.render();
+
+            assert_eq!(
+                framebuffer.pixel(9, 9),
+                Some([0, 0, 0, 0]),
+                "should have clipped the corner of the box"
+            );
+
+            tester.assert_golden(name);
+        }
+    }
+
+    #[test]
+    pub fn matches_golden_text() {
+        let mut tester = WidgetTester::with_size(
+            build! {
+                <ColoredBox> {
+                    color: Color::from_rgb((1.0, 1.0, 1.0)),
+
+                    child: <Padding> {
+                        padding: EdgeInsets::all(4.0),
+
+                        child: <Text> {
+                            style: TextStyle::new().size(20.0),
+                            text: "Hello, world!".into(),
+                        },
+                    },
+                }
+            },
+            Size::new(96.0, 64.0),
+        );
+
+        tester
+            .add_font(include_bytes!("../../../examples/fonts/DejaVuSans.ttf").to_vec())
+            .expect("failed to load the font");
+
+        tester.assert_golden("text");
+    }
+}
@@ -0,0 +1,152 @@
+use std::{io, path::Path, sync::Arc};
+
+use agui_core::unit::Size;
+use parking_lot::Mutex;
+use tiny_skia::{ColorU8, IntSize, Pixmap};
+
+/// An RGBA image that rendered frames are presented to.
+///
+/// Framebuffers are cheap to clone, and every clone refers to the same image, so one may be
+/// bound to a view while another is used to read back what it presented.
+#[derive(Clone)]
+pub struct Framebuffer {
+    pixmap: Arc<Mutex<Pixmap>>,
+}
+
+impl Framebuffer {
+    /// Creates a fully transparent framebuffer. Each dimension is at least one pixel.
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            pixmap: Arc::new(Mutex::new(create_pixmap(width, height))),
+        }
+    }
+
+    /// Creates a framebuffer from straight (non-premultiplied) RGBA pixel data, row by row.
+    ///
+    /// Returns `None` if the framebuffer is empty or if the data does not match its size.
+    pub fn from_rgba8(width: u32, height: u32, rgba: &[u8]) -> Option<Self> {
+        Some(Self {
+            pixmap: Arc::new(Mutex::new(pixmap_from_rgba8(width, height, rgba)?)),
+        })
+    }
+
+    /// Decodes a framebuffer from a PNG image.
+    pub fn decode_png(data: &[u8]) -> io::Result<Self> {
+        let pixmap = Pixmap::decode_png(data).map_err(io::Error::from)?;
+
+        Ok(Self {
+            pixmap: Arc::new(Mutex::new(pixmap)),
+        })
+    }
+
+    /// Reads a framebuffer from the PNG image at the given path.
+    pub fn load_png(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::decode_png(&std::fs::read(path)?)
+    }
+
+    pub fn width(&self) -> u32 {
+        self.pixmap.lock().width()
+    }
+
+    pub fn height(&self) -> u32 {
+        self.pixmap.lock().height()
+    }
+
+    pub fn size(&self) -> Size {
+        let pixmap = self.pixmap.lock();
+
+        Size::new(pixmap.width() as f32, pixmap.height() as f32)
+    }
+
+    /// Returns the straight (non-premultiplied) RGBA value of the pixel at the given position,
+    /// or `None` if it lies outside of the framebuffer.
+    pub fn pixel(&self, x: u32, y: u32) -> Option<[u8; 4]> {
+        let pixmap = self.pixmap.lock();
+
+        if x >= pixmap.width() || y >= pixmap.height() {
+            return None;
+        }
+
+        pixmap.pixel(x, y).map(|pixel| {
+            let pixel = pixel.demultiply();
+
+            [pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()]
+        })
+    }
+
+    /// Returns the straight (non-premultiplied) RGBA values of every pixel, row by row.
+    pub fn to_rgba8(&self) -> Vec<u8> {
+        self.pixmap
+            .lock()
+            .pixels()
+            .iter()
+            .flat_map(|pixel| {
+                let pixel = pixel.demultiply();
+
+                [pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()]
+            })
+            .collect()
+    }
+
+    /// Encodes the framebuffer as a PNG image.
+    pub fn encode_png(&self) -> io::Result<Vec<u8>> {
+        self.pixmap.lock().encode_png().map_err(io::Error::from)
+    }
+
+    /// Writes the framebuffer to the given path as a PNG image.
+    pub fn save_png(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        self.pixmap.lock().save_png(path).map_err(io::Error::from)
+    }
+
+    pub(crate) fn resize(&self, width: u32, height: u32) {
+        let mut pixmap = self.pixmap.lock();
+
+        if pixmap.width() != width.max(1) || pixmap.height() != height.max(1) {
+            *pixmap = create_pixmap(width, height);
+        }
+    }
+
+    pub(crate) fn present(&self, frame: &Pixmap) {
+        let mut pixmap = self.pixmap.lock();
+
+        if pixmap.width() == frame.width() && pixmap.height() == frame.height() {
+            pixmap.data_mut().copy_from_slice(frame.data());
+        } else {
+            *pixmap = frame.clone();
+        }
+    }
+}
+
+impl std::fmt::Debug for Framebuffer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pixmap = self.pixmap.lock();
+
+        f.debug_struct("Framebuffer")
+            .field("width", &pixmap.width())
+            .field("height", &pixmap.height())
+            .finish_non_exhaustive()
+    }
+}
+
+pub(crate) fn create_pixmap(width: u32, height: u32) -> Pixmap {
+    Pixmap::new(width.max(1), height.max(1)).expect("framebuffer is too large")
+}
+
+/// Creates a pixmap from straight (non-premultiplied) RGBA pixel data, row by row.
+///
+/// Returns `None` if the pixmap is empty or if the data does not match its size.
+pub(crate) fn pixmap_from_rgba8(width: u32, height: u32, rgba: &[u8]) -> Option<Pixmap> {
+    let size = IntSize::from_wh(width, height)?;
+
+    if rgba.len() != size.width() as usize * size.height() as usize * 4 {
+        return None;
+    }
+
+    let mut pixmap = Pixmap::new(size.width(), size.height())?;
+
+    for (pixel, rgba) in pixmap.pixels_mut().iter_mut().zip(rgba.chunks_exact(4)) {
+        *pixel = ColorU8::from_rgba(rgba[0], rgba[1], rgba[2], rgba[3]).premultiply();
+    }
+
+    Some(pixmap)
+}
@@ -0,0 +1,11 @@
+mod framebuffer;
+mod path;
+mod raster;
+mod renderer;
+mod textures;
+mod view;
+
+pub use ab_glyph::InvalidFont;
+pub use framebuffer::Framebuffer;
+pub use renderer::TinySkiaRenderer;
+pub use view::{TinySkiaView, TinySkiaViewRenderer};
@@ -0,0 +1,194 @@
+use ab_glyph::{Font, FontArc, OutlineCurve, Point};
+use agui_core::unit::{Rect, Shape};
+use agui_primitives::text::glyph_layout::{layout_text, FontMetrics};
+use lyon::path::Event;
+use tiny_skia::{Path, PathBuilder};
+
+/// The distance of the control points from the corners when approximating a quarter circle
+/// with a cubic curve, relative to its radius.
+const KAPPA: f32 = 0.552_284_8;
+
+pub fn to_rect(rect: Rect) -> Option<tiny_skia::Rect> {
+    tiny_skia::Rect::from_xywh(rect.left, rect.top, rect.width, rect.height)
+}
+
+/// Builds the outline of a shape that fills the given rect.
+///
+/// Paths are used as-is, since their points are already in the coordinate space of the canvas.
+pub fn build_shape_path(shape: &Shape, rect: Rect) -> Option<Path> {
+    match shape {
+        Shape::Rect => Some(PathBuilder::from_rect(to_rect(rect)?)),
+
+        Shape::RoundedRect {
+            top_left,
+            top_right,
+            bottom_right,
+            bottom_left,
+        } => build_rounded_rect_path(
+            to_rect(rect)?,
+            [*top_left, *top_right, *bottom_right, *bottom_left],
+        ),
+
+        Shape::Circle => PathBuilder::from_oval(to_rect(rect)?),
+
+        Shape::Path(path) => {
+            let mut builder = PathBuilder::new();
+
+            for event in path.iter() {
+                match event {
+                    Event::Begin { at } => builder.move_to(at.x, at.y),
+
+                    Event::Line { to, .. } => builder.line_to(to.x, to.y),
+
+                    Event::Quadratic { ctrl, to, .. } => {
+                        builder.quad_to(ctrl.x, ctrl.y, to.x, to.y);
+                    }
+
+                    Event::Cubic {
+                        ctrl1, ctrl2, to, ..
+                    } => builder.cubic_to(ctrl1.x, ctrl1.y, ctrl2.x, ctrl2.y, to.x, to.y),
+
+                    Event::End { close, .. } => {
+                        if close {
+                            builder.close();
+                        }
+                    }
+                }
+            }
+
+            builder.finish()
+        }
+    }
+}
+
+fn build_rounded_rect_path(rect: tiny_skia::Rect, radii: [f32; 4]) -> Option<Path> {
+    let max_radius = rect.width().min(rect.height()) / 2.0;
+
+    let [top_left, top_right, bottom_right, bottom_left] =
+        radii.map(|radius| radius.clamp(0.0, max_radius));
+
+    let (left, top, right, bottom) = (rect.left(), rect.top(), rect.right(), rect.bottom());
+
+    let mut builder = PathBuilder::new();
+
+    builder.move_to(left + top_left, top);
+
+    builder.line_to(right - top_right, top);
+    builder.cubic_to(
+        right - top_right * (1.0 - KAPPA),
+        top,
+        right,
+        top + top_right * (1.0 - KAPPA),
+        right,
+        top + top_right,
+    );
+
+    builder.line_to(right, bottom - bottom_right);
+    builder.cubic_to(
+        right,
+        bottom - bottom_right * (1.0 - KAPPA),
+        right - bottom_right * (1.0 - KAPPA),
+        bottom,
+        right - bottom_right,
+        bottom,
+    );
+
+    builder.line_to(left + bottom_left, bottom);
+    builder.cubic_to(
+        left + bottom_left * (1.0 - KAPPA),
+        bottom,
+        left,
+        bottom - bottom_left * (1.0 - KAPPA),
+        left,
+        bottom - bottom_left,
+    );
+
+    builder.line_to(left, top + top_left);
+    builder.cubic_to(
+        left,
+        top + top_left * (1.0 - KAPPA),
+        left + top_left * (1.0 - KAPPA),
+        top,
+        left + top_left,
+        top,
+    );
+
+    builder.close();
+
+    builder.finish()
+}
+
+/// Builds the outlines of every glyph in the text, laid out from the top left of the rect using
+/// [`layout_text`] so that it matches the size the text was measured to be.
+pub fn build_text_path(font: &FontArc, size: f32, rect: Rect, text: &str) -> Option<Path> {
+    let metrics = FontMetrics::new(font, size)?;
+    let scale = metrics.scale();
+
+    let mut builder = PathBuilder::new();
+
+    layout_text(&metrics, rect.width, text, |glyph| {
+        let Some(outline) = font.outline(glyph.id) else {
+            return;
+        };
+
+        // Font units point upwards, while the canvas points downwards.
+        let to_canvas = |point: Point| {
+            (
+                rect.left + glyph.x + point.x * scale,
+                rect.top + glyph.baseline - point.y * scale,
+            )
+        };
+
+        let mut last_point = None;
+
+        for curve in &outline.curves {
+            let (start, end) = match *curve {
+                OutlineCurve::Line(start, end)
+                | OutlineCurve::Quad(start, _, end)
+                | OutlineCurve::Cubic(start, _, _, end) => (start, end),
+            };
+
+            // Each contour is a continuous run of curves, so a gap starts a new one.
+            if last_point != Some(start) {
+                if last_point.is_some() {
+                    builder.close();
+                }
+
+                let (x, y) = to_canvas(start);
+
+                builder.move_to(x, y);
+            }
+
+            match *curve {
+                OutlineCurve::Line(_, end) => {
+                    let (x, y) = to_canvas(end);
+
+                    builder.line_to(x, y);
+                }
+
+                OutlineCurve::Quad(_, ctrl, end) => {
+                    let (ctrl_x, ctrl_y) = to_canvas(ctrl);
+                    let (x, y) = to_canvas(end);
+
+                    builder.quad_to(ctrl_x, ctrl_y, x, y);
+                }
+
+                OutlineCurve::Cubic(_, ctrl1, ctrl2, end) => {
+                    let (ctrl1_x, ctrl1_y) = to_canvas(ctrl1);
+                    let (ctrl2_x, ctrl2_y) = to_canvas(ctrl2);
+                    let (x, y) = to_canvas(end);
+
+                    builder.cubic_to(ctrl1_x, ctrl1_y, ctrl2_x, ctrl2_y, x, y);
+                }
+            }
+
+            last_point = Some(end);
+        }
+
+        if last_point.is_some() {
+            builder.close();
+        }
+    });
+
+    builder.finish()
+}
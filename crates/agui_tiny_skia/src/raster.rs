@@ -0,0 +1,600 @@
+use agui_core::{
+    engine::EngineError,
+    render::{
+        canvas::{Canvas, CanvasCommand, CanvasLayer},
+        Paint, RenderObject, RenderObjectId,
+    },
+    unit::{BlendMode, Bounds, Color, Rect},
+    util::tree::Tree,
+};
+use agui_primitives::text::fonts::FontRegistry;
+use tiny_skia::{
+    FillRule, FilterQuality, Mask, Pattern, Pixmap, PixmapPaint, SpreadMode, Transform,
+};
+
+use crate::{
+    path::{build_shape_path, build_text_path},
+    textures::TinySkiaTextures,
+};
+
+/// Draws canvases into a pixmap, looking up any fonts and textures they reference.
+pub struct Rasterizer<'r> {
+    pub fonts: &'r FontRegistry,
+    pub textures: &'r TinySkiaTextures,
+}
+
+impl Rasterizer<'_> {
+    /// Draws a render object and all of its descendants.
+    ///
+    /// Descendants are drawn into the tail layer of their ancestors' canvases, so that they're
+    /// clipped and composited along with it.
+    pub fn draw_render_object(
+        &self,
+        pixmap: &mut Pixmap,
+        render_objects: &Tree<RenderObjectId, RenderObject>,
+        render_object_id: RenderObjectId,
+        transform: Transform,
+    ) -> Result<(), EngineError> {
+        let render_object = render_objects
+            .get(render_object_id)
+            .ok_or(EngineError::MissingRenderObject { render_object_id })?;
+
+        let offset = render_object.offset();
+
+        let transform = transform.pre_translate(offset.x, offset.y);
+
+        let mut draw_children = |pixmap: &mut Pixmap| {
+            for child_id in render_objects
+                .get_children(render_object_id)
+                .into_iter()
+                .flatten()
+            {
+                self.draw_render_object(pixmap, render_objects, *child_id, transform)?;
+            }
+
+            Ok(())
+        };
+
+        match render_object.paint()? {
+            Some(canvas) => self.draw_canvas(pixmap, &canvas, transform, &mut draw_children),
+            None => draw_children(pixmap),
+        }
+    }
+
+    /// Draws a canvas, followed by the `contents` within its tail layer.
+    pub fn draw_canvas<E>(
+        &self,
+        pixmap: &mut Pixmap,
+        canvas: &Canvas,
+        transform: Transform,
+        contents: &mut dyn FnMut(&mut Pixmap) -> Result<(), E>,
+    ) -> Result<(), E> {
+        for command in &canvas.head {
+            self.draw_command(pixmap, &canvas.paints, command, transform);
+        }
+
+        for layer in &canvas.children {
+            self.draw_layer(pixmap, &canvas.paints, layer, transform, &mut |_| Ok(()))?;
+        }
+
+        match &canvas.tail {
+            Some(tail) => self.draw_layer(pixmap, &canvas.paints, tail, transform, contents),
+            None => contents(pixmap),
+        }
+    }
+
+    /// Draws a layer into its own pixmap, then composites it using the paint of its style,
+    /// clipped to the style's shape.
+    fn draw_layer<E>(
+        &self,
+        pixmap: &mut Pixmap,
+        paints: &[Paint],
+        layer: &CanvasLayer,
+        transform: Transform,
+        contents: &mut dyn FnMut(&mut Pixmap) -> Result<(), E>,
+    ) -> Result<(), E> {
+        let paint = paints
+            .get(layer.style.paint_idx)
+            .cloned()
+            .unwrap_or_else(|| {
+                tracing::warn!(
+                    paint_idx = layer.style.paint_idx,
+                    "layer references a paint that does not exist"
+                );
+
+                Paint::default()
+            });
+
+        let transform = transform.pre_translate(layer.offset.x, layer.offset.y);
+
+        let mut layer_pixmap = Pixmap::new(pixmap.width(), pixmap.height())
+            .expect("layer has the same size as its parent");
+
+        self.draw_canvas(&mut layer_pixmap, &layer.canvas, transform, contents)?;
+
+        let mask = build_shape_path(
+            &layer.style.shape,
+            bound_rect(Rect::from(layer.canvas.size), transform, pixmap),
+        )
+        .and_then(|path| {
+            let mut mask = Mask::new(pixmap.width(), pixmap.height())?;
+
+            mask.fill_path(&path, FillRule::Winding, paint.anti_alias, transform);
+
+            Some(mask)
+        });
+
+        pixmap.draw_pixmap(
+            0,
+            0,
+            layer_pixmap.as_ref(),
+            &PixmapPaint {
+                opacity: paint.color.alpha.clamp(0.0, 1.0),
+                blend_mode: to_blend_mode(paint.blend_mode),
+                quality: FilterQuality::Nearest,
+            },
+            Transform::identity(),
+            mask.as_ref(),
+        );
+
+        Ok(())
+    }
+
+    fn draw_command(
+        &self,
+        pixmap: &mut Pixmap,
+        paints: &[Paint],
+        command: &CanvasCommand,
+        transform: Transform,
+    ) {
+        if command.is_noop() {
+            return;
+        }
+
+        match command {
+            CanvasCommand::Shape {
+                paint_idx,
+                rect,
+                shape,
+            } => {
+                let Some(paint) = get_paint(paints, *paint_idx) else {
+                    return;
+                };
+
+                let Some(path) = build_shape_path(shape, bound_rect(*rect, transform, pixmap))
+                else {
+                    return;
+                };
+
+                pixmap.fill_path(&path, &to_paint(paint), FillRule::Winding, transform, None);
+            }
+
+            CanvasCommand::Texture {
+                rect,
+                shape,
+                texture_id,
+                tex_bounds,
+            } => {
+                let Some(texture) = self.textures.get(*texture_id) else {
+                    tracing::warn!(?texture_id, "texture does not exist");
+
+                    return;
+                };
+
+                let Some(path) = build_shape_path(shape, *rect) else {
+                    return;
+                };
+
+                let paint = tiny_skia::Paint {
+                    shader: Pattern::new(
+                        texture.as_ref(),
+                        SpreadMode::Pad,
+                        FilterQuality::Bilinear,
+                        1.0,
+                        texture_transform(texture, *rect, *tex_bounds),
+                    ),
+                    anti_alias: true,
+                    ..tiny_skia::Paint::default()
+                };
+
+                pixmap.fill_path(&path, &paint, FillRule::Winding, transform, None);
+            }
+
+            CanvasCommand::Text {
+                paint_idx,
+                rect,
+                text_style,
+                text,
+            } => {
+                let Some(paint) = get_paint(paints, *paint_idx) else {
+                    return;
+                };
+
+                let Some(font) = self.fonts.get_or_default(text_style.font) else {
+                    tracing::warn!("no font is available to draw text with");
+
+                    return;
+                };
+
+                let Some(path) = build_text_path(font, text_style.size, *rect, text) else {
+                    return;
+                };
+
+                // Glyphs are unreadable without anti-aliasing, so it's always enabled for text.
+                let mut paint = to_paint(paint);
+                paint.anti_alias = true;
+
+                pixmap.fill_path(&path, &paint, FillRule::Winding, transform, None);
+            }
+
+            command => {
+                tracing::error!("unknown command: {:?}", command);
+            }
+        }
+    }
+}
+
+/// Limits unbounded rects, such as those of the root render object, to the visible area of the
+/// pixmap so that they can be drawn. Bounded rects are returned unchanged.
+fn bound_rect(rect: Rect, transform: Transform, pixmap: &Pixmap) -> Rect {
+    if rect.width.is_finite() && rect.height.is_finite() {
+        return rect;
+    }
+
+    let Some(visible) = transform.invert().and_then(|inverse| {
+        tiny_skia::Rect::from_xywh(0.0, 0.0, pixmap.width() as f32, pixmap.height() as f32)?
+            .transform(inverse)
+    }) else {
+        return rect;
+    };
+
+    // Extend past the visible area slightly, so that the edges of the rect aren't anti-aliased.
+    let left = rect.left.max(visible.left() - 1.0);
+    let top = rect.top.max(visible.top() - 1.0);
+    let right = (rect.left + rect.width).min(visible.right() + 1.0);
+    let bottom = (rect.top + rect.height).min(visible.bottom() + 1.0);
+
+    Rect::new(left, top, right - left, bottom - top)
+}
+
+fn get_paint(paints: &[Paint], paint_idx: usize) -> Option<&Paint> {
+    let paint = paints.get(paint_idx);
+
+    if paint.is_none() {
+        tracing::warn!(paint_idx, "command references a paint that does not exist");
+    }
+
+    paint
+}
+
+/// Maps the region of the texture selected by the bounds onto the rect. The bounds are
+/// normalized to the size of the texture, and bounds with no area select the entire texture.
+fn texture_transform(texture: &Pixmap, rect: Rect, tex_bounds: Bounds) -> Transform {
+    let (width, height) = (texture.width() as f32, texture.height() as f32);
+
+    let (left, top, right, bottom) =
+        if tex_bounds.right > tex_bounds.left && tex_bounds.bottom > tex_bounds.top {
+            (
+                tex_bounds.left * width,
+                tex_bounds.top * height,
+                tex_bounds.right * width,
+                tex_bounds.bottom * height,
+            )
+        } else {
+            (0.0, 0.0, width, height)
+        };
+
+    let scale_x = rect.width / (right - left);
+    let scale_y = rect.height / (bottom - top);
+
+    Transform::from_row(
+        scale_x,
+        0.0,
+        0.0,
+        scale_y,
+        rect.left - left * scale_x,
+        rect.top - top * scale_y,
+    )
+}
+
+pub fn to_paint(paint: &Paint) -> tiny_skia::Paint<'static> {
+    let mut result = tiny_skia::Paint {
+        anti_alias: paint.anti_alias,
+        blend_mode: to_blend_mode(paint.blend_mode),
+        ..tiny_skia::Paint::default()
+    };
+
+    result.set_color(to_color(paint.color));
+
+    result
+}
+
+pub fn to_color(color: Color) -> tiny_skia::Color {
+    tiny_skia::Color::from_rgba(
+        color.red.clamp(0.0, 1.0),
+        color.green.clamp(0.0, 1.0),
+        color.blue.clamp(0.0, 1.0),
+        color.alpha.clamp(0.0, 1.0),
+    )
+    .unwrap_or(tiny_skia::Color::TRANSPARENT)
+}
+
+pub fn to_blend_mode(blend_mode: BlendMode) -> tiny_skia::BlendMode {
+    match blend_mode {
+        BlendMode::Clear => tiny_skia::BlendMode::Clear,
+        BlendMode::Source => tiny_skia::BlendMode::Source,
+        BlendMode::Destination => tiny_skia::BlendMode::Destination,
+        BlendMode::SourceOver => tiny_skia::BlendMode::SourceOver,
+        BlendMode::DestinationOver => tiny_skia::BlendMode::DestinationOver,
+        BlendMode::SourceIn => tiny_skia::BlendMode::SourceIn,
+        BlendMode::DestinationIn => tiny_skia::BlendMode::DestinationIn,
+        BlendMode::SourceOut => tiny_skia::BlendMode::SourceOut,
+        BlendMode::DestinationOut => tiny_skia::BlendMode::DestinationOut,
+        BlendMode::SourceAlphaTop => tiny_skia::BlendMode::SourceAtop,
+        BlendMode::DestinationAlphaTop => tiny_skia::BlendMode::DestinationAtop,
+        BlendMode::XOr => tiny_skia::BlendMode::Xor,
+        BlendMode::Plus => tiny_skia::BlendMode::Plus,
+        BlendMode::Modulate => tiny_skia::BlendMode::Modulate,
+        BlendMode::Screen => tiny_skia::BlendMode::Screen,
+        BlendMode::Overlay => tiny_skia::BlendMode::Overlay,
+        BlendMode::Darken => tiny_skia::BlendMode::Darken,
+        BlendMode::Lighten => tiny_skia::BlendMode::Lighten,
+        BlendMode::ColorDodge => tiny_skia::BlendMode::ColorDodge,
+        BlendMode::ColorBurn => tiny_skia::BlendMode::ColorBurn,
+        BlendMode::HardLight => tiny_skia::BlendMode::HardLight,
+        BlendMode::SoftLight => tiny_skia::BlendMode::SoftLight,
+        BlendMode::Difference => tiny_skia::BlendMode::Difference,
+        BlendMode::Exclusion => tiny_skia::BlendMode::Exclusion,
+        BlendMode::Multiply => tiny_skia::BlendMode::Multiply,
+        BlendMode::Hue => tiny_skia::BlendMode::Hue,
+        BlendMode::Saturation => tiny_skia::BlendMode::Saturation,
+        BlendMode::Color => tiny_skia::BlendMode::Color,
+        BlendMode::Luminosity => tiny_skia::BlendMode::Luminosity,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use agui_core::{
+        render::{
+            canvas::{Canvas, CanvasCommand},
+            CanvasPainter, Paint,
+        },
+        unit::{BlendMode, Color, Rect, Shape, Size, TextStyle},
+    };
+    use agui_primitives::text::fonts::FontRegistry;
+    use tiny_skia::{Pixmap, Transform};
+
+    use crate::textures::TinySkiaTextures;
+
+    use super::Rasterizer;
+
+    const RED: Color = Color::from_rgb((1.0, 0.0, 0.0));
+    const BLUE: Color = Color::from_rgb((0.0, 0.0, 1.0));
+
+    fn paint_canvas(size: Size, func: impl FnOnce(CanvasPainter)) -> Canvas {
+        let mut canvas = Canvas {
+            size,
+
+            paints: Vec::default(),
+
+            head: Vec::default(),
+            children: Vec::default(),
+            tail: None,
+        };
+
+        func(CanvasPainter::begin(&mut canvas));
+
+        canvas
+    }
+
+    fn rasterize(canvas: &Canvas, fonts: &FontRegistry, textures: &TinySkiaTextures) -> Pixmap {
+        let mut pixmap = Pixmap::new(canvas.size.width as u32, canvas.size.height as u32).unwrap();
+
+        Rasterizer { fonts, textures }
+            .draw_canvas::<()>(&mut pixmap, canvas, Transform::identity(), &mut |_| Ok(()))
+            .unwrap();
+
+        pixmap
+    }
+
+    fn pixel(pixmap: &Pixmap, x: u32, y: u32) -> [u8; 4] {
+        let pixel = pixmap.pixel(x, y).unwrap().demultiply();
+
+        [pixel.red(), pixel.green(), pixel.blue(), pixel.alpha()]
+    }
+
+    #[test]
+    pub fn fills_shapes() {
+        let canvas = paint_canvas(Size::new(100.0, 100.0), |mut canvas| {
+            let brush = canvas.add_paint(Paint {
+                color: RED,
+                ..Paint::default()
+            });
+
+            canvas.draw_rect_at(Rect::new(10.0, 10.0, 30.0, 30.0), &brush);
+            canvas.draw_rounded_rect_at(
+                Rect::new(50.0, 50.0, 40.0, 40.0),
+                &brush,
+                20.0,
+                20.0,
+                20.0,
+                20.0,
+            );
+        });
+
+        let pixmap = rasterize(
+            &canvas,
+            &FontRegistry::default(),
+            &TinySkiaTextures::default(),
+        );
+
+        assert_eq!(pixel(&pixmap, 20, 20), [255, 0, 0, 255]);
+        assert_eq!(
+            pixel(&pixmap, 45, 20),
+            [0, 0, 0, 0],
+            "should only fill the rect"
+        );
+
+        assert_eq!(pixel(&pixmap, 70, 70), [255, 0, 0, 255]);
+        assert_eq!(
+            pixel(&pixmap, 51, 51),
+            [0, 0, 0, 0],
+            "should not have filled the rounded corners"
+        );
+    }
+
+    #[test]
+    pub fn clips_layers_to_their_shape() {
+        let canvas = paint_canvas(Size::new(100.0, 100.0), |mut canvas| {
+            let layer_brush = canvas.add_paint(Paint::default());
+
+            canvas.layer(&layer_brush, Shape::Circle, |mut canvas| {
+                let brush = canvas.add_paint(Paint {
+                    color: RED,
+                    ..Paint::default()
+                });
+
+                canvas.draw_rect(&brush);
+            });
+        });
+
+        let pixmap = rasterize(
+            &canvas,
+            &FontRegistry::default(),
+            &TinySkiaTextures::default(),
+        );
+
+        assert_eq!(pixel(&pixmap, 50, 50), [255, 0, 0, 255]);
+        assert_eq!(
+            pixel(&pixmap, 2, 2),
+            [0, 0, 0, 0],
+            "should have clipped the corners of the layer"
+        );
+    }
+
+    #[test]
+    pub fn composites_layers_with_their_paint() {
+        let canvas = paint_canvas(Size::new(10.0, 10.0), |mut canvas| {
+            let background = canvas.add_paint(Paint {
+                color: Color::from_rgb((1.0, 0.0, 1.0)),
+                ..Paint::default()
+            });
+
+            canvas.draw_rect(&background);
+
+            let layer_brush = canvas.add_paint(Paint {
+                blend_mode: BlendMode::Multiply,
+                ..Paint::default()
+            });
+
+            canvas.layer(&layer_brush, Shape::Rect, |mut canvas| {
+                let brush = canvas.add_paint(Paint {
+                    color: BLUE,
+                    ..Paint::default()
+                });
+
+                canvas.draw_rect(&brush);
+            });
+        });
+
+        let pixmap = rasterize(
+            &canvas,
+            &FontRegistry::default(),
+            &TinySkiaTextures::default(),
+        );
+
+        assert_eq!(pixel(&pixmap, 5, 5), [0, 0, 255, 255]);
+
+        let canvas = paint_canvas(Size::new(10.0, 10.0), |mut canvas| {
+            let layer_brush = canvas.add_paint(Paint {
+                color: Color::from_rgba((1.0, 1.0, 1.0, 0.5)),
+                ..Paint::default()
+            });
+
+            canvas.layer(&layer_brush, Shape::Rect, |mut canvas| {
+                let brush = canvas.add_paint(Paint {
+                    color: RED,
+                    ..Paint::default()
+                });
+
+                canvas.draw_rect(&brush);
+            });
+        });
+
+        let pixmap = rasterize(
+            &canvas,
+            &FontRegistry::default(),
+            &TinySkiaTextures::default(),
+        );
+
+        assert_eq!(
+            pixel(&pixmap, 5, 5),
+            [255, 0, 0, 128],
+            "should have used the alpha of the layer's paint as its opacity"
+        );
+    }
+
+    #[test]
+    pub fn draws_textures() {
+        let mut textures = TinySkiaTextures::default();
+
+        let texture_id = textures
+            .add_texture(2, 1, &[255, 0, 0, 255, 0, 0, 255, 255])
+            .unwrap();
+
+        let mut canvas = paint_canvas(Size::new(10.0, 10.0), |_| {});
+
+        canvas.head.push(CanvasCommand::Texture {
+            rect: Rect::new(0.0, 0.0, 10.0, 10.0),
+            shape: Shape::Rect,
+            texture_id,
+            tex_bounds: Default::default(),
+        });
+
+        let pixmap = rasterize(&canvas, &FontRegistry::default(), &textures);
+
+        assert_eq!(pixel(&pixmap, 1, 5), [255, 0, 0, 255]);
+        assert_eq!(pixel(&pixmap, 8, 5), [0, 0, 255, 255]);
+    }
+
+    #[test]
+    pub fn draws_text() {
+        let mut fonts = FontRegistry::default();
+
+        fonts.add_font(
+            ab_glyph::FontArc::try_from_slice(include_bytes!(
+                "../../../examples/fonts/DejaVuSans.ttf"
+            ))
+            .unwrap(),
+        );
+
+        let canvas = paint_canvas(Size::new(100.0, 40.0), |mut canvas| {
+            let brush = canvas.add_paint(Paint {
+                color: RED,
+                ..Paint::default()
+            });
+
+            canvas.draw_text_at(
+                Rect::new(0.0, 0.0, 50.0, 40.0),
+                &brush,
+                TextStyle::default().size(32.0),
+                "I",
+            );
+        });
+
+        let pixmap = rasterize(&canvas, &fonts, &TinySkiaTextures::default());
+
+        let covered = (0..pixmap.height())
+            .flat_map(|y| (0..pixmap.width()).map(move |x| (x, y)))
+            .filter(|&(x, y)| pixel(&pixmap, x, y)[3] > 0)
+            .collect::<Vec<_>>();
+
+        assert!(!covered.is_empty(), "should have drawn the glyph");
+
+        assert!(
+            covered.iter().all(|&(x, y)| x < 20 && y < 32),
+            "should have drawn the glyph at the top left of its rect"
+        );
+    }
+}
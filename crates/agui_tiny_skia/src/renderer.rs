@@ -0,0 +1,281 @@
+use std::sync::Arc;
+
+use ab_glyph::{FontArc, InvalidFont};
+use agui_core::{
+    engine::{Engine, EngineError},
+    unit::{Font, Size, TextureId},
+};
+use agui_primitives::text::{fonts::FontRegistry, glyph_layout::GlyphLayoutDelegate};
+use agui_renderer::{BufferedViews, RenderViewId, Renderer, ViewRenderer};
+use parking_lot::Mutex;
+use tiny_skia::Transform;
+
+use crate::{
+    framebuffer::{create_pixmap, Framebuffer},
+    raster::Rasterizer,
+    textures::TinySkiaTextures,
+    view::TinySkiaView,
+};
+
+/// Renders views entirely on the CPU, into [`Framebuffer`]s.
+///
+/// Since rendering doesn't require a GPU, this is suitable for headless environments such as
+/// continuous integration.
+#[derive(Default)]
+pub struct TinySkiaRenderer {
+    fonts: Arc<Mutex<FontRegistry>>,
+    textures: Arc<Mutex<TinySkiaTextures>>,
+
+    views: BufferedViews<TinySkiaView>,
+}
+
+impl TinySkiaRenderer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads a font from its raw data. The first font that is added is used to draw any text
+    /// which doesn't specify one.
+    pub fn add_font(&self, font_data: Vec<u8>) -> Result<Font, InvalidFont> {
+        let font = FontArc::try_from_vec(font_data)?;
+
+        Ok(self.fonts.lock().add_font(font))
+    }
+
+    /// Creates a delegate that measures text with the renderer's fonts, to be given to a
+    /// [`TextLayoutController`](agui_primitives::text::layout_controller::TextLayoutController).
+    pub fn text_layout_delegate(&self) -> GlyphLayoutDelegate {
+        GlyphLayoutDelegate::new(Arc::clone(&self.fonts))
+    }
+
+    /// The fonts the renderer draws with, which may be shared with other backends so that they
+    /// draw text with the same fonts.
+    pub fn fonts(&self) -> Arc<Mutex<FontRegistry>> {
+        Arc::clone(&self.fonts)
+    }
+
+    /// Adds a texture from straight (non-premultiplied) RGBA pixel data, row by row.
+    ///
+    /// Returns `None` if the texture is empty or if the data does not match its size.
+    pub fn add_texture(&self, width: u32, height: u32, rgba: &[u8]) -> Option<TextureId> {
+        self.textures.lock().add_texture(width, height, rgba)
+    }
+
+    /// Redraws every bound view from the engine's render tree. The new frames are not presented
+    /// until each view is rendered.
+    pub fn redraw(&self, engine: &Engine) -> Result<(), EngineError> {
+        self.views.redraw(engine)
+    }
+
+    /// Draws the engine's render tree directly into the framebuffer, replacing its contents.
+    pub fn rasterize(&self, engine: &Engine, framebuffer: &Framebuffer) -> Result<(), EngineError> {
+        let mut pixmap = create_pixmap(framebuffer.width(), framebuffer.height());
+
+        if let Some(root_id) = engine.render_objects().root() {
+            let fonts = self.fonts.lock();
+            let textures = self.textures.lock();
+
+            Rasterizer {
+                fonts: &fonts,
+                textures: &textures,
+            }
+            .draw_render_object(
+                &mut pixmap,
+                engine.render_objects(),
+                root_id,
+                Transform::identity(),
+            )?;
+        }
+
+        framebuffer.present(&pixmap);
+
+        Ok(())
+    }
+}
+
+impl Renderer for TinySkiaRenderer {
+    type Target = Framebuffer;
+
+    fn bind(
+        &self,
+        render_view_id: RenderViewId,
+        target: &Self::Target,
+        size: Size,
+    ) -> Result<Arc<dyn ViewRenderer>, Box<dyn std::error::Error>> {
+        target.resize(size.width.floor() as u32, size.height.floor() as u32);
+
+        Ok(self.views.bind(
+            render_view_id,
+            TinySkiaView::new(
+                Arc::clone(&self.fonts),
+                Arc::clone(&self.textures),
+                target.clone(),
+            ),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use agui_core::{
+        element::mock::render::{MockRenderObject, MockRenderWidget},
+        engine::Engine,
+        render::{canvas::Canvas, CanvasPainter, Paint},
+        unit::{Color, Offset, Rect, Shape, Size},
+        widget::IntoWidget,
+    };
+    use agui_renderer::{RenderViewId, Renderer};
+
+    use crate::Framebuffer;
+
+    use super::TinySkiaRenderer;
+
+    fn paint_canvas(size: Size, func: impl FnOnce(CanvasPainter)) -> Canvas {
+        let mut canvas = Canvas {
+            size,
+
+            paints: Vec::default(),
+
+            head: Vec::default(),
+            children: Vec::default(),
+            tail: None,
+        };
+
+        func(CanvasPainter::begin(&mut canvas));
+
+        canvas
+    }
+
+    #[test]
+    pub fn renders_views_into_framebuffers() {
+        let child_widget = MockRenderWidget::new("ChildWidget");
+        {
+            child_widget
+                .mock
+                .borrow_mut()
+                .expect_children()
+                .returning(Vec::new);
+
+            child_widget
+                .mock
+                .borrow_mut()
+                .expect_create_render_object()
+                .returning(|| {
+                    let render_object = MockRenderObject::new("ChildRenderObject");
+                    {
+                        let mut render_object_mock = render_object.mock.lock();
+
+                        render_object_mock
+                            .expect_layout()
+                            .returning(|_, constraints| constraints.biggest());
+
+                        render_object_mock.expect_paint().returning(|size| {
+                            Some(paint_canvas(size, |mut canvas| {
+                                let brush = canvas.add_paint(Paint {
+                                    color: Color::from_rgb((1.0, 0.0, 0.0)),
+                                    ..Paint::default()
+                                });
+
+                                canvas.draw_rect(&brush);
+                            }))
+                        });
+                    }
+                    render_object.into()
+                });
+        }
+
+        let child_widget = child_widget.into_widget();
+
+        let root_widget = MockRenderWidget::new("RootWidget");
+        {
+            root_widget
+                .mock
+                .borrow_mut()
+                .expect_children()
+                .returning_st(move || vec![child_widget.clone()]);
+
+            root_widget
+                .mock
+                .borrow_mut()
+                .expect_create_render_object()
+                .returning(|| {
+                    let render_object = MockRenderObject::new("RootRenderObject");
+                    {
+                        let mut render_object_mock = render_object.mock.lock();
+
+                        render_object_mock.expect_layout().returning(|mut ctx, _| {
+                            let mut children = ctx.iter_children_mut();
+
+                            while let Some(mut child) = children.next() {
+                                child.compute_layout(Size::new(100.0, 100.0));
+                                child.set_offset(Offset::new(10.0, 10.0));
+                            }
+
+                            Size::new(100.0, 100.0)
+                        });
+
+                        render_object_mock.expect_paint().returning(|size| {
+                            Some(paint_canvas(size, |mut canvas| {
+                                let brush = canvas.add_paint(Paint {
+                                    color: Color::from_rgb((0.0, 0.0, 1.0)),
+                                    ..Paint::default()
+                                });
+
+                                canvas.draw_rect(&brush);
+
+                                let clip = canvas.add_paint(Paint::default());
+
+                                canvas.start_layer_at(
+                                    Rect::new(0.0, 0.0, 50.0, 50.0),
+                                    &clip,
+                                    Shape::Rect,
+                                );
+                            }))
+                        });
+                    }
+                    render_object.into()
+                });
+        }
+
+        let mut engine = Engine::builder().with_root(root_widget).build();
+
+        engine.update().unwrap();
+
+        let renderer = TinySkiaRenderer::new();
+
+        let framebuffer = Framebuffer::new(1, 1);
+
+        let view = renderer
+            .bind(
+                RenderViewId::default(),
+                &framebuffer,
+                Size::new(100.0, 100.0),
+            )
+            .unwrap();
+
+        assert_eq!(framebuffer.size(), Size::new(100.0, 100.0));
+
+        renderer.redraw(&engine).unwrap();
+
+        view.render();
+
+        assert_eq!(framebuffer.pixel(5, 5), Some([0, 0, 255, 255]));
+
+        assert_eq!(
+            framebuffer.pixel(30, 30),
+            Some([255, 0, 0, 255]),
+            "should have drawn the child over its parent"
+        );
+
+        assert_eq!(
+            framebuffer.pixel(70, 70),
+            Some([0, 0, 255, 255]),
+            "should have clipped the child to its parent's tail layer"
+        );
+
+        assert!(
+            framebuffer.encode_png().unwrap().starts_with(b"\x89PNG"),
+            "should have encoded the framebuffer as a png"
+        );
+    }
+}
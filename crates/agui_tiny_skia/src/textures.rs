@@ -0,0 +1,26 @@
+use agui_core::unit::TextureId;
+use tiny_skia::Pixmap;
+
+use crate::framebuffer::pixmap_from_rgba8;
+
+#[derive(Default)]
+pub struct TinySkiaTextures {
+    textures: Vec<Pixmap>,
+}
+
+impl TinySkiaTextures {
+    /// Adds a texture from straight (non-premultiplied) RGBA pixel data.
+    ///
+    /// Returns `None` if the texture is empty or if the data does not match its size.
+    pub fn add_texture(&mut self, width: u32, height: u32, rgba: &[u8]) -> Option<TextureId> {
+        let pixmap = pixmap_from_rgba8(width, height, rgba)?;
+
+        self.textures.push(pixmap);
+
+        Some(TextureId::new(self.textures.len() - 1))
+    }
+
+    pub fn get(&self, texture_id: TextureId) -> Option<&Pixmap> {
+        texture_id.idx().and_then(|idx| self.textures.get(idx))
+    }
+}
@@ -0,0 +1,79 @@
+use std::sync::Arc;
+
+use agui_core::{engine::Engine, engine::EngineError, unit::Size};
+use agui_primitives::text::fonts::FontRegistry;
+use agui_renderer::{BufferedView, BufferedViewRenderer};
+use parking_lot::Mutex;
+use tiny_skia::{Pixmap, Transform};
+
+use crate::{
+    framebuffer::{create_pixmap, Framebuffer},
+    raster::Rasterizer,
+    textures::TinySkiaTextures,
+};
+
+/// Renders a view into a [`Framebuffer`].
+pub type TinySkiaViewRenderer = BufferedViewRenderer<TinySkiaView>;
+
+/// A view whose frames are drawn into a back buffer, and are only copied to the framebuffer when
+/// the view is rendered.
+pub struct TinySkiaView {
+    fonts: Arc<Mutex<FontRegistry>>,
+    textures: Arc<Mutex<TinySkiaTextures>>,
+
+    framebuffer: Framebuffer,
+    scene: Pixmap,
+}
+
+impl TinySkiaView {
+    pub(crate) fn new(
+        fonts: Arc<Mutex<FontRegistry>>,
+        textures: Arc<Mutex<TinySkiaTextures>>,
+        framebuffer: Framebuffer,
+    ) -> Self {
+        let scene = create_pixmap(framebuffer.width(), framebuffer.height());
+
+        Self {
+            fonts,
+            textures,
+
+            framebuffer,
+            scene,
+        }
+    }
+}
+
+impl BufferedView for TinySkiaView {
+    fn redraw(&mut self, engine: &Engine) -> Result<(), EngineError> {
+        self.scene.fill(tiny_skia::Color::TRANSPARENT);
+
+        let Some(root_id) = engine.render_objects().root() else {
+            return Ok(());
+        };
+
+        let fonts = self.fonts.lock();
+        let textures = self.textures.lock();
+
+        Rasterizer {
+            fonts: &fonts,
+            textures: &textures,
+        }
+        .draw_render_object(
+            &mut self.scene,
+            engine.render_objects(),
+            root_id,
+            Transform::identity(),
+        )
+    }
+
+    fn resize(&mut self, size: Size) {
+        let (width, height) = (size.width.floor() as u32, size.height.floor() as u32);
+
+        self.framebuffer.resize(width, height);
+        self.scene = create_pixmap(width, height);
+    }
+
+    fn present(&self) {
+        self.framebuffer.present(&self.scene);
+    }
+}
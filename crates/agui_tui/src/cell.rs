@@ -0,0 +1,109 @@
+use agui_core::unit::Color;
+
+/// A single character cell of a terminal.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cell {
+    pub symbol: char,
+
+    /// The color of the symbol, or `None` to use the terminal's default.
+    pub foreground: Option<Color>,
+
+    /// The color behind the symbol, or `None` to use the terminal's default.
+    pub background: Option<Color>,
+}
+
+impl Cell {
+    pub const EMPTY: Self = Self {
+        symbol: ' ',
+
+        foreground: None,
+        background: None,
+    };
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self::EMPTY
+    }
+}
+
+/// An in-memory grid of cells that frames are drawn into, row by row.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CellBuffer {
+    width: usize,
+    height: usize,
+
+    cells: Vec<Cell>,
+}
+
+impl CellBuffer {
+    /// Creates a buffer of the given size in cells, filled with empty cells.
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+
+            cells: vec![Cell::EMPTY; width * height],
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&Cell> {
+        if x < self.width && y < self.height {
+            self.cells.get(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut Cell> {
+        if x < self.width && y < self.height {
+            self.cells.get_mut(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the cells of a row, from left to right.
+    pub fn row(&self, y: usize) -> Option<&[Cell]> {
+        if y < self.height {
+            Some(&self.cells[y * self.width..(y + 1) * self.width])
+        } else {
+            None
+        }
+    }
+
+    /// Resizes the buffer, clearing every cell.
+    pub fn resize(&mut self, width: usize, height: usize) {
+        self.width = width;
+        self.height = height;
+
+        self.cells.clear();
+        self.cells.resize(width * height, Cell::EMPTY);
+    }
+
+    /// Resets every cell to an empty one.
+    pub fn clear(&mut self) {
+        self.cells.fill(Cell::EMPTY);
+    }
+
+    /// Returns the symbols of the buffer, with each row on its own line. Trailing spaces are
+    /// trimmed from each row.
+    pub fn to_text(&self) -> String {
+        (0..self.height)
+            .filter_map(|y| self.row(y))
+            .map(|row| {
+                let line = row.iter().map(|cell| cell.symbol).collect::<String>();
+
+                format!("{}\n", line.trim_end())
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,121 @@
+use agui_core::engine::Engine;
+use agui_gestures::event::{Key, KeyEvent, KeyState, PointerEventKind};
+use crossterm::event::{Event, KeyCode, KeyEventKind, MouseEventKind};
+
+use crate::input::{cell_center, TuiInput};
+
+/// Converts a terminal key event into an agui key event, if the key is one agui knows of.
+///
+/// Most terminals only report key presses unless keyboard enhancements are enabled, in which
+/// case releases are reported as well.
+pub fn to_key_event(event: &crossterm::event::KeyEvent) -> Option<KeyEvent> {
+    let key = match event.code {
+        KeyCode::Char(ch) => Key::Character(ch),
+
+        KeyCode::Enter => Key::Enter,
+        KeyCode::Tab => Key::Tab,
+        KeyCode::Backspace => Key::Backspace,
+        KeyCode::Delete => Key::Delete,
+        KeyCode::Esc => Key::Escape,
+
+        KeyCode::Left => Key::ArrowLeft,
+        KeyCode::Right => Key::ArrowRight,
+        KeyCode::Up => Key::ArrowUp,
+        KeyCode::Down => Key::ArrowDown,
+
+        KeyCode::Home => Key::Home,
+        KeyCode::End => Key::End,
+
+        _ => return None,
+    };
+
+    let state = match event.kind {
+        KeyEventKind::Press | KeyEventKind::Repeat => KeyState::Pressed,
+        KeyEventKind::Release => KeyState::Released,
+    };
+
+    Some(KeyEvent { key, state })
+}
+
+/// Converts the kind of a terminal mouse event into the kind of pointer event it represents.
+/// Hovering and scrolling have no equivalent, and return `None`.
+pub fn to_pointer_event_kind(kind: MouseEventKind) -> Option<PointerEventKind> {
+    match kind {
+        MouseEventKind::Down(_) => Some(PointerEventKind::Down),
+        MouseEventKind::Drag(_) => Some(PointerEventKind::Move),
+        MouseEventKind::Up(_) => Some(PointerEventKind::Up),
+
+        MouseEventKind::Moved
+        | MouseEventKind::ScrollDown
+        | MouseEventKind::ScrollUp
+        | MouseEventKind::ScrollLeft
+        | MouseEventKind::ScrollRight => None,
+    }
+}
+
+impl TuiInput {
+    /// Sends a terminal event to the widgets of the engine. Events which aren't input, such as
+    /// the terminal being resized, are ignored.
+    ///
+    /// Mouse events are only reported by the terminal once mouse capture is enabled.
+    pub fn handle_event(&mut self, engine: &Engine, event: &Event) {
+        match event {
+            Event::Key(event) => {
+                if let Some(event) = to_key_event(event) {
+                    self.handle_key(engine, event);
+                }
+            }
+
+            Event::Mouse(event) => {
+                if let Some(kind) = to_pointer_event_kind(event.kind) {
+                    self.handle_pointer(engine, kind, cell_center(event.column, event.row));
+                }
+            }
+
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use agui_gestures::event::{Key, KeyEvent, PointerEventKind};
+    use crossterm::event::{KeyCode, KeyEventKind, KeyModifiers, MouseButton, MouseEventKind};
+
+    use super::{to_key_event, to_pointer_event_kind};
+
+    #[test]
+    pub fn converts_terminal_events() {
+        assert_eq!(
+            to_key_event(&crossterm::event::KeyEvent::new(
+                KeyCode::Char('a'),
+                KeyModifiers::NONE
+            )),
+            Some(KeyEvent::pressed(Key::Character('a')))
+        );
+
+        let mut released = crossterm::event::KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+
+        released.kind = KeyEventKind::Release;
+
+        assert_eq!(
+            to_key_event(&released),
+            Some(KeyEvent::released(Key::Escape))
+        );
+
+        assert_eq!(
+            to_key_event(&crossterm::event::KeyEvent::new(
+                KeyCode::F(1),
+                KeyModifiers::NONE
+            )),
+            None
+        );
+
+        assert_eq!(
+            to_pointer_event_kind(MouseEventKind::Drag(MouseButton::Left)),
+            Some(PointerEventKind::Move)
+        );
+
+        assert_eq!(to_pointer_event_kind(MouseEventKind::ScrollUp), None);
+    }
+}
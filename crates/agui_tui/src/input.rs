@@ -0,0 +1,91 @@
+use agui_core::{
+    engine::Engine,
+    unit::{HitTestResult, Offset},
+};
+use agui_gestures::{
+    dispatch::{dispatch_key_event, dispatch_pointer_event},
+    event::{KeyEvent, PointerEvent, PointerEventKind},
+};
+
+/// Returns the position of the center of a cell, which is where pointer events within it occur.
+pub fn cell_center(column: u16, row: u16) -> Offset {
+    Offset::new(f32::from(column) + 0.5, f32::from(row) + 0.5)
+}
+
+/// Routes terminal input to the [`Listener`](agui_gestures::listener::Listener)s of an engine.
+///
+/// Once the pointer is pressed, every pointer event is sent to the widgets that were under it
+/// until it's released. Since terminals have no focus, key events are sent to the widgets under
+/// the position the pointer was last seen at, starting at the top left cell.
+pub struct TuiInput {
+    position: Offset,
+
+    pressed: Option<HitTestResult>,
+}
+
+impl Default for TuiInput {
+    fn default() -> Self {
+        Self {
+            position: cell_center(0, 0),
+
+            pressed: None,
+        }
+    }
+}
+
+impl TuiInput {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sends a pointer event to the widgets at the given position. Moving the pointer is only
+    /// reported while it's pressed.
+    ///
+    /// The engine must be updated afterwards for any changes to take effect.
+    pub fn handle_pointer(&mut self, engine: &Engine, kind: PointerEventKind, position: Offset) {
+        let delta = position - self.position;
+
+        self.position = position;
+
+        match kind {
+            PointerEventKind::Down => {
+                let result = engine.hit_test(position);
+
+                dispatch_pointer_event(&result, PointerEvent::new(kind, position));
+
+                self.pressed = Some(result);
+            }
+
+            PointerEventKind::Move => {
+                if let Some(result) = &self.pressed {
+                    dispatch_pointer_event(
+                        result,
+                        PointerEvent::new(kind, position).with_delta(delta),
+                    );
+                }
+            }
+
+            PointerEventKind::Up | PointerEventKind::Cancel => {
+                if let Some(result) = self.pressed.take() {
+                    dispatch_pointer_event(&result, PointerEvent::new(kind, position));
+                }
+            }
+        }
+    }
+
+    /// Sends a key event to the widgets under the pointer.
+    ///
+    /// The engine must be updated afterwards for any changes to take effect.
+    pub fn handle_key(&mut self, engine: &Engine, event: KeyEvent) {
+        dispatch_key_event(&engine.hit_test(self.position), event);
+    }
+}
+
+impl std::fmt::Debug for TuiInput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TuiInput")
+            .field("position", &self.position)
+            .field("pressed", &self.pressed.is_some())
+            .finish()
+    }
+}
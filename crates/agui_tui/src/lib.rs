@@ -0,0 +1,27 @@
+mod cell;
+mod input;
+mod painter;
+mod renderer;
+mod screen;
+mod surface;
+mod text_layout;
+mod view;
+
+#[cfg(feature = "crossterm")]
+mod events;
+#[cfg(feature = "crossterm")]
+mod writer;
+
+pub use cell::{Cell, CellBuffer};
+pub use input::{cell_center, TuiInput};
+pub use painter::CellPainter;
+pub use renderer::TuiRenderer;
+pub use screen::TuiScreen;
+pub use surface::TuiSurface;
+pub use text_layout::TuiTextLayoutDelegate;
+pub use view::{TuiView, TuiViewRenderer};
+
+#[cfg(feature = "crossterm")]
+pub use events::{to_key_event, to_pointer_event_kind};
+#[cfg(feature = "crossterm")]
+pub use writer::{to_crossterm_color, CrosstermWriter};
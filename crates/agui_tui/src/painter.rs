@@ -0,0 +1,484 @@
+use agui_core::{
+    engine::EngineError,
+    render::{
+        canvas::{Canvas, CanvasCommand, CanvasLayer},
+        Paint, RenderObject, RenderObjectId,
+    },
+    unit::{Color, Offset, Rect, Shape},
+    util::tree::Tree,
+};
+use agui_primitives::text::glyph_layout::layout_text;
+use lyon::{
+    algorithms::hit_test::hit_test_path,
+    math::point,
+    path::{FillRule, Path},
+};
+
+use crate::{cell::CellBuffer, text_layout::CellMetrics};
+
+/// The tolerance used when flattening curves to test whether a cell is within a shape.
+const TOLERANCE: f32 = 0.1;
+
+/// Paints canvases into a [`CellBuffer`], where each unit of the canvas is a single cell.
+///
+/// A cell is covered by a shape if its center is, in which case its background takes on the
+/// color of the shape. Text replaces the symbols of the cells it covers, keeping their
+/// background. Translucent colors are blended with the cells beneath them, but other blend modes
+/// aren't supported.
+pub struct CellPainter<'p> {
+    buffer: &'p mut CellBuffer,
+
+    /// The shapes of the layers currently being painted into, in cell coordinates.
+    clips: Vec<Path>,
+
+    /// The opacity of the layers currently being painted into. Since layers aren't composited
+    /// separately, this is applied to each command within them instead.
+    opacity: f32,
+}
+
+impl<'p> CellPainter<'p> {
+    pub fn new(buffer: &'p mut CellBuffer) -> Self {
+        Self {
+            buffer,
+
+            clips: Vec::new(),
+
+            opacity: 1.0,
+        }
+    }
+
+    /// Paints a render object and all of its descendants.
+    ///
+    /// Descendants are painted into the tail layer of their ancestors' canvases, so that they're
+    /// clipped along with it.
+    pub fn paint_render_object(
+        &mut self,
+        render_objects: &Tree<RenderObjectId, RenderObject>,
+        render_object_id: RenderObjectId,
+        origin: Offset,
+    ) -> Result<(), EngineError> {
+        let render_object = render_objects
+            .get(render_object_id)
+            .ok_or(EngineError::MissingRenderObject { render_object_id })?;
+
+        let origin = origin + render_object.offset();
+
+        let mut paint_children = |painter: &mut Self| {
+            for child_id in render_objects
+                .get_children(render_object_id)
+                .into_iter()
+                .flatten()
+            {
+                painter.paint_render_object(render_objects, *child_id, origin)?;
+            }
+
+            Ok(())
+        };
+
+        match render_object.paint()? {
+            Some(canvas) => self.paint_canvas(&canvas, origin, &mut paint_children),
+            None => paint_children(self),
+        }
+    }
+
+    /// Paints a canvas at the given position, followed by the `contents` within its tail layer.
+    pub fn paint_canvas<E>(
+        &mut self,
+        canvas: &Canvas,
+        origin: Offset,
+        contents: &mut dyn FnMut(&mut Self) -> Result<(), E>,
+    ) -> Result<(), E> {
+        for command in &canvas.head {
+            self.paint_command(&canvas.paints, command, origin);
+        }
+
+        for layer in &canvas.children {
+            self.paint_layer(&canvas.paints, layer, origin, &mut |_| Ok(()))?;
+        }
+
+        match &canvas.tail {
+            Some(tail) => self.paint_layer(&canvas.paints, tail, origin, contents),
+            None => contents(self),
+        }
+    }
+
+    /// Paints a layer clipped to the shape of its style.
+    fn paint_layer<E>(
+        &mut self,
+        paints: &[Paint],
+        layer: &CanvasLayer,
+        origin: Offset,
+        contents: &mut dyn FnMut(&mut Self) -> Result<(), E>,
+    ) -> Result<(), E> {
+        let paint = paints
+            .get(layer.style.paint_idx)
+            .cloned()
+            .unwrap_or_else(|| {
+                tracing::warn!(
+                    paint_idx = layer.style.paint_idx,
+                    "layer references a paint that does not exist"
+                );
+
+                Paint::default()
+            });
+
+        let origin = origin + layer.offset;
+
+        let clip_rect = self.to_cells(Rect::from(layer.canvas.size), origin);
+
+        self.clips.push(layer.style.shape.build_path(clip_rect));
+
+        let opacity = self.opacity;
+
+        self.opacity *= paint.color.alpha.clamp(0.0, 1.0);
+
+        let result = self.paint_canvas(&layer.canvas, origin, contents);
+
+        self.opacity = opacity;
+
+        self.clips.pop();
+
+        result
+    }
+
+    fn paint_command(&mut self, paints: &[Paint], command: &CanvasCommand, origin: Offset) {
+        if command.is_noop() {
+            return;
+        }
+
+        match command {
+            CanvasCommand::Shape {
+                paint_idx,
+                rect,
+                shape,
+            } => {
+                let Some(paint) = get_paint(paints, *paint_idx) else {
+                    return;
+                };
+
+                self.fill_shape(self.to_cells(*rect, origin), shape, paint.color);
+            }
+
+            CanvasCommand::Texture { texture_id, .. } => {
+                tracing::warn!(?texture_id, "textures are not supported in the terminal");
+            }
+
+            CanvasCommand::Text {
+                paint_idx,
+                rect,
+                text_style: _,
+                text,
+            } => {
+                let Some(paint) = get_paint(paints, *paint_idx) else {
+                    return;
+                };
+
+                let alpha = paint.color.alpha.clamp(0.0, 1.0) * self.opacity;
+
+                if alpha <= 0.0 {
+                    return;
+                }
+
+                let (left, top) = (rect.left + origin.x, rect.top + origin.y);
+
+                layout_text(&CellMetrics, rect.width, text, |positioned| {
+                    // Like shapes, characters are drawn into the cell whose center they cover.
+                    let x = (left + positioned.x - 0.5).ceil();
+                    let y = (top + positioned.baseline - 0.5).ceil();
+
+                    if x < 0.0 || y < 0.0 || !self.is_visible(x as usize, y as usize) {
+                        return;
+                    }
+
+                    if let Some(cell) = self.buffer.get_mut(x as usize, y as usize) {
+                        // Control characters would move the terminal's cursor, so they're drawn
+                        // as spaces.
+                        cell.symbol = if positioned.ch.is_control() {
+                            ' '
+                        } else {
+                            positioned.ch
+                        };
+                        cell.foreground = Some(blend(cell.background, paint.color, alpha));
+                    }
+                });
+            }
+
+            command => {
+                tracing::error!("unknown command: {:?}", command);
+            }
+        }
+    }
+
+    /// Sets the background of every visible cell whose center is within the shape.
+    fn fill_shape(&mut self, rect: Rect, shape: &Shape, color: Color) {
+        let alpha = color.alpha.clamp(0.0, 1.0) * self.opacity;
+
+        if alpha <= 0.0 {
+            return;
+        }
+
+        let path = (!matches!(shape, Shape::Rect)).then(|| shape.build_path(rect));
+
+        let (left, top) = (rect.left.max(0.0), rect.top.max(0.0));
+
+        let right = (rect.left + rect.width).min(self.buffer.width() as f32);
+        let bottom = (rect.top + rect.height).min(self.buffer.height() as f32);
+
+        for y in (top.floor() as usize)..(bottom.ceil().max(0.0) as usize) {
+            for x in (left.floor() as usize)..(right.ceil().max(0.0) as usize) {
+                let (center_x, center_y) = (x as f32 + 0.5, y as f32 + 0.5);
+
+                let is_within = match &path {
+                    Some(path) => hit_test_path(
+                        &point(center_x, center_y),
+                        path.iter(),
+                        FillRule::NonZero,
+                        TOLERANCE,
+                    ),
+
+                    None => {
+                        center_x >= rect.left
+                            && center_x < rect.left + rect.width
+                            && center_y >= rect.top
+                            && center_y < rect.top + rect.height
+                    }
+                };
+
+                if !is_within || !self.is_visible(x, y) {
+                    continue;
+                }
+
+                let Some(cell) = self.buffer.get_mut(x, y) else {
+                    continue;
+                };
+
+                if alpha >= 1.0 {
+                    // Opaque shapes hide anything that was drawn beneath them.
+                    cell.symbol = ' ';
+                    cell.foreground = None;
+                } else if cell.foreground.is_some() {
+                    cell.foreground = Some(blend(cell.foreground, color, alpha));
+                }
+
+                cell.background = Some(blend(cell.background, color, alpha));
+            }
+        }
+    }
+
+    /// Returns whether the cell is within the clip of every layer being painted into.
+    fn is_visible(&self, x: usize, y: usize) -> bool {
+        let center = point(x as f32 + 0.5, y as f32 + 0.5);
+
+        self.clips
+            .iter()
+            .all(|clip| hit_test_path(&center, clip.iter(), FillRule::NonZero, TOLERANCE))
+    }
+
+    /// Moves the rect into the coordinates of the buffer, limiting unbounded rects (such as those
+    /// of the root render object) to its area.
+    fn to_cells(&self, rect: Rect, origin: Offset) -> Rect {
+        let rect = Rect::new(
+            rect.left + origin.x,
+            rect.top + origin.y,
+            rect.width,
+            rect.height,
+        );
+
+        if rect.width.is_finite() && rect.height.is_finite() {
+            return rect;
+        }
+
+        let (left, top) = (rect.left.max(0.0), rect.top.max(0.0));
+
+        let right = (rect.left + rect.width).min(self.buffer.width() as f32);
+        let bottom = (rect.top + rect.height).min(self.buffer.height() as f32);
+
+        Rect::new(left, top, (right - left).max(0.0), (bottom - top).max(0.0))
+    }
+}
+
+fn get_paint(paints: &[Paint], paint_idx: usize) -> Option<&Paint> {
+    let paint = paints.get(paint_idx);
+
+    if paint.is_none() {
+        tracing::warn!(paint_idx, "command references a paint that does not exist");
+    }
+
+    paint
+}
+
+/// Blends the color over the one beneath it. Since terminals have no notion of transparency,
+/// the result is always opaque, and colors drawn over the terminal's default are used as-is.
+fn blend(beneath: Option<Color>, color: Color, alpha: f32) -> Color {
+    let Some(beneath) = beneath else {
+        return Color::from_rgb(color.as_rgb());
+    };
+
+    let mix = |from: f32, to: f32| from + (to - from) * alpha;
+
+    Color::from_rgb((
+        mix(beneath.red, color.red),
+        mix(beneath.green, color.green),
+        mix(beneath.blue, color.blue),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use agui_core::{
+        render::{canvas::Canvas, CanvasPainter, Paint},
+        unit::{Color, Offset, Rect, Shape, Size, TextStyle},
+    };
+
+    use crate::cell::CellBuffer;
+
+    use super::CellPainter;
+
+    const RED: Color = Color::from_rgb((1.0, 0.0, 0.0));
+    const WHITE: Color = Color::from_rgb((1.0, 1.0, 1.0));
+
+    fn paint_canvas(size: Size, func: impl FnOnce(CanvasPainter)) -> Canvas {
+        let mut canvas = Canvas {
+            size,
+
+            paints: Vec::default(),
+
+            head: Vec::default(),
+            children: Vec::default(),
+            tail: None,
+        };
+
+        func(CanvasPainter::begin(&mut canvas));
+
+        canvas
+    }
+
+    fn paint(canvas: &Canvas, width: usize, height: usize) -> CellBuffer {
+        let mut buffer = CellBuffer::new(width, height);
+
+        CellPainter::new(&mut buffer)
+            .paint_canvas::<()>(canvas, Offset::ZERO, &mut |_| Ok(()))
+            .unwrap();
+
+        buffer
+    }
+
+    #[test]
+    pub fn paints_shapes_as_backgrounds() {
+        let canvas = paint_canvas(Size::new(6.0, 4.0), |mut canvas| {
+            let brush = canvas.add_paint(Paint {
+                color: RED,
+                ..Paint::default()
+            });
+
+            canvas.draw_rect_at(Rect::new(1.0, 1.0, 2.0, 2.0), &brush);
+        });
+
+        let buffer = paint(&canvas, 6, 4);
+
+        for (x, y) in [(1, 1), (2, 1), (1, 2), (2, 2)] {
+            assert_eq!(buffer.get(x, y).unwrap().background, Some(RED));
+        }
+
+        for (x, y) in [(0, 0), (3, 1), (1, 3)] {
+            assert_eq!(
+                buffer.get(x, y).unwrap().background,
+                None,
+                "should not have painted ({x}, {y})"
+            );
+        }
+    }
+
+    #[test]
+    pub fn paints_text_as_characters() {
+        let canvas = paint_canvas(Size::new(6.0, 3.0), |mut canvas| {
+            let background = canvas.add_paint(Paint {
+                color: RED,
+                ..Paint::default()
+            });
+
+            canvas.draw_rect(&background);
+
+            let brush = canvas.add_paint(Paint {
+                color: WHITE,
+                ..Paint::default()
+            });
+
+            canvas.draw_text_at(
+                Rect::new(1.0, 1.0, 3.0, 2.0),
+                &brush,
+                TextStyle::default(),
+                "hello",
+            );
+        });
+
+        let buffer = paint(&canvas, 6, 3);
+
+        assert_eq!(buffer.to_text(), "\n hel\n lo\n");
+
+        let cell = buffer.get(1, 1).unwrap();
+
+        assert_eq!(cell.foreground, Some(WHITE));
+        assert_eq!(
+            cell.background,
+            Some(RED),
+            "should have kept the background"
+        );
+    }
+
+    #[test]
+    pub fn clips_to_layers() {
+        let canvas = paint_canvas(Size::new(5.0, 5.0), |mut canvas| {
+            let clip = canvas.add_paint(Paint::default());
+
+            canvas.layer_at(
+                Rect::new(0.0, 0.0, 5.0, 5.0),
+                &clip,
+                Shape::Circle,
+                |mut canvas| {
+                    let brush = canvas.add_paint(Paint {
+                        color: RED,
+                        ..Paint::default()
+                    });
+
+                    canvas.draw_rect(&brush);
+                },
+            );
+        });
+
+        let buffer = paint(&canvas, 5, 5);
+
+        assert_eq!(buffer.get(2, 2).unwrap().background, Some(RED));
+        assert_eq!(buffer.get(2, 0).unwrap().background, Some(RED));
+
+        assert_eq!(
+            buffer.get(0, 0).unwrap().background,
+            None,
+            "should have clipped the corners of the layer"
+        );
+    }
+
+    #[test]
+    pub fn blends_translucent_colors() {
+        let canvas = paint_canvas(Size::new(1.0, 1.0), |mut canvas| {
+            let background = canvas.add_paint(Paint {
+                color: WHITE,
+                ..Paint::default()
+            });
+
+            canvas.draw_rect(&background);
+
+            let brush = canvas.add_paint(Paint {
+                color: Color::from_rgba((0.0, 0.0, 0.0, 0.5)),
+                ..Paint::default()
+            });
+
+            canvas.draw_rect(&brush);
+        });
+
+        assert_eq!(
+            paint(&canvas, 1, 1).get(0, 0).unwrap().background,
+            Some(Color::from_rgb((0.5, 0.5, 0.5)))
+        );
+    }
+}
@@ -0,0 +1,210 @@
+use std::sync::Arc;
+
+use agui_core::{
+    engine::{Engine, EngineError},
+    unit::{Offset, Size},
+};
+use agui_renderer::{BufferedViews, RenderViewId, Renderer, ViewRenderer};
+
+use crate::{
+    cell::CellBuffer, painter::CellPainter, surface::TuiSurface,
+    text_layout::TuiTextLayoutDelegate, view::TuiView,
+};
+
+/// Renders views into [`TuiSurface`]s, where each unit of layout is a single terminal cell.
+///
+/// Shapes are painted as the background of the cells they cover, and text as the characters of
+/// the cells it covers. Widgets should be laid out with the delegate from
+/// [`TuiRenderer::text_layout_delegate`], so that text is measured in cells.
+#[derive(Default)]
+pub struct TuiRenderer {
+    views: BufferedViews<TuiView>,
+}
+
+impl TuiRenderer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a delegate that measures text in cells, to be given to a
+    /// [`TextLayoutController`](agui_primitives::text::layout_controller::TextLayoutController).
+    pub fn text_layout_delegate(&self) -> TuiTextLayoutDelegate {
+        TuiTextLayoutDelegate
+    }
+
+    /// Redraws every bound view from the engine's render tree. The new frames are not presented
+    /// until each view is rendered.
+    pub fn redraw(&self, engine: &Engine) -> Result<(), EngineError> {
+        self.views.redraw(engine)
+    }
+
+    /// Paints the engine's render tree into the buffer, on top of whatever it already contains.
+    pub fn draw(&self, engine: &Engine, buffer: &mut CellBuffer) -> Result<(), EngineError> {
+        let Some(root_id) = engine.render_objects().root() else {
+            return Ok(());
+        };
+
+        CellPainter::new(buffer).paint_render_object(engine.render_objects(), root_id, Offset::ZERO)
+    }
+}
+
+impl Renderer for TuiRenderer {
+    type Target = TuiSurface;
+
+    fn bind(
+        &self,
+        render_view_id: RenderViewId,
+        target: &Self::Target,
+        size: Size,
+    ) -> Result<Arc<dyn ViewRenderer>, Box<dyn std::error::Error>> {
+        Ok(self
+            .views
+            .bind(render_view_id, TuiView::new(target.clone(), size)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{rc::Rc, sync::Arc};
+
+    use agui_core::{
+        engine::Engine,
+        unit::{Color, Size},
+    };
+    use agui_gestures::{
+        event::{Key, KeyEvent, PointerEvent, PointerEventKind},
+        listener::Listener,
+    };
+    use agui_inheritance::InheritancePlugin;
+    use agui_macros::build;
+    use agui_primitives::{
+        align::Center, colored_box::ColoredBox, text::layout_controller::TextLayoutController,
+        text::Text,
+    };
+    use agui_renderer::{RenderViewId, Renderer};
+    use parking_lot::Mutex;
+
+    use crate::{input::cell_center, surface::TuiSurface, TuiInput, TuiScreen};
+
+    use super::TuiRenderer;
+
+    #[test]
+    pub fn renders_widgets_into_cells() {
+        let renderer = TuiRenderer::new();
+
+        let pointer_events = Arc::new(Mutex::new(Vec::<PointerEvent>::new()));
+        let key_events = Arc::new(Mutex::new(Vec::<KeyEvent>::new()));
+
+        let mut engine = Engine::builder()
+            .add_plugin(InheritancePlugin::default())
+            .with_root(TextLayoutController {
+                delegate: Rc::new(renderer.text_layout_delegate()),
+
+                child: Some(build! {
+                    <TuiScreen> {
+                        size: Size::new(12.0, 3.0),
+
+                        child: <Center> {
+                            child: <Listener> {
+                                on_pointer: {
+                                    let pointer_events = Arc::clone(&pointer_events);
+
+                                    move |event: PointerEvent| pointer_events.lock().push(event)
+                                },
+
+                                on_key: {
+                                    let key_events = Arc::clone(&key_events);
+
+                                    move |event: KeyEvent| key_events.lock().push(event)
+                                },
+
+                                child: <ColoredBox> {
+                                    color: Color::from_rgb((0.0, 0.0, 1.0)),
+
+                                    child: <Text> {
+                                        text: "hello".into(),
+                                    },
+                                },
+                            },
+                        },
+                    }
+                }),
+            })
+            .build();
+
+        engine.update().unwrap();
+
+        let surface = TuiSurface::new();
+
+        let view = renderer
+            .bind(RenderViewId::default(), &surface, Size::new(12.0, 3.0))
+            .unwrap();
+
+        renderer.redraw(&engine).unwrap();
+
+        view.render();
+
+        assert_eq!(
+            surface.to_text(),
+            "\n   hello\n\n",
+            "should have laid out the text in cells"
+        );
+
+        let buffer = surface.buffer();
+
+        assert_eq!(
+            buffer.get(3, 1).unwrap().background,
+            Some(Color::from_rgb((0.0, 0.0, 1.0)))
+        );
+
+        assert_eq!(
+            buffer.get(8, 1).unwrap().background,
+            None,
+            "should have sized the box to the text"
+        );
+
+        let mut input = TuiInput::new();
+
+        input.handle_pointer(&engine, PointerEventKind::Down, cell_center(0, 0));
+        input.handle_pointer(&engine, PointerEventKind::Up, cell_center(0, 0));
+
+        assert!(
+            pointer_events.lock().is_empty(),
+            "should not have received a tap outside of the listener"
+        );
+
+        input.handle_pointer(&engine, PointerEventKind::Down, cell_center(4, 1));
+        input.handle_pointer(&engine, PointerEventKind::Move, cell_center(9, 1));
+        input.handle_pointer(&engine, PointerEventKind::Up, cell_center(9, 1));
+
+        assert_eq!(
+            pointer_events
+                .lock()
+                .iter()
+                .map(|event| (event.kind, event.local_position.x))
+                .collect::<Vec<_>>(),
+            [
+                (PointerEventKind::Down, 1.0),
+                (PointerEventKind::Move, 6.0),
+                (PointerEventKind::Up, 6.0),
+            ],
+            "should have sent the drag to the widget it started on"
+        );
+
+        input.handle_key(&engine, KeyEvent::pressed(Key::Enter));
+
+        assert!(
+            key_events.lock().is_empty(),
+            "should have sent keys to where the pointer was released"
+        );
+
+        input.handle_pointer(&engine, PointerEventKind::Down, cell_center(5, 1));
+        input.handle_key(&engine, KeyEvent::pressed(Key::Enter));
+
+        assert_eq!(
+            *key_events.lock(),
+            [KeyEvent::pressed(Key::Enter)],
+            "should have sent keys to the widget under the pointer"
+        );
+    }
+}
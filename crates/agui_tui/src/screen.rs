@@ -0,0 +1,44 @@
+use agui_core::{
+    unit::{Constraints, IntrinsicDimension, Offset, Size},
+    widget::Widget,
+};
+use agui_elements::layout::{IntrinsicSizeContext, LayoutContext, WidgetLayout};
+use agui_macros::LayoutWidget;
+
+/// Forces its child to the size of the terminal, in cells.
+///
+/// This should be the root of the widget tree, and be rebuilt with the new size whenever the
+/// terminal is resized.
+#[derive(LayoutWidget, Debug)]
+pub struct TuiScreen {
+    pub size: Size,
+
+    #[prop(into)]
+    pub child: Widget,
+}
+
+impl WidgetLayout for TuiScreen {
+    fn children(&self) -> Vec<Widget> {
+        Vec::from([self.child.clone()])
+    }
+
+    fn intrinsic_size(
+        &self,
+        _: &mut IntrinsicSizeContext,
+        dimension: IntrinsicDimension,
+        _: f32,
+    ) -> f32 {
+        self.size.extent(dimension.axis())
+    }
+
+    fn layout(&self, ctx: &mut LayoutContext, _: Constraints) -> Size {
+        let mut children = ctx.iter_children_mut();
+
+        while let Some(mut child) = children.next() {
+            child.compute_layout(Constraints::tight(self.size));
+            child.set_offset(Offset::ZERO);
+        }
+
+        self.size
+    }
+}
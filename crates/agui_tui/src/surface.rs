@@ -0,0 +1,46 @@
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+
+use crate::cell::CellBuffer;
+
+/// An in-memory terminal screen that rendered frames are presented to.
+///
+/// Surfaces are cheap to clone, and every clone refers to the same cells, so one may be bound to
+/// a view while another is used to read back what it presented, or to write it to a terminal.
+#[derive(Clone, Default)]
+pub struct TuiSurface {
+    buffer: Arc<Mutex<CellBuffer>>,
+}
+
+impl TuiSurface {
+    /// Creates a surface that is empty until a frame is presented to it.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cells of the most recently presented frame.
+    pub fn buffer(&self) -> CellBuffer {
+        self.buffer.lock().clone()
+    }
+
+    /// Returns the symbols of the most recently presented frame, with each row on its own line.
+    pub fn to_text(&self) -> String {
+        self.buffer.lock().to_text()
+    }
+
+    pub(crate) fn present(&self, frame: &CellBuffer) {
+        self.buffer.lock().clone_from(frame);
+    }
+}
+
+impl std::fmt::Debug for TuiSurface {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let buffer = self.buffer.lock();
+
+        f.debug_struct("TuiSurface")
+            .field("width", &buffer.width())
+            .field("height", &buffer.height())
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,57 @@
+use std::borrow::Cow;
+
+use agui_core::unit::{Constraints, IntrinsicDimension, Size, TextStyle};
+use agui_primitives::text::{
+    glyph_layout::{intrinsic_size, layout_text, GlyphMetrics},
+    layout_controller::TextLayoutDelegate,
+};
+
+/// Measures text in cells, so that widgets are laid out on the terminal's grid. The size of the
+/// text style is ignored, since every character takes up exactly one cell.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TuiTextLayoutDelegate;
+
+impl TextLayoutDelegate for TuiTextLayoutDelegate {
+    fn compute_intrinsic_size(
+        &self,
+        _: &TextStyle,
+        text: Cow<'static, str>,
+        dimension: IntrinsicDimension,
+        cross_axis: f32,
+    ) -> f32 {
+        intrinsic_size(&CellMetrics, &text, dimension, cross_axis)
+    }
+
+    fn compute_layout(
+        &self,
+        _: &TextStyle,
+        text: Cow<'static, str>,
+        constraints: Constraints,
+    ) -> Size {
+        layout_text(&CellMetrics, constraints.max_width(), &text, |_| {})
+    }
+}
+
+/// Places every character in its own cell, with the baseline at the top of the line so that it
+/// is also the row the character is drawn in.
+pub(crate) struct CellMetrics;
+
+impl GlyphMetrics for CellMetrics {
+    type GlyphId = char;
+
+    fn glyph_id(&self, ch: char) -> Self::GlyphId {
+        ch
+    }
+
+    fn h_advance(&self, _: Self::GlyphId) -> f32 {
+        1.0
+    }
+
+    fn ascent(&self) -> f32 {
+        0.0
+    }
+
+    fn line_height(&self) -> f32 {
+        1.0
+    }
+}
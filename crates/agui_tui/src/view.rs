@@ -0,0 +1,65 @@
+use agui_core::{
+    engine::{Engine, EngineError},
+    unit::{Offset, Size},
+};
+use agui_renderer::{BufferedView, BufferedViewRenderer};
+
+use crate::{cell::CellBuffer, painter::CellPainter, surface::TuiSurface};
+
+/// Renders a view into a [`TuiSurface`].
+pub type TuiViewRenderer = BufferedViewRenderer<TuiView>;
+
+/// A view whose frames are painted into a buffer, and are only presented to the surface when the
+/// view is rendered.
+pub struct TuiView {
+    surface: TuiSurface,
+
+    frame: CellBuffer,
+}
+
+impl TuiView {
+    pub(crate) fn new(surface: TuiSurface, size: Size) -> Self {
+        let (width, height) = to_cells(size);
+
+        Self {
+            surface,
+
+            frame: CellBuffer::new(width, height),
+        }
+    }
+}
+
+impl BufferedView for TuiView {
+    fn redraw(&mut self, engine: &Engine) -> Result<(), EngineError> {
+        self.frame.clear();
+
+        if let Some(root_id) = engine.render_objects().root() {
+            CellPainter::new(&mut self.frame).paint_render_object(
+                engine.render_objects(),
+                root_id,
+                Offset::ZERO,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Resizes the view to the given size in cells.
+    fn resize(&mut self, size: Size) {
+        let (width, height) = to_cells(size);
+
+        self.frame.resize(width, height);
+    }
+
+    fn present(&self) {
+        self.surface.present(&self.frame);
+    }
+}
+
+/// Converts a size into whole cells, dropping any partial ones.
+fn to_cells(size: Size) -> (usize, usize) {
+    (
+        size.width.max(0.0).floor() as usize,
+        size.height.max(0.0).floor() as usize,
+    )
+}
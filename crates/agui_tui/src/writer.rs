@@ -0,0 +1,165 @@
+use std::io::{self, Write};
+
+use agui_core::unit::Color;
+use crossterm::{
+    cursor::MoveTo,
+    queue,
+    style::{Print, ResetColor, SetBackgroundColor, SetForegroundColor},
+    terminal::{Clear, ClearType},
+};
+
+use crate::cell::{Cell, CellBuffer};
+
+/// Writes cell buffers to a terminal using crossterm.
+///
+/// Only the cells that changed since the previous buffer are written, so the same writer should
+/// be used for every frame. The terminal should already be in raw mode and on the alternate
+/// screen, which the writer leaves to the caller.
+pub struct CrosstermWriter<W: Write> {
+    writer: W,
+
+    previous: Option<CellBuffer>,
+}
+
+impl<W: Write> CrosstermWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+
+            previous: None,
+        }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    /// Forces the next buffer to be written in its entirety, such as after the terminal has
+    /// been cleared by something else.
+    pub fn invalidate(&mut self) {
+        self.previous = None;
+    }
+
+    /// Writes the cells that changed since the previous buffer, then flushes the terminal.
+    pub fn write(&mut self, buffer: &CellBuffer) -> io::Result<()> {
+        let previous = self.previous.take().filter(|previous| {
+            previous.width() == buffer.width() && previous.height() == buffer.height()
+        });
+
+        if previous.is_none() {
+            queue!(self.writer, ResetColor, Clear(ClearType::All))?;
+        }
+
+        // Avoid repeating the escape codes for the cursor and colors when they haven't changed.
+        let mut cursor = None;
+        let mut colors = None;
+
+        for y in 0..buffer.height() {
+            for x in 0..buffer.width() {
+                let Some(cell) = buffer.get(x, y) else {
+                    continue;
+                };
+
+                let previous_cell = match &previous {
+                    Some(previous) => previous.get(x, y),
+                    None => Some(&Cell::EMPTY),
+                };
+
+                if previous_cell == Some(cell) {
+                    continue;
+                }
+
+                if cursor != Some((x, y)) {
+                    queue!(
+                        self.writer,
+                        MoveTo(
+                            u16::try_from(x).unwrap_or(u16::MAX),
+                            u16::try_from(y).unwrap_or(u16::MAX)
+                        )
+                    )?;
+                }
+
+                if colors != Some((cell.foreground, cell.background)) {
+                    queue!(
+                        self.writer,
+                        SetForegroundColor(to_crossterm_color(cell.foreground)),
+                        SetBackgroundColor(to_crossterm_color(cell.background)),
+                    )?;
+
+                    colors = Some((cell.foreground, cell.background));
+                }
+
+                queue!(self.writer, Print(cell.symbol))?;
+
+                cursor = Some((x + 1, y));
+            }
+        }
+
+        queue!(self.writer, ResetColor)?;
+
+        self.writer.flush()?;
+
+        self.previous = Some(buffer.clone());
+
+        Ok(())
+    }
+}
+
+/// Converts a cell color into a true color, or the terminal's default if there is none.
+pub fn to_crossterm_color(color: Option<Color>) -> crossterm::style::Color {
+    let Some(color) = color else {
+        return crossterm::style::Color::Reset;
+    };
+
+    let to_u8 = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+
+    crossterm::style::Color::Rgb {
+        r: to_u8(color.red),
+        g: to_u8(color.green),
+        b: to_u8(color.blue),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use agui_core::unit::Color;
+
+    use crate::cell::CellBuffer;
+
+    use super::CrosstermWriter;
+
+    #[test]
+    pub fn writes_changed_cells() {
+        let mut buffer = CellBuffer::new(4, 2);
+
+        let mut writer = CrosstermWriter::new(Vec::new());
+
+        writer.write(&buffer).unwrap();
+
+        let first_frame = writer.into_inner();
+
+        assert!(
+            first_frame.starts_with(b"\x1B[0m\x1B[2J"),
+            "should have cleared the terminal"
+        );
+
+        let mut writer = CrosstermWriter::new(Vec::new());
+
+        writer.write(&buffer).unwrap();
+        writer.writer.clear();
+
+        let cell = buffer.get_mut(2, 1).unwrap();
+
+        cell.symbol = 'x';
+        cell.background = Some(Color::from_rgb((1.0, 0.0, 0.0)));
+
+        writer.write(&buffer).unwrap();
+
+        let output = String::from_utf8(writer.into_inner()).unwrap();
+
+        assert_eq!(
+            output, "\x1B[2;3H\x1B[39m\x1B[48;2;255;0;0mx\x1B[0m",
+            "should have only written the cell that changed"
+        );
+    }
+}
@@ -1,4 +1,9 @@
+use std::sync::Arc;
+
+use ab_glyph::FontArc;
 use agui_core::unit::Font;
+use agui_primitives::text::fonts::FontRegistry;
+use parking_lot::Mutex;
 use rustc_hash::FxHashMap;
 use vello::{
     fello::{raw::FontRef, FontKey, Setting},
@@ -9,6 +14,9 @@ pub struct VelloFonts {
     glyph_context: GlyphContext,
     fonts: FxHashMap<Font, FontRef<'static>>,
 
+    /// The same fonts, loaded for the backends that lay out and draw text with `ab_glyph`.
+    registry: Arc<Mutex<FontRegistry>>,
+
     default_font: Option<Font>,
 }
 
@@ -18,6 +26,8 @@ impl Default for VelloFonts {
             glyph_context: GlyphContext::new(),
             fonts: FxHashMap::default(),
 
+            registry: Arc::default(),
+
             default_font: None,
         }
     }
@@ -44,16 +54,28 @@ impl VelloFonts {
             .new_provider(font, font_id, ppem, hint, variations)
     }
 
-    pub fn add_font(&mut self, font: FontRef<'static>) -> Font {
-        let font_id = Font::by_id(self.fonts.len());
+    /// Loads a font from its raw data, registering it with the [`FontRegistry`] as well so that
+    /// the returned [`Font`] resolves to the same typeface in any backend sharing the registry.
+    pub fn add_font(
+        &mut self,
+        font_data: &'static [u8],
+    ) -> Result<Font, Box<dyn std::error::Error>> {
+        let font_ref = FontRef::new(font_data)?;
+        let font = FontArc::try_from_slice(font_data)?;
 
-        self.fonts.insert(font_id, font);
+        let font_id = self.registry.lock().add_font(font);
+
+        self.fonts.insert(font_id, font_ref);
 
         if self.default_font.is_none() {
             self.default_font = Some(font_id);
         }
 
-        font_id
+        Ok(font_id)
+    }
+
+    pub fn registry(&self) -> &Arc<Mutex<FontRegistry>> {
+        &self.registry
     }
 
     pub fn get(&self, font: Font) -> Option<FontRef<'static>> {
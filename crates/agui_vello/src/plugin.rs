@@ -3,11 +3,11 @@ use std::sync::{mpsc, Arc};
 
 use agui_core::plugin::context::{PluginAfterUpdateContext, PluginBeforeUpdateContext};
 use agui_core::{plugin::Plugin, unit::Font};
+use agui_primitives::text::fonts::FontRegistry;
 use agui_renderer::RenderViewId;
 use parking_lot::Mutex;
 use raw_window_handle::{HasRawDisplayHandle, HasRawWindowHandle};
 use rustc_hash::FxHashMap;
-use vello::glyph::fello::raw::{FontRef, ReadError};
 
 use crate::{
     event::VelloPluginEvent, fonts::VelloFonts, handle::VelloHandle,
@@ -54,10 +54,18 @@ impl VelloPlugin {
     //     &self.fonts
     // }
 
-    pub fn add_font(&self, font_data: Vec<u8>) -> Result<Font, ReadError> {
-        let font_ref = FontRef::new(Box::leak(Box::new(font_data)))?;
+    /// Loads a TrueType font from its raw data. The first font that is added is used to draw
+    /// any text which doesn't specify one.
+    pub fn add_font(&self, font_data: Vec<u8>) -> Result<Font, Box<dyn std::error::Error>> {
+        self.fonts
+            .lock()
+            .add_font(Box::leak(font_data.into_boxed_slice()))
+    }
 
-        Ok(self.fonts.lock().add_font(font_ref))
+    /// The fonts the plugin draws with, which may be shared with other backends (such as
+    /// `PdfExporter::with_fonts`) so that they draw text with the same fonts.
+    pub fn fonts(&self) -> Arc<Mutex<FontRegistry>> {
+        Arc::clone(self.fonts.lock().registry())
     }
 }
 
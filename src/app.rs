@@ -1,6 +1,12 @@
-use std::{sync::mpsc, time::Instant};
+use std::{
+    sync::mpsc,
+    time::{Duration, Instant},
+};
 
-use agui_core::{engine::Engine, widget::IntoWidget};
+use agui_core::{
+    engine::Engine, plugin::context::ContextPlugins, scheduler::FrameSchedulerPlugin,
+    timer::TimerPlugin, widget::IntoWidget,
+};
 use agui_inheritance::InheritancePlugin;
 use agui_renderer::{DefaultRenderer, RenderViewPlugin};
 #[cfg(feature = "vello")]
@@ -9,13 +15,16 @@ use agui_vello::VelloPlugin;
 use agui_winit::WinitPlugin;
 use winit::window::Window;
 use winit::{
-    event::Event as WinitEvent,
+    event::{Event as WinitEvent, StartCause},
     event_loop::{ControlFlow, EventLoopBuilder},
 };
 
 #[cfg(not(all(feature = "vello", feature = "winit")))]
 compile_error!("app feature requires both winit and vello to be enabled");
 
+/// The time between frames while any ticker is active.
+const FRAME_INTERVAL: Duration = Duration::from_micros(16_667);
+
 pub fn run_app(root: impl IntoWidget) -> Result<(), Box<dyn std::error::Error>> {
     let (update_notifier_tx, update_notifier_rx) = mpsc::channel();
 
@@ -30,6 +39,8 @@ pub fn run_app(root: impl IntoWidget) -> Result<(), Box<dyn std::error::Error>>
 
     let engine = Engine::builder()
         .with_notifier(update_notifier_tx.clone())
+        .add_plugin(FrameSchedulerPlugin::default().with_notifier(update_notifier_tx.clone()))
+        .add_plugin(TimerPlugin::default())
         .add_plugin(InheritancePlugin::default())
         .add_plugin(RenderViewPlugin::default());
 
@@ -59,6 +70,8 @@ pub fn run_app(root: impl IntoWidget) -> Result<(), Box<dyn std::error::Error>>
         }
     });
 
+    let mut last_frame_at = Instant::now();
+
     event_loop.run(move |event, window_target, control_flow| {
         *control_flow = ControlFlow::Wait;
 
@@ -86,13 +99,21 @@ pub fn run_app(root: impl IntoWidget) -> Result<(), Box<dyn std::error::Error>>
                 requires_update = true;
             }
 
+            WinitEvent::NewEvents(StartCause::ResumeTimeReached { .. }) => {
+                requires_update = true;
+            }
+
             _ => (),
         }
 
         if requires_update {
             let now = Instant::now();
 
-            engine.update();
+            last_frame_at = now;
+
+            if let Err(err) = engine.update() {
+                tracing::error!("failed to update: {}", err);
+            }
 
             tracing::info!("updated in: {:?}", Instant::now().duration_since(now));
 
@@ -101,5 +122,23 @@ pub fn run_app(root: impl IntoWidget) -> Result<(), Box<dyn std::error::Error>>
             //     window.request_redraw();
             // });
         }
+
+        // Keep producing frames for as long as any ticker is active
+        let next_frame_at = engine
+            .plugins()
+            .get::<FrameSchedulerPlugin>()
+            .is_some_and(FrameSchedulerPlugin::has_scheduled_frame)
+            .then_some(last_frame_at + FRAME_INTERVAL);
+
+        // Wake up in time for the next timer to fire, if it comes before the next frame
+        let next_timer_at = engine
+            .plugins()
+            .get::<TimerPlugin>()
+            .and_then(TimerPlugin::next_timeout)
+            .map(|timeout| Instant::now() + timeout);
+
+        if let Some(wake_at) = next_frame_at.into_iter().chain(next_timer_at).min() {
+            *control_flow = ControlFlow::WaitUntil(wake_at);
+        }
     });
 }
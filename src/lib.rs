@@ -17,6 +17,18 @@ pub use agui_winit as winit;
 #[cfg(feature = "vello")]
 pub use agui_vello as vello;
 
+#[cfg(feature = "tiny-skia")]
+pub use agui_tiny_skia as tiny_skia;
+
+#[cfg(feature = "svg")]
+pub use agui_svg as svg;
+
+#[cfg(feature = "pdf")]
+pub use agui_pdf as pdf;
+
+#[cfg(feature = "tui")]
+pub use agui_tui as tui;
+
 pub mod prelude {
     pub use agui_core::{callback::*, element, query::*, render::*, unit::*, widget::*};
     pub use agui_elements::{layout::*, paint::*, stateful::*, stateless::*};